@@ -8,7 +8,6 @@ mod logging;
 mod options;
 mod verbose;
 
-use clap::Parser;
 use color_eyre::eyre;
 
 /// Main entry point for `cargo-bumpversion`.
@@ -21,18 +20,18 @@ async fn main() -> eyre::Result<()> {
     let bin_name = env!("CARGO_BIN_NAME");
     let bin_name = bin_name.strip_prefix("cargo-").unwrap_or(bin_name);
 
-    let args: Vec<String> = std::env::args_os()
-        // skip executable name
-        .skip(1)
-        // skip our own cargo-* command name
-        .skip_while(|arg| {
-            let arg = arg.as_os_str();
-            arg == bin_name || arg == "cargo"
-        })
-        .map(|s| s.to_string_lossy().to_string())
+    let args: Vec<std::ffi::OsString> = std::iter::once(bin_name.into())
+        .chain(
+            std::env::args_os()
+                // skip executable name
+                .skip(1)
+                // skip our own cargo-* command name
+                .skip_while(|arg| {
+                    let arg = arg.as_os_str();
+                    arg == bin_name || arg == "cargo"
+                }),
+        )
         .collect();
 
-    let mut options = options::Options::parse_from(args);
-    options::fix(&mut options);
-    common::bumpversion(options).await
+    common::bumpversion(args).await
 }
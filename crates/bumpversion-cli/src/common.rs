@@ -3,19 +3,25 @@
 //! Sets up logging, loads configuration, and orchestrates the bump process.
 use crate::options;
 use bumpversion::{
-    config,
-    vcs::{TagAndRevision, VersionControlSystem, git::GitRepository},
+    config, context, conventional,
+    diagnostics::DiagnosticExt,
+    show,
+    vcs::{
+        GitBackend, TagAndRevision, VersionControlSystem, git::GitRepository,
+        gitoxide::GitoxideRepository, hg::MercurialRepository,
+    },
 };
+use codespan_reporting::term::termcolor::StandardStream;
 use color_eyre::eyre::{self, WrapErr};
 
 /// Ensure the working directory is clean, unless `allow_dirty` is set.
 ///
 /// # Errors
 /// Returns an error if the repo is dirty and not allowed by config.
-async fn check_is_dirty(
-    repo: &GitRepository,
-    config: &config::FinalizedConfig,
-) -> eyre::Result<()> {
+async fn check_is_dirty<VCS>(repo: &VCS, config: &config::FinalizedConfig) -> eyre::Result<()>
+where
+    VCS: VersionControlSystem,
+{
     let dirty_files = repo.dirty_files().await?;
     if !config.global.allow_dirty && !dirty_files.is_empty() {
         eyre::bail!(
@@ -31,52 +37,297 @@ async fn check_is_dirty(
     Ok(())
 }
 
+/// Get the latest tag and revision of the `repo`.
+///
+/// Warns if the version of the latest tag does not match the configured version.
+async fn latest_tag_and_revision<VCS>(
+    repo: &VCS,
+    config: &config::FinalizedConfig,
+) -> eyre::Result<TagAndRevision>
+where
+    VCS: VersionControlSystem,
+{
+    let TagAndRevision {
+        tool,
+        tag,
+        revision,
+    } = repo
+        .latest_tag_and_revision(
+            &context::expand_variables(&config.global.tag_name, &config.context),
+            &config.global.parse_version_pattern,
+        )
+        .await?;
+
+    tracing::debug!(?tag, "current");
+    tracing::debug!(?revision, "current");
+
+    let configured_version = &config.global.current_version;
+    let actual_version = tag.as_ref().map(|tag| &tag.current_version).cloned();
+
+    // if both versions are present, they should match
+    if let Some((configured_version, actual_version)) =
+        configured_version.as_ref().zip(actual_version.as_ref())
+        && configured_version != actual_version
+    {
+        tracing::warn!(
+            "version {configured_version} from config does not match last tagged version ({actual_version})",
+        );
+    }
+
+    Ok(TagAndRevision {
+        tool,
+        tag,
+        revision,
+    })
+}
+
+/// Print the resolved configuration, versions, and context.
+///
+/// # Errors
+/// Returns an error if the new version cannot be computed or a field does not exist.
+fn show_state(
+    config: &config::FinalizedConfig,
+    components: &config::VersionComponentConfigs,
+    tag_and_revision: &TagAndRevision,
+    new_version: Option<&str>,
+    show_options: &options::ShowOptions,
+) -> eyre::Result<()> {
+    let bump = if let Some(new_version) = new_version {
+        Some(bumpversion::Bump::NewVersion(new_version))
+    } else {
+        show_options
+            .increment
+            .as_deref()
+            .map(bumpversion::Bump::Component)
+    };
+    let state = show::state(config, components, tag_and_revision, bump)?;
+    let selected = show::select(&state, &show_options.fields)?;
+    println!("{}", show::render(&selected, show_options.format));
+    Ok(())
+}
+
+/// Check that all configured files and the config file contain the current version.
+///
+/// # Errors
+/// Returns an error if the current version is missing or mismatched in any of the files.
+async fn check_current_version<VCS>(
+    config: &mut config::FinalizedConfig,
+    components: &config::VersionComponentConfigs,
+    repo: &VCS,
+    config_file: &config::ConfigFile,
+    tag_and_revision: &TagAndRevision,
+    printer: &bumpversion::diagnostics::Printer<StandardStream>,
+) -> eyre::Result<()>
+where
+    VCS: VersionControlSystem,
+{
+    let file_map =
+        bumpversion::files::resolve_files_from_config(config, components, Some(repo.path()))?;
+    let diagnostics = bumpversion::check::check(
+        &bumpversion::fs::LocalFileSystem,
+        config,
+        file_map,
+        Some(config_file),
+        components,
+        tag_and_revision,
+        printer,
+    )
+    .await?;
+    for diagnostic in &diagnostics {
+        printer.emit(diagnostic)?;
+    }
+    let num_errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if num_errors > 0 {
+        eyre::bail!("found {num_errors} problem(s) with the current version");
+    }
+    Ok(())
+}
+
+/// Load the config file given in the `options` or find it in `dir`.
+///
+/// # Errors
+/// Returns an error if the config cannot be loaded or no config file was found.
+async fn load_config(
+    options: &options::Options,
+    dir: &std::path::Path,
+    printer: &bumpversion::diagnostics::Printer<StandardStream>,
+) -> eyre::Result<(config::ConfigFile, config::FinalizedConfig)> {
+    let cli_overrides = options::global_cli_config(options)?;
+    let loaded = if let Some(config_file) = options.config_file.as_deref() {
+        bumpversion::load_config(
+            config_file,
+            options.config_format,
+            &cli_overrides,
+            options.strict,
+            printer,
+        )
+        .await?
+    } else {
+        bumpversion::find_config(dir, &cli_overrides, options.strict, printer)
+            .await?
+            .ok_or(eyre::eyre!("missing config file"))?
+    };
+    Ok(loaded)
+}
+
+/// The version control system of a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Vcs {
+    Git,
+    Mercurial,
+}
+
+/// Detect the version control system of the repository containing `dir`.
+///
+/// The closest directory containing a `.git` or `.hg` entry decides.
+/// Defaults to git if neither is found.
+fn detect_vcs(dir: &std::path::Path) -> Vcs {
+    for ancestor in dir.ancestors() {
+        if ancestor.join(".git").exists() {
+            return Vcs::Git;
+        }
+        if ancestor.join(".hg").is_dir() {
+            return Vcs::Mercurial;
+        }
+    }
+    Vcs::Git
+}
+
 /// Entry point for the `bumpversion` CLI.
 ///
-/// Processes command-line `options`, loads the project config, and performs the bump.
-pub async fn bumpversion(mut options: options::Options) -> eyre::Result<()> {
+/// Parses the command-line `args`, loads the project config, and performs the bump.
+///
+/// The arguments are parsed in two passes: the first pass finds the config,
+/// the second pass adds a bump subcommand for each configured version component.
+pub async fn bumpversion(args: Vec<std::ffi::OsString>) -> eyre::Result<()> {
     let start = std::time::Instant::now();
 
+    let options = options::parse_partial(&args);
+
     let color_choice = options.color_choice.unwrap_or(termcolor::ColorChoice::Auto);
     let use_color = crate::logging::setup(options.log_level, color_choice)?;
     colored::control::set_override(use_color);
 
     let cwd = std::env::current_dir().wrap_err("could not determine current working dir")?;
     let dir = options.dir.as_deref().unwrap_or(&cwd).canonicalize()?;
-    let repo = GitRepository::open(&dir)?;
 
     let printer = bumpversion::diagnostics::Printer::stderr(color_choice.into());
 
-    let cli_overrides = options::global_cli_config(&options)?;
-    let (config_file_path, mut config) = bumpversion::find_config(&dir, &cli_overrides, &printer)
-        .await?
-        .ok_or(eyre::eyre!("missing config file"))?;
+    // without a config, only the builtin subcommands (and help) are available
+    let loaded = match load_config(&options, &dir, &printer).await {
+        Err(err) if options.bump.is_some() => return Err(err),
+        loaded => loaded,
+    };
+    let components = loaded
+        .as_ref()
+        .map(|(_, config)| config::version::version_component_configs(config))
+        .unwrap_or_default();
 
-    let components = config::version::version_component_configs(&config);
-    let (bump, cli_files) = options::parse_positional_arguments(&mut options, &components)?;
+    // parse again, now that the version components are known
+    let options = options::parse(&args, &components).unwrap_or_else(|err| err.exit());
 
-    let TagAndRevision { tag, revision } = repo
-        .latest_tag_and_revision(
-            &config.global.tag_name,
-            &config.global.parse_version_pattern,
-        )
-        .await?;
+    if let Some(options::Command::Completions(completions_options)) = &options.command {
+        let mut command = options::command(&components);
+        let bin_name = command.get_name().to_string();
+        clap_complete::generate(
+            completions_options.shell,
+            &mut command,
+            bin_name,
+            &mut std::io::stdout(),
+        );
+        return Ok(());
+    }
 
-    tracing::debug!(?tag, "current");
-    tracing::debug!(?revision, "current");
+    let (config_file_path, config) = loaded?;
 
-    let configured_version = &config.global.current_version;
-    let actual_version = tag.as_ref().map(|tag| &tag.current_version).cloned();
+    let vcs = detect_vcs(&dir);
+    tracing::debug!(?vcs, "detected");
+    match vcs {
+        Vcs::Git if options.git_backend == Some(GitBackend::Gitoxide) => {
+            let repo = GitoxideRepository::open(&dir)?;
+            run(
+                repo,
+                &options,
+                config,
+                config_file_path,
+                components,
+                &printer,
+            )
+            .await?;
+        }
+        Vcs::Git => {
+            let repo = GitRepository::open(&dir)?;
+            run(
+                repo,
+                &options,
+                config,
+                config_file_path,
+                components,
+                &printer,
+            )
+            .await?;
+        }
+        Vcs::Mercurial => {
+            let repo = MercurialRepository::open(&dir)?;
+            run(
+                repo,
+                &options,
+                config,
+                config_file_path,
+                components,
+                &printer,
+            )
+            .await?;
+        }
+    }
 
-    // if both versions are present, they should match
-    if let Some((configured_version, actual_version)) =
-        configured_version.as_ref().zip(actual_version.as_ref())
-    {
-        if configured_version != actual_version {
-            tracing::warn!(
-                "version {configured_version} from config does not match last tagged version ({actual_version})",
+    tracing::info!(elapsed = ?start.elapsed(), "done");
+    Ok(())
+}
+
+/// Run the subcommand given in the `options` using the VCS `repo`.
+async fn run<VCS>(
+    repo: VCS,
+    options: &options::Options,
+    mut config: config::FinalizedConfig,
+    config_file_path: config::ConfigFile,
+    components: config::VersionComponentConfigs,
+    printer: &bumpversion::diagnostics::Printer<StandardStream>,
+) -> eyre::Result<()>
+where
+    VCS: VersionControlSystem + std::fmt::Debug + Send + Sync + 'static,
+{
+    let tag_and_revision = latest_tag_and_revision(&repo, &config).await?;
+    match &options.command {
+        Some(options::Command::Show(show_options)) => {
+            return show_state(
+                &config,
+                &components,
+                &tag_and_revision,
+                options.new_version.as_deref(),
+                show_options,
             );
         }
+        Some(options::Command::ShowBump) => {
+            let preview = show::preview_bumps(&config, &components, &tag_and_revision)?;
+            println!("{preview}");
+            return Ok(());
+        }
+        Some(options::Command::Check) => {
+            return check_current_version(
+                &mut config,
+                &components,
+                &repo,
+                &config_file_path,
+                &tag_and_revision,
+                printer,
+            )
+            .await;
+        }
+        Some(
+            options::Command::Auto | options::Command::Bump(_) | options::Command::Completions(_),
+        )
+        | None => {}
     }
 
     check_is_dirty(&repo, &config).await?;
@@ -89,16 +340,30 @@ pub async fn bumpversion(mut options: options::Options) -> eyre::Result<()> {
         config.global.excluded_paths = Some(file_map.keys().cloned().collect());
     }
 
-    if !cli_files.is_empty() {
-        // file_map.extend(cli_files);
-        // config.add_files(files);
-        config.global.included_paths = Some(cli_files);
+    if !options.files.is_empty() {
+        config.global.included_paths = Some(options.files.clone());
     }
 
+    let auto_bump = if matches!(options.command, Some(options::Command::Auto)) {
+        let Some(auto_bump) =
+            conventional::select_since_latest_tag(&repo, &tag_and_revision, &config.global).await?
+        else {
+            println!("no commits since the latest tag require a version bump");
+            return Ok(());
+        };
+        println!("{auto_bump}");
+        Some(auto_bump)
+    } else {
+        None
+    };
+
     let bump = if let Some(new_version) = options.new_version.as_deref() {
         bumpversion::Bump::NewVersion(new_version)
+    } else if let Some(auto_bump) = &auto_bump {
+        auto_bump.bump()
     } else {
-        let bump = bump
+        let bump = options
+            .bump
             .as_deref()
             .ok_or_else(|| eyre::eyre!("missing version component to bump"))?;
         bumpversion::Bump::Component(bump)
@@ -113,15 +378,14 @@ pub async fn bumpversion(mut options: options::Options) -> eyre::Result<()> {
     let logger = crate::verbose::Logger::new(verbosity).dry_run(config.global.dry_run);
     let manager = bumpversion::BumpVersion {
         repo,
+        fs: bumpversion::fs::LocalFileSystem,
         config,
         logger,
-        tag_and_revision: TagAndRevision { tag, revision },
+        tag_and_revision,
         file_map,
         components,
         config_file: Some(config_file_path),
     };
     manager.bump(bump).await?;
-
-    tracing::info!(elapsed = ?start.elapsed(), "done");
     Ok(())
 }
@@ -2,6 +2,7 @@
 //!
 //! Defines flags, positional arguments, and configuration overrides via environment.
 use bumpversion::config;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use color_eyre::eyre;
 use std::path::PathBuf;
 
@@ -17,25 +18,67 @@ impl Invert for Option<bool> {
     }
 }
 
-/// Which version component to bump: `major`, `minor`, or `patch`.
-#[derive(clap::Parser, Debug, Clone)]
-pub enum BumpCommand {
-    #[clap(name = "major")]
-    Major,
-    #[clap(name = "minor")]
-    Minor,
-    #[clap(name = "patch")]
-    Patch,
+/// Subcommands besides bumping one of the configured version components.
+///
+/// The bump subcommands are built at runtime from the configured version components,
+/// see [`command`].
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum Command {
+    #[clap(
+        name = "show",
+        about = "show the resolved configuration, versions, and context"
+    )]
+    Show(ShowOptions),
+    #[clap(
+        name = "show-bump",
+        about = "show the next version when bumping each version component"
+    )]
+    ShowBump,
+    #[clap(
+        name = "check",
+        about = "check that all configured files and the config file contain the current version"
+    )]
+    Check,
+    #[clap(
+        name = "auto",
+        about = "bump the version component selected from the conventional commits since the latest tag"
+    )]
+    Auto,
+    #[clap(
+        name = "completions",
+        about = "generate shell completions, including the bump subcommands of the configured version components"
+    )]
+    Completions(CompletionsOptions),
+    /// Version component to bump, before the configured components are known.
+    #[clap(external_subcommand)]
+    Bump(Vec<String>),
 }
 
-impl AsRef<str> for BumpCommand {
-    fn as_ref(&self) -> &str {
-        match self {
-            BumpCommand::Major => "major",
-            BumpCommand::Minor => "minor",
-            BumpCommand::Patch => "patch",
-        }
-    }
+/// Options for the `show` subcommand.
+#[derive(clap::Args, Debug, Clone)]
+pub struct ShowOptions {
+    #[arg(
+        long = "format",
+        default_value = "default",
+        help = "output format (one of default, yaml, json)"
+    )]
+    pub format: bumpversion::show::OutputFormat,
+
+    #[arg(
+        long = "increment",
+        help = "version component to increment when showing the new version"
+    )]
+    pub increment: Option<String>,
+
+    #[arg(help = "fields to show, e.g. `current_version` or `scm_info.branch_name`")]
+    pub fields: Vec<String>,
+}
+
+/// Options for the `completions` subcommand.
+#[derive(clap::Args, Debug, Clone)]
+pub struct CompletionsOptions {
+    #[arg(help = "shell to generate completions for")]
+    pub shell: clap_complete::Shell,
 }
 
 /// Logging flags to `#[command(flatten)]` into your CLI
@@ -86,6 +129,21 @@ pub struct Options {
     )]
     pub config_file: Option<PathBuf>,
 
+    #[clap(
+        long = "config-format",
+        help = "format of the config file (one of bumpversion-toml, pyproject-toml, bumpversion-cfg, setup-cfg, cargo-toml). Inferred from the file name by default",
+        env = "BUMPVERSION_CONFIG_FORMAT",
+        requires = "config_file"
+    )]
+    pub config_format: Option<config::ConfigFormat>,
+
+    #[clap(
+        long = "git-backend",
+        help = "implementation used to access git repositories (one of command, gitoxide). Defaults to running git commands",
+        env = "BUMPVERSION_GIT_BACKEND"
+    )]
+    pub git_backend: Option<bumpversion::vcs::GitBackend>,
+
     #[arg(
         long = "color",
         env = "BUMPVERSION_COLOR",
@@ -93,6 +151,13 @@ pub struct Options {
     )]
     pub color_choice: Option<termcolor::ColorChoice>,
 
+    #[clap(
+        long = "strict",
+        help = "fail on unknown keys in the config file instead of warning about them",
+        env = "BUMPVERSION_STRICT"
+    )]
+    pub strict: bool,
+
     #[command(flatten)]
     pub verbosity: Verbosity,
 
@@ -302,11 +367,46 @@ pub struct Options {
     )]
     pub commit_args: Option<String>,
 
+    #[clap(
+        long = "push",
+        help = "push the commit and tag to the remote",
+        env = "BUMPVERSION_PUSH",
+        action = clap::ArgAction::SetTrue,
+    )]
+    pub push: Option<bool>,
+
+    #[clap(
+        long = "no-push",
+        help = "do not push the commit and tag to the remote",
+        env = "BUMPVERSION_NO_PUSH",
+        action = clap::ArgAction::SetTrue,
+    )]
+    pub no_push: Option<bool>,
+
+    #[clap(
+        long = "push-remote",
+        help = "remote to push to",
+        env = "BUMPVERSION_PUSH_REMOTE"
+    )]
+    pub push_remote: Option<String>,
+
+    #[clap(
+        long = "push-args",
+        help = "extra arguments to push command",
+        env = "BUMPVERSION_PUSH_ARGS"
+    )]
+    pub push_args: Option<String>,
+
     #[clap(subcommand)]
-    pub bump: Option<BumpCommand>,
+    pub command: Option<Command>,
 
-    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
-    pub args: Vec<String>,
+    /// Version component to bump.
+    #[clap(skip)]
+    pub bump: Option<String>,
+
+    /// Files to update instead of the configured files.
+    #[clap(skip)]
+    pub files: Vec<PathBuf>,
 }
 
 pub fn fix(options: &mut Options) {
@@ -334,6 +434,8 @@ pub fn fix(options: &mut Options) {
         &mut options.no_tag,
         &mut options.sign_tags,
         &mut options.no_sign_tag,
+        &mut options.push,
+        &mut options.no_push,
     ] {
         if *boolean_option != Some(true) {
             *boolean_option = None;
@@ -341,45 +443,98 @@ pub fn fix(options: &mut Options) {
     }
 }
 
-pub fn parse_positional_arguments(
-    options: &mut Options,
-    components: &config::VersionComponentConfigs,
-) -> eyre::Result<(Option<String>, Vec<PathBuf>)> {
-    let mut cli_files = vec![];
-    let mut bump: Option<String> = options
-        .bump
-        .as_ref()
-        .map(AsRef::as_ref)
-        .map(ToString::to_string);
+/// Build a bump subcommand for a version component.
+fn bump_command(name: &str, spec: &config::VersionComponentSpec) -> clap::Command {
+    let about = if let Some(calver_format) = &spec.calver_format {
+        format!("bump the {name} version component to the date of today ({calver_format})")
+    } else if spec.values.is_empty() {
+        format!("bump the {name} version component")
+    } else {
+        format!(
+            "bump the {name} version component (one of {})",
+            spec.values.join(", ")
+        )
+    };
+    clap::Command::new(name.to_string()).about(about).arg(
+        clap::Arg::new("files")
+            .value_name("FILE")
+            .num_args(0..)
+            .value_parser(clap::value_parser!(PathBuf))
+            .help("only replace the version in these files"),
+    )
+}
 
-    // first, check for invalid flags
-    for arg in &options.args {
-        if arg.starts_with("--") {
-            eyre::bail!("unknown flag {arg:?}");
-        }
-    }
+/// Build the CLI command with a bump subcommand for each of the version `components`.
+pub fn command(components: &config::VersionComponentConfigs) -> clap::Command {
+    let command = Options::command()
+        .allow_external_subcommands(false)
+        .external_subcommand_value_parser(None);
+    let builtin: Vec<String> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    components
+        .iter()
+        .filter(|(name, _)| {
+            let is_builtin = builtin.contains(name);
+            if is_builtin {
+                tracing::warn!("version component {name:?} conflicts with the {name:?} command");
+            }
+            !is_builtin
+        })
+        .fold(command, |command, (name, spec)| {
+            command.subcommand(bump_command(name, spec))
+        })
+}
 
-    if !options.args.is_empty() {
-        if options.bump.is_none() {
-            // first argument must be version component to bump
-            let component = options.args.remove(0);
-            if components.contains_key(&component) {
-                bump = Some(component);
+/// Parse the options needed to find the configuration.
+///
+/// Since the bump subcommands depend on the configuration, parsing errors
+/// and help flags are ignored until the arguments are parsed again using [`parse`].
+pub fn parse_partial<I, T>(args: I) -> Options
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let mut options = Options::command()
+        .ignore_errors(true)
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .try_get_matches_from(args)
+        .and_then(|matches| Options::from_arg_matches(&matches))
+        .unwrap_or_else(|_| Options::parse_from([env!("CARGO_BIN_NAME")]));
+    if let Some(Command::Bump(args)) = &options.command {
+        options.bump = args.first().cloned();
+        options.command = None;
+    }
+    fix(&mut options);
+    options
+}
 
-                // remaining arguments are files
-                cli_files.extend(options.args.drain(..).map(PathBuf::from));
-            } else {
-                eyre::bail!(
-                    "first argument must be one of the version components {:?}",
-                    components.keys().collect::<Vec<_>>()
-                )
-            }
-        } else {
-            // assume all arguments are files to run on
-            cli_files.extend(options.args.drain(..).map(PathBuf::from));
-        }
+/// Parse the options, including the bump subcommands of the configured version `components`.
+pub fn parse<I, T>(
+    args: I,
+    components: &config::VersionComponentConfigs,
+) -> Result<Options, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let mut matches = command(components).try_get_matches_from(args)?;
+    let bump = match matches.subcommand_name() {
+        Some(name) if components.contains_key(name) => matches.remove_subcommand(),
+        _ => None,
+    };
+    let mut options = Options::from_arg_matches(&matches)?;
+    if let Some((component, mut bump_matches)) = bump {
+        options.files = bump_matches
+            .remove_many::<PathBuf>("files")
+            .map(Iterator::collect)
+            .unwrap_or_default();
+        options.bump = Some(component);
     }
-    Ok((bump, cli_files))
+    fix(&mut options);
+    Ok(options)
 }
 
 pub fn global_cli_config(options: &Options) -> eyre::Result<bumpversion::config::GlobalConfig> {
@@ -460,7 +615,129 @@ pub fn global_cli_config(options: &Options) -> eyre::Result<bumpversion::config:
         tag_message,
         commit_message,
         commit_args: options.commit_args.clone(),
+        push: options.push.or(options.no_push.invert()),
+        push_remote: options.push_remote.clone(),
+        push_args: options.push_args.clone(),
         ..bumpversion::config::GlobalConfig::empty()
     };
     Ok(cli_overrides)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+    use bumpversion::config::{VersionComponentConfigs, VersionComponentSpec};
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    fn components() -> VersionComponentConfigs {
+        let pre_l = VersionComponentSpec {
+            values: vec!["dev".to_string(), "rc".to_string(), "final".to_string()],
+            optional_value: Some("final".to_string()),
+            ..VersionComponentSpec::default()
+        };
+        [
+            ("major".to_string(), VersionComponentSpec::default()),
+            ("minor".to_string(), VersionComponentSpec::default()),
+            ("patch".to_string(), VersionComponentSpec::default()),
+            ("pre_l".to_string(), pre_l),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_parse_partial_ignores_unknown_components() {
+        let options = super::parse_partial([
+            "bumpversion",
+            "--dir",
+            "/repo",
+            "--dry-run",
+            "pre_l",
+            "--help",
+        ]);
+        sim_assert_eq!(options.dir, Some(PathBuf::from("/repo")));
+        sim_assert_eq!(options.dry_run, Some(true));
+        sim_assert_eq!(options.bump.as_deref(), Some("pre_l"));
+        assert!(options.command.is_none());
+
+        let options = super::parse_partial(["bumpversion", "show", "current_version"]);
+        assert!(matches!(options.command, Some(Command::Show(_))));
+        sim_assert_eq!(options.bump, None);
+    }
+
+    #[test]
+    fn test_parse_configured_component() -> Result<(), clap::Error> {
+        let options = super::parse(
+            [
+                "bumpversion",
+                "--dry-run",
+                "pre_l",
+                "VERSION",
+                "src/version.txt",
+            ],
+            &components(),
+        )?;
+        sim_assert_eq!(options.dry_run, Some(true));
+        sim_assert_eq!(options.bump.as_deref(), Some("pre_l"));
+        sim_assert_eq!(
+            options.files,
+            vec![PathBuf::from("VERSION"), PathBuf::from("src/version.txt")]
+        );
+        assert!(options.command.is_none());
+
+        let options = super::parse(["bumpversion", "show-bump"], &components())?;
+        assert!(matches!(options.command, Some(Command::ShowBump)));
+        sim_assert_eq!(options.bump, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unknown_component() {
+        let err = super::parse(["bumpversion", "build"], &components()).unwrap_err();
+        sim_assert_eq!(err.kind(), clap::error::ErrorKind::InvalidSubcommand);
+
+        // without configured components, there is nothing to bump
+        let err = super::parse(
+            ["bumpversion", "patch"],
+            &VersionComponentConfigs::default(),
+        )
+        .unwrap_err();
+        sim_assert_eq!(err.kind(), clap::error::ErrorKind::InvalidSubcommand);
+    }
+
+    #[test]
+    fn test_command_has_subcommand_for_each_component() {
+        let mut components = components();
+        components.insert("check".to_string(), VersionComponentSpec::default());
+
+        let command = super::command(&components);
+        let subcommands: Vec<&str> = command
+            .get_subcommands()
+            .map(clap::Command::get_name)
+            .collect();
+        sim_assert_eq!(
+            subcommands,
+            vec![
+                "show",
+                "show-bump",
+                "check",
+                "auto",
+                "completions",
+                "major",
+                "minor",
+                "patch",
+                "pre_l",
+            ]
+        );
+
+        let about = command
+            .find_subcommand("pre_l")
+            .and_then(clap::Command::get_about)
+            .map(ToString::to_string);
+        sim_assert_eq!(
+            about.as_deref(),
+            Some("bump the pre_l version component (one of dev, rc, final)")
+        );
+    }
+}
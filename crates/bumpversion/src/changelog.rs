@@ -0,0 +1,215 @@
+//! Changelog generation from conventional commits.
+//!
+//! Groups the commits since the latest tag by their conventional commit type
+//! and prepends them as a new release to the changelog.
+use crate::{
+    config::FinalizedChangelogConfig, conventional, f_string::FormatError, vcs::CommitInfo,
+};
+use std::collections::HashMap;
+
+/// Render the release notes of the `commits` using the `ctx` of the bump.
+///
+/// Commits are grouped by the configured sections, in order.
+/// Commits that do not follow the conventional commit format or whose type has no
+/// section are omitted.
+pub fn render<S>(
+    commits: &[CommitInfo],
+    config: &FinalizedChangelogConfig,
+    ctx: &HashMap<String, String, S>,
+) -> Result<String, FormatError>
+where
+    S: std::hash::BuildHasher,
+{
+    let ctx: HashMap<&str, &str> = ctx
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    let mut out = config.heading.format(&ctx, true)?;
+    out.push('\n');
+
+    for (commit_type, title) in &config.sections {
+        let entries = commits
+            .iter()
+            .filter_map(|commit| {
+                let subject = conventional::parse_subject(&commit.subject)?;
+                subject
+                    .commit_type
+                    .eq_ignore_ascii_case(commit_type)
+                    .then_some((commit, subject))
+            })
+            .map(|(commit, subject)| {
+                let short_sha = commit.sha.get(..7).unwrap_or(&commit.sha);
+                let mut ctx = ctx.clone();
+                ctx.extend([
+                    ("type", subject.commit_type),
+                    ("scope", subject.scope.unwrap_or_default()),
+                    ("description", subject.description),
+                    ("subject", commit.subject.as_str()),
+                    ("sha", commit.sha.as_str()),
+                    ("short_sha", short_sha),
+                ]);
+                config.entry.format(&ctx, true)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if entries.is_empty() {
+            continue;
+        }
+        out.push_str("\n### ");
+        out.push_str(title);
+        out.push_str("\n\n");
+        for entry in entries {
+            out.push_str(&entry);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Prepend the `release` notes to the `changelog`.
+///
+/// The release is inserted before the first heading of the same level as the release heading,
+/// so that a title and introduction at the top of the changelog are kept.
+/// If there is no such heading, the release is appended after a leading title,
+/// or prepended otherwise.
+#[must_use]
+pub fn prepend(changelog: &str, release: &str) -> String {
+    let release = release.trim_end();
+    let level = release
+        .lines()
+        .next()
+        .map(|heading| heading.chars().take_while(|c| *c == '#').count())
+        .unwrap_or_default();
+    let is_release_heading = |line: &str| {
+        level > 0
+            && line.chars().take_while(|c| *c == '#').count() == level
+            && line[level..].starts_with(' ')
+    };
+
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        if is_release_heading(line) {
+            let (before, after) = changelog.split_at(offset);
+            return format!("{before}{release}\n\n{after}");
+        }
+        offset += line.len();
+    }
+
+    if changelog.starts_with("# ") {
+        format!("{}\n\n{release}\n", changelog.trim_end())
+    } else if changelog.trim().is_empty() {
+        format!("{release}\n")
+    } else {
+        format!("{release}\n\n{changelog}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::{ChangelogConfig, FinalizedChangelogConfig},
+        f_string::PythonFormatString,
+        vcs::CommitInfo,
+    };
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::HashMap;
+
+    fn commit(sha: &str, subject: &str) -> CommitInfo {
+        CommitInfo {
+            sha: sha.repeat(40),
+            subject: subject.to_string(),
+            body: String::new(),
+        }
+    }
+
+    fn ctx() -> HashMap<String, String> {
+        [
+            ("new_version", "1.3.0"),
+            ("current_version", "1.2.3"),
+            ("now", "2025-03-01T12:00:00+00:00"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn test_render() -> eyre::Result<()> {
+        crate::tests::init();
+        let commits = [
+            commit("a", "fix(cli): handle empty repos"),
+            commit("b", "chore: update dependencies"),
+            commit("c", "feat!: add auto bump"),
+            commit("d", "Update README"),
+            commit("e", "feat: add changelog"),
+        ];
+        let release = super::render(&commits, &FinalizedChangelogConfig::default(), &ctx())?;
+        sim_assert_eq!(
+            release,
+            indoc::indoc! {"
+                ## 1.3.0 (2025-03-01)
+
+                ### Features
+
+                - add auto bump (ccccccc)
+                - add changelog (eeeeeee)
+
+                ### Bug Fixes
+
+                - handle empty repos (aaaaaaa)
+            "}
+        );
+
+        let config = ChangelogConfig {
+            heading: Some(PythonFormatString::parse("# v{new_version}")?),
+            entry: Some(PythonFormatString::parse("* {scope}: {subject}")?),
+            sections: Some(
+                [("fix", "Fixes")]
+                    .into_iter()
+                    .map(|(commit_type, title)| (commit_type.to_string(), title.to_string()))
+                    .collect(),
+            ),
+            ..ChangelogConfig::default()
+        }
+        .finalize();
+        let release = super::render(&commits, &config, &ctx())?;
+        sim_assert_eq!(
+            release,
+            indoc::indoc! {"
+                # v1.3.0
+
+                ### Fixes
+
+                * cli: fix(cli): handle empty repos
+            "}
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepend() {
+        crate::tests::init();
+        let release = "## 1.3.0\n\n- new\n";
+        sim_assert_eq!(super::prepend("", release), "## 1.3.0\n\n- new\n");
+        sim_assert_eq!(
+            super::prepend(
+                "# Changelog\n\nAll notable changes.\n\n## 1.2.3\n\n- old\n",
+                release
+            ),
+            "# Changelog\n\nAll notable changes.\n\n## 1.3.0\n\n- new\n\n## 1.2.3\n\n- old\n"
+        );
+        sim_assert_eq!(
+            super::prepend("# Changelog\n", release),
+            "# Changelog\n\n## 1.3.0\n\n- new\n"
+        );
+        sim_assert_eq!(
+            super::prepend("- old\n", release),
+            "## 1.3.0\n\n- new\n\n- old\n"
+        );
+        sim_assert_eq!(
+            super::prepend("### Notes\n\n## 1.2.3\n", release),
+            "### Notes\n\n## 1.3.0\n\n- new\n\n## 1.2.3\n"
+        );
+    }
+}
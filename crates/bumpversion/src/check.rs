@@ -0,0 +1,468 @@
+//! Check that all configured files agree on the current version.
+//!
+//! Reports missing and mismatched occurrences of the current version as diagnostics.
+use crate::{
+    config::{self, FileChange, RegexTemplate, regex::RegexTemplateError},
+    context,
+    data_file::{self, DataFormat},
+    diagnostics::{DiagnosticExt, FileId, Printer},
+    f_string::{PythonFormatString, Value},
+    files::{FileMap, IoError},
+    fs::FileSystem,
+    vcs::TagAndRevision,
+    version::{self, Version},
+};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Errors that can occur when checking the current version.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// Current version was not found in configuration.
+    #[error("missing current version")]
+    MissingCurrentVersion,
+    /// The current version could not be parsed.
+    #[error("invalid current version {0:?}")]
+    InvalidVersion(String),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Serialize(#[from] version::SerializeError),
+    #[error(transparent)]
+    RegexTemplate(#[from] RegexTemplateError),
+    #[error(transparent)]
+    DataFile(#[from] data_file::Error),
+    #[error(transparent)]
+    Context(#[from] context::Error),
+}
+
+/// Placeholder for the current version when searching for any version.
+const VERSION_PLACEHOLDER: &str = "BUMPVERSIONCURRENTVERSIONPLACEHOLDER";
+
+static NAMED_GROUP_REGEX: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| regex::Regex::new(r"\(\?P?<[A-Za-z_][A-Za-z0-9_]*>").unwrap());
+
+/// Build a regex matching the `search` template with any version in place of the current version.
+///
+/// The first occurrence of the version is captured in the `version` group.
+/// Returns `None` if the template does not contain the current version.
+fn any_version_regex(
+    search: &RegexTemplate,
+    parse_version_pattern: &regex::Regex,
+    ctx: &HashMap<&str, &str>,
+) -> Option<regex::Regex> {
+    let mut ctx = ctx.clone();
+    ctx.insert("current_version", VERSION_PLACEHOLDER);
+    let pattern = search.format(&ctx, true).ok()?;
+    let pattern = pattern.as_str();
+    if !pattern.contains(VERSION_PLACEHOLDER) {
+        return None;
+    }
+    let version_pattern = NAMED_GROUP_REGEX.replace_all(parse_version_pattern.as_str(), "(?:");
+    let pattern = pattern
+        .replacen(
+            VERSION_PLACEHOLDER,
+            &format!("(?P<version>{version_pattern})"),
+            1,
+        )
+        .replace(VERSION_PLACEHOLDER, &format!("(?:{version_pattern})"));
+    regex::RegexBuilder::new(&pattern)
+        .multi_line(true)
+        .build()
+        .ok()
+}
+
+/// What to search for in a file.
+struct Search<'a> {
+    template: &'a RegexTemplate,
+    parse_version_pattern: &'a regex::Regex,
+    /// The current version, serialized for this file.
+    current_version: String,
+    key_path: Option<&'a str>,
+    /// Report problems as errors instead of warnings.
+    strict: bool,
+}
+
+/// Check that the `contents` of the file at `path` contain the current version.
+fn check_contents(
+    path: &Path,
+    contents: &str,
+    file_id: FileId,
+    search: &Search<'_>,
+    ctx: &HashMap<&str, &str>,
+) -> Result<Vec<Diagnostic<FileId>>, Error> {
+    let original_version = ctx.get("current_version").copied();
+    let mut ctx = ctx.clone();
+    ctx.insert("current_version", search.current_version.as_str());
+    let search_regex = search.template.format(&ctx, true)?;
+
+    let data_file = search
+        .key_path
+        .and_then(|key_path| DataFormat::from_path(path).map(|format| (format, key_path)));
+
+    let is_match = |regex: &regex::Regex| -> Result<bool, Error> {
+        match data_file {
+            Some((format, key_path)) => {
+                Ok(data_file::replace_value(format, contents, key_path, regex, "")?.is_some())
+            }
+            None => Ok(regex.is_match(contents)),
+        }
+    };
+
+    if is_match(&search_regex)? {
+        return Ok(vec![]);
+    }
+
+    // the original version (representing the same version) might match instead
+    if let Some(original_version) =
+        original_version.filter(|original| *original != search.current_version)
+    {
+        let mut original_ctx = ctx.clone();
+        original_ctx.insert("current_version", original_version);
+        if is_match(&search.template.format(&original_ctx, true)?)? {
+            return Ok(vec![]);
+        }
+    }
+
+    let diagnostic = Diagnostic::warning_or_error(search.strict);
+    let mismatches: Vec<_> = if data_file.is_some() {
+        vec![]
+    } else {
+        any_version_regex(search.template, search.parse_version_pattern, &ctx)
+            .map(|regex| {
+                regex
+                    .captures_iter(contents)
+                    .filter_map(|captures| captures.name("version"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let diagnostic = if mismatches.is_empty() {
+        let mut notes = vec![format!("searched for `{}`", search_regex.as_str())];
+        if let Some(key_path) = search.key_path {
+            notes.push(format!("at key path `{key_path}`"));
+        }
+        diagnostic
+            .with_message(format!(
+                "did not find current version {} in {}",
+                search.current_version,
+                path.display()
+            ))
+            .with_notes(notes)
+    } else {
+        diagnostic
+            .with_message(format!(
+                "version in {} does not match current version {}",
+                path.display(),
+                search.current_version
+            ))
+            .with_labels(
+                mismatches
+                    .into_iter()
+                    .map(|found| {
+                        Label::primary(file_id, found.range()).with_message(format!(
+                            "found version {}, expected {}",
+                            found.as_str(),
+                            search.current_version
+                        ))
+                    })
+                    .collect(),
+            )
+    };
+    Ok(vec![diagnostic])
+}
+
+/// Check that the file at `path` contains the current version for all of its `changes`.
+async fn check_file<FS, W>(
+    fs: &FS,
+    path: &Path,
+    changes: &[FileChange],
+    current_version: &Version,
+    ctx: &HashMap<&str, &str>,
+    printer: &Printer<W>,
+) -> Result<Vec<Diagnostic<FileId>>, Error>
+where
+    FS: FileSystem,
+{
+    if !fs.is_file(path).await {
+        if changes.iter().all(|change| change.ignore_missing_file) {
+            tracing::info!(?path, "file not found");
+            return Ok(vec![]);
+        }
+        return Ok(vec![
+            Diagnostic::error().with_message(format!("file {} does not exist", path.display())),
+        ]);
+    }
+
+    let contents = fs.read_to_string(path).await?;
+    let file_id = printer.add_source_file(path, contents.clone());
+
+    let mut diagnostics = vec![];
+    for change in changes {
+        let search = Search {
+            template: &change.search,
+            parse_version_pattern: &change.parse_version_pattern,
+            current_version: current_version.serialize(&change.serialize_version_patterns, ctx)?,
+            key_path: change.key_path.as_deref(),
+            strict: !change.ignore_missing_version,
+        };
+        diagnostics.extend(check_contents(path, &contents, file_id, &search, ctx)?);
+    }
+    Ok(diagnostics)
+}
+
+/// Check that the current version is found in all configured files and the config file.
+///
+/// The configured files are searched using the `search` template of each of their changes.
+/// All files are read from `fs`.
+/// Source files are added to the `printer`, so the returned diagnostics can be emitted using it.
+///
+/// # Errors
+/// - When no current version is configured or it cannot be parsed.
+/// - When a file cannot be read.
+/// - When a search template cannot be formatted.
+pub async fn check<FS, W>(
+    fs: &FS,
+    config: &config::FinalizedConfig,
+    file_map: FileMap,
+    config_file: Option<&config::ConfigFile>,
+    components: &config::VersionComponentConfigs,
+    tag_and_revision: &TagAndRevision,
+    printer: &Printer<W>,
+) -> Result<Vec<Diagnostic<FileId>>, Error>
+where
+    FS: FileSystem,
+{
+    let current_version_serialized = config
+        .global
+        .current_version
+        .as_deref()
+        .ok_or(Error::MissingCurrentVersion)?;
+    let version_spec = version::VersionSpec::from_components(components.clone());
+    let current_version = Version::parse(
+        current_version_serialized,
+        &config.global.parse_version_pattern,
+        &version_spec,
+    )
+    .ok_or_else(|| Error::InvalidVersion(current_version_serialized.to_string()))?;
+
+    let mut ctx: HashMap<String, String> = context::get_context(
+        Some(tag_and_revision),
+        Some(&current_version),
+        None,
+        Some(current_version_serialized),
+        None,
+    )
+    .collect();
+    context::render_variables(&config.context, &mut ctx)?;
+    let ctx: HashMap<&str, &str> = ctx.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+
+    let mut diagnostics = vec![];
+    for (path, changes) in crate::files::files_to_modify(config, file_map) {
+        diagnostics.extend(check_file(fs, &path, &changes, &current_version, &ctx, printer).await?);
+    }
+
+    if let Some(config_file) = config_file {
+        let path = config_file.path();
+        let contents = fs.read_to_string(path).await?;
+        let file_id = printer.add_source_file(path, contents.clone());
+        let search = RegexTemplate::Escaped(PythonFormatString(vec![Value::Argument(
+            "current_version".to_string(),
+        )]));
+        let search = Search {
+            template: &search,
+            parse_version_pattern: &config.global.parse_version_pattern,
+            current_version: current_version_serialized.to_string(),
+            key_path: None,
+            strict: true,
+        };
+        diagnostics.extend(check_contents(path, &contents, file_id, &search, &ctx)?);
+    }
+
+    Ok(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::{self, Config, GlobalConfig, InputFile, file::FileConfig},
+        diagnostics::{BufferedPrinter, DiagnosticExt},
+        fs::{FileSystem, LocalFileSystem, MemoryFileSystem},
+        vcs::TagAndRevision,
+    };
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    async fn check(
+        fs: &impl FileSystem,
+        dir: &std::path::Path,
+        files: &[(&str, FileConfig)],
+    ) -> eyre::Result<(
+        Vec<codespan_reporting::diagnostic::Diagnostic<usize>>,
+        BufferedPrinter,
+    )> {
+        let mut config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                ..GlobalConfig::empty()
+            },
+            files: files
+                .iter()
+                .map(|(path, file_config)| (InputFile::Path(path.into()), file_config.clone()))
+                .collect(),
+            ..Config::default()
+        };
+        config.merge_file_configs_with_global_config();
+        let mut config = config.finalize();
+        let components = config::version::version_component_configs(&config);
+        let file_map =
+            crate::files::resolve_files_from_config(&mut config, &components, Some(dir))?;
+        let printer = BufferedPrinter::default();
+        let diagnostics = super::check(
+            fs,
+            &config,
+            file_map,
+            None,
+            &components,
+            &TagAndRevision::default(),
+            &printer,
+        )
+        .await?;
+        Ok((diagnostics, printer))
+    }
+
+    #[tokio::test]
+    async fn test_check_matching_versions() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        tokio::fs::write(dir.path().join("VERSION"), "1.2.3\n").await?;
+        tokio::fs::write(dir.path().join("setup.py"), "version='1.2.3'\n").await?;
+
+        let setup_py = FileConfig {
+            search: Some(config::RegexTemplate::Escaped(
+                crate::f_string::PythonFormatString::parse("version='{current_version}'")?,
+            )),
+            ..FileConfig::empty()
+        };
+        let (diagnostics, _) = check(
+            &LocalFileSystem,
+            dir.path(),
+            &[("VERSION", FileConfig::empty()), ("setup.py", setup_py)],
+        )
+        .await?;
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_mismatched_and_missing_versions() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        tokio::fs::write(dir.path().join("VERSION"), "1.2.3\n").await?;
+        tokio::fs::write(
+            dir.path().join("setup.py"),
+            "name='demo'\nversion='1.2.4'\n",
+        )
+        .await?;
+        tokio::fs::write(dir.path().join("README.md"), "# demo\n").await?;
+
+        let setup_py = FileConfig {
+            search: Some(config::RegexTemplate::Escaped(
+                crate::f_string::PythonFormatString::parse("version='{current_version}'")?,
+            )),
+            ..FileConfig::empty()
+        };
+        let (diagnostics, printer) = check(
+            &LocalFileSystem,
+            dir.path(),
+            &[
+                ("VERSION", FileConfig::empty()),
+                ("setup.py", setup_py),
+                ("README.md", FileConfig::empty()),
+            ],
+        )
+        .await?;
+
+        sim_assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(DiagnosticExt::is_error));
+
+        let mismatch = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.message.contains("setup.py"))
+            .ok_or_else(|| eyre::eyre!("missing setup.py diagnostic"))?;
+        assert!(
+            mismatch
+                .message
+                .contains("does not match current version 1.2.3")
+        );
+        sim_assert_eq!(mismatch.labels.len(), 1);
+        sim_assert_eq!(mismatch.labels[0].range, 21..26);
+        sim_assert_eq!(
+            mismatch.labels[0].message,
+            "found version 1.2.4, expected 1.2.3"
+        );
+        sim_assert_eq!(printer.lines(mismatch)?, vec![1]);
+
+        let missing = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.message.contains("README.md"))
+            .ok_or_else(|| eyre::eyre!("missing README.md diagnostic"))?;
+        assert!(
+            missing
+                .message
+                .starts_with("did not find current version 1.2.3")
+        );
+        assert!(missing.labels.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_ignores_missing_versions() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        tokio::fs::write(dir.path().join("README.md"), "# demo\n").await?;
+
+        let readme = FileConfig {
+            ignore_missing_version: Some(true),
+            ..FileConfig::empty()
+        };
+        let (diagnostics, _) =
+            check(&LocalFileSystem, dir.path(), &[("README.md", readme)]).await?;
+        sim_assert_eq!(diagnostics.len(), 1);
+        assert!(!diagnostics[0].is_error());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_memory_file_system() -> eyre::Result<()> {
+        crate::tests::init();
+        let fs = MemoryFileSystem::new();
+        fs.insert("/repo/VERSION", "1.2.3\n");
+        fs.insert("/repo/README.md", "version 1.2.4\n");
+
+        let (diagnostics, printer) = check(
+            &fs,
+            std::path::Path::new("/repo"),
+            &[
+                ("/repo/VERSION", FileConfig::empty()),
+                ("/repo/README.md", FileConfig::empty()),
+                ("/repo/CHANGELOG.md", FileConfig::empty()),
+            ],
+        )
+        .await?;
+        sim_assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "version in /repo/README.md does not match current version 1.2.3",
+                "file /repo/CHANGELOG.md does not exist",
+            ]
+        );
+        sim_assert_eq!(printer.lines(&diagnostics[0])?, vec![0]);
+        Ok(())
+    }
+}
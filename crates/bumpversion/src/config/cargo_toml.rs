@@ -0,0 +1,228 @@
+use crate::{
+    config::{
+        Config,
+        pyproject_toml::{ParseError, as_string},
+    },
+    diagnostics::FileId,
+    files,
+    fs::FileSystem,
+};
+use codespan_reporting::diagnostic::Diagnostic;
+use std::collections::HashMap;
+use std::path::Path;
+use toml_span as toml;
+
+/// Key paths of the version in a `Cargo.toml` that are updated when bumping.
+const VERSION_KEY_PATHS: [&[&str]; 4] = [
+    &["package", "version"],
+    &["package", "metadata", "bumpversion", "current_version"],
+    &["workspace", "package", "version"],
+    &["workspace", "metadata", "bumpversion", "current_version"],
+];
+
+/// Get the value at `key_path` of a TOML value.
+fn get<'a, 'de>(value: &'a toml::Value<'de>, key_path: &[&str]) -> Option<&'a toml::Value<'de>> {
+    key_path.iter().try_fold(value, |value, key| {
+        value.as_table().and_then(|table| table.get(*key))
+    })
+}
+
+impl Config {
+    pub fn from_cargo_toml_value(
+        config: &toml::Value,
+        file_id: FileId,
+        strict: bool,
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
+    ) -> Result<Option<Self>, ParseError> {
+        let Some(bumpversion) = get(config, &["package", "metadata", "bumpversion"])
+            .or_else(|| get(config, &["workspace", "metadata", "bumpversion"]))
+        else {
+            return Ok(None);
+        };
+
+        let mut config_file =
+            Self::from_bumpversion_table(bumpversion, file_id, strict, diagnostics)?;
+
+        if config_file.global.current_version.is_none() {
+            // fall back to the version of the package
+            let package_version = get(config, &["package", "version"])
+                .or_else(|| get(config, &["workspace", "package", "version"]))
+                // the version may be inherited from the workspace (`version.workspace = true`)
+                .filter(|version| version.as_str().is_some())
+                .map(as_string)
+                .transpose()?;
+            config_file.global.current_version = package_version;
+        }
+
+        Ok(Some(config_file))
+    }
+
+    pub fn from_cargo_toml(
+        config: &str,
+        file_id: FileId,
+        strict: bool,
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
+    ) -> Result<Option<Self>, ParseError> {
+        let config = toml_span::parse(config).map_err(|source| ParseError::Toml { source })?;
+        Self::from_cargo_toml_value(&config, file_id, strict, diagnostics)
+    }
+}
+
+/// Update the package version and `current_version` key in the `Cargo.toml`
+pub(crate) async fn replace_version<FS, K, V>(
+    fs: &FS,
+    path: &Path,
+    config: &super::FinalizedConfig,
+    ctx: &HashMap<K, V>,
+) -> Result<Option<files::Modification>, files::ReplaceVersionError>
+where
+    FS: FileSystem,
+    K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
+    V: AsRef<str> + std::fmt::Debug,
+{
+    super::toml::replace_version_of_keys(fs, path, &VERSION_KEY_PATHS, config, ctx).await
+}
+
+#[cfg(test)]
+#[allow(clippy::too_many_lines, clippy::unnecessary_wraps)]
+mod tests {
+    use crate::{
+        config::{Config, GlobalConfig, TemplateSpans},
+        diagnostics::{BufferedPrinter, ToDiagnostics},
+        fs::LocalFileSystem,
+    };
+    use color_eyre::eyre;
+    use indexmap::IndexMap;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::HashMap;
+
+    fn parse_cargo_toml(config: &str) -> Result<Option<Config>, super::ParseError> {
+        let printer = BufferedPrinter::default();
+        let mut diagnostics = vec![];
+        let file_id = printer.add_source_file("Cargo.toml".to_string(), config.to_string());
+        let strict = true;
+        let config = Config::from_cargo_toml(config, file_id, strict, &mut diagnostics);
+        if let Err(ref err) = config {
+            diagnostics.extend(err.to_diagnostics(file_id));
+        }
+        for diagnostic in &diagnostics {
+            printer.emit(diagnostic).expect("emit diagnostics");
+        }
+        printer.print().expect("print diagnostics");
+        config
+    }
+
+    #[test]
+    fn parse_cargo_toml_package_metadata() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let cargo_toml = indoc::indoc! {r#"
+            [package]
+            name = "mycrate"
+            version = "0.1.8"
+
+            [package.metadata.bumpversion]
+            commit = true
+            tag = false
+        "#};
+
+        let config = parse_cargo_toml(cargo_toml)?;
+        let expected = Config {
+            global: GlobalConfig {
+                current_version: Some("0.1.8".to_string()),
+                commit: Some(true),
+                tag: Some(false),
+                ..GlobalConfig::empty()
+            },
+            files: vec![],
+            components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
+        };
+        sim_assert_eq!(config, Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cargo_toml_workspace_metadata() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let cargo_toml = indoc::indoc! {r#"
+            [workspace]
+            members = ["crates/*"]
+
+            [workspace.package]
+            version = "1.2.3"
+
+            [workspace.metadata.bumpversion]
+            current_version = "1.2.4"
+        "#};
+
+        let config = parse_cargo_toml(cargo_toml)?;
+        sim_assert_eq!(
+            config.and_then(|config| config.global.current_version),
+            Some("1.2.4".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cargo_toml_without_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let cargo_toml = indoc::indoc! {r#"
+            [package]
+            name = "mycrate"
+            version = "0.1.8"
+        "#};
+
+        let config = parse_cargo_toml(cargo_toml)?;
+        sim_assert_eq!(config, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replace_version() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let cargo_toml = indoc::indoc! {r#"
+            [package]
+            name = "mycrate"
+            version = "0.1.8" # the version
+
+            [dependencies]
+            serde = { version = "0.1.8" }
+
+            [package.metadata.bumpversion]
+            commit = true
+        "#};
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("Cargo.toml");
+        tokio::fs::write(&path, cargo_toml).await?;
+
+        let config = parse_cargo_toml(cargo_toml)?
+            .ok_or_else(|| eyre::eyre!("missing config"))?
+            .finalize();
+        let ctx: HashMap<&str, &str> = [("current_version", "0.1.8"), ("new_version", "0.2.0")]
+            .into_iter()
+            .collect();
+        let modification = super::replace_version(&LocalFileSystem, &path, &config, &ctx).await?;
+
+        let want = indoc::indoc! {r#"
+            [package]
+            name = "mycrate"
+            version = "0.2.0" # the version
+
+            [dependencies]
+            serde = { version = "0.1.8" }
+
+            [package.metadata.bumpversion]
+            commit = true
+        "#};
+        sim_assert_eq!(tokio::fs::read_to_string(&path).await?, cargo_toml);
+        sim_assert_eq!(modification.map(|m| m.after), Some(want.to_string()));
+        Ok(())
+    }
+}
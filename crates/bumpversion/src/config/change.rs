@@ -7,11 +7,16 @@ pub struct FileChange {
     pub parse_version_pattern: super::regex::Regex,
     pub serialize_version_patterns: Vec<PythonFormatString>,
     pub search: RegexTemplate,
+    /// Whether the `search` is the global search rather than one configured for the file.
+    ///
+    /// Only then, the original current version is searched if `search` does not match.
+    pub uses_global_search: bool,
     pub replace: String,
     pub ignore_missing_version: bool,
     pub ignore_missing_file: bool,
-    // If specified, and has an appropriate extension, will be treated as a data file
-    // pub key_path: Option<String>,
+    /// If specified, and the file has a TOML, JSON or YAML extension,
+    /// only the value at this dot-separated key path is updated
+    pub key_path: Option<String>,
     pub include_bumps: Option<Vec<String>>,
     pub exclude_bumps: Option<Vec<String>>,
 }
@@ -29,13 +34,14 @@ impl FileChange {
             // .unwrap_or(defaults::SERIALIZE_VERSION_PATTERNS.clone()),
             // TODO: make this an enum that is either regex or string?
             search: file_config.search, // .unwrap_or(defaults::SEARCH.clone()),
+            uses_global_search: true,
             replace: file_config.replace, // .unwrap_or(defaults::REPLACE.to_string()),
             ignore_missing_version: file_config.ignore_missing_version,
             // .unwrap_or(defaults::IGNORE_MISSING_VERSION),
             ignore_missing_file: file_config.ignore_missing_file,
             // .unwrap_or(defaults::IGNORE_MISSING_FILES),
             include_bumps: Some(components.keys().cloned().collect()),
-            // key_path: None,
+            key_path: file_config.key_path,
             exclude_bumps: None,
         }
     }
@@ -0,0 +1,76 @@
+use crate::f_string::{PythonFormatString, Value};
+use indexmap::IndexMap;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChangelogConfig {
+    /// Path of the changelog, relative to the repository root
+    pub path: Option<PathBuf>,
+    /// Template for the heading of a release
+    pub heading: Option<PythonFormatString>,
+    /// Template for a single commit
+    pub entry: Option<PythonFormatString>,
+    /// Title of the section for each conventional commit type, in order
+    ///
+    /// Commits of other types are not included in the changelog.
+    pub sections: Option<IndexMap<String, String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalizedChangelogConfig {
+    /// Path of the changelog, relative to the repository root
+    pub path: PathBuf,
+    /// Template for the heading of a release
+    pub heading: PythonFormatString,
+    /// Template for a single commit
+    pub entry: PythonFormatString,
+    /// Title of the section for each conventional commit type, in order
+    ///
+    /// Commits of other types are not included in the changelog.
+    pub sections: IndexMap<String, String>,
+}
+
+impl Default for FinalizedChangelogConfig {
+    fn default() -> Self {
+        let heading = PythonFormatString(vec![
+            Value::String("## ".to_string()),
+            Value::Argument("new_version".to_string()),
+            Value::String(" (".to_string()),
+            Value::Argument("now:%Y-%m-%d".to_string()),
+            Value::String(")".to_string()),
+        ]);
+        let entry = PythonFormatString(vec![
+            Value::String("- ".to_string()),
+            Value::Argument("description".to_string()),
+            Value::String(" (".to_string()),
+            Value::Argument("short_sha".to_string()),
+            Value::String(")".to_string()),
+        ]);
+        let sections = [("feat", "Features"), ("fix", "Bug Fixes")]
+            .into_iter()
+            .map(|(commit_type, title)| (commit_type.to_string(), title.to_string()))
+            .collect();
+        Self {
+            path: PathBuf::from("CHANGELOG.md"),
+            heading,
+            entry,
+            sections,
+        }
+    }
+}
+
+impl ChangelogConfig {
+    /// Finalize the changelog config.
+    ///
+    /// All unset configuration options will be set to their default value.
+    #[must_use]
+    pub fn finalize(self) -> FinalizedChangelogConfig {
+        let default = FinalizedChangelogConfig::default();
+        FinalizedChangelogConfig {
+            path: self.path.unwrap_or(default.path),
+            heading: self.heading.unwrap_or(default.heading),
+            entry: self.entry.unwrap_or(default.entry),
+            sections: self.sections.unwrap_or(default.sections),
+        }
+    }
+}
@@ -18,6 +18,8 @@ pub struct FileConfig {
     pub ignore_missing_file: Option<bool>,
     /// Ignore any missing version when searching and replacing version
     pub ignore_missing_version: Option<bool>,
+    /// Dot-separated path of the key holding the version in a TOML, JSON or YAML data file
+    pub key_path: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -34,6 +36,8 @@ pub struct FinalizedFileConfig {
     pub ignore_missing_file: bool,
     /// Ignore any missing version when searching and replacing version
     pub ignore_missing_version: bool,
+    /// Dot-separated path of the key holding the version in a TOML, JSON or YAML data file
+    pub key_path: Option<String>,
 }
 
 impl FileConfig {
@@ -46,6 +50,7 @@ impl FileConfig {
             replace: None,
             ignore_missing_file: None,
             ignore_missing_version: None,
+            key_path: None,
         }
     }
 }
@@ -80,6 +85,7 @@ impl Default for FinalizedFileConfig {
             replace: "{new_version}".to_string(),
             ignore_missing_version: false,
             ignore_missing_file: false,
+            key_path: None,
         }
     }
 }
@@ -94,6 +100,7 @@ impl Default for FileConfig {
             replace: Some(default.replace),
             ignore_missing_version: Some(default.ignore_missing_version),
             ignore_missing_file: Some(default.ignore_missing_file),
+            key_path: default.key_path,
         }
     }
 }
@@ -120,6 +127,7 @@ impl FileConfig {
             ignore_missing_file: self
                 .ignore_missing_file
                 .unwrap_or(default.ignore_missing_file),
+            key_path: self.key_path,
         }
     }
 }
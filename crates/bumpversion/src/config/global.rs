@@ -40,6 +40,18 @@ pub struct GlobalConfig {
     pub commit_message: Option<PythonFormatString>,
     /// Extra arguments to commit command
     pub commit_args: Option<String>,
+    /// Push the commit and tag to the remote
+    pub push: Option<bool>,
+    /// Remote to push to
+    pub push_remote: Option<String>,
+    /// Extra arguments to push command
+    pub push_args: Option<String>,
+    /// Version component to bump for breaking changes when bumping automatically
+    pub auto_bump_breaking: Option<String>,
+    /// Version component to bump for `feat` commits when bumping automatically
+    pub auto_bump_feat: Option<String>,
+    /// Version component to bump for `fix` commits when bumping automatically
+    pub auto_bump_fix: Option<String>,
 
     /// Setup hooks
     pub setup_hooks: Option<Vec<String>>,
@@ -97,6 +109,20 @@ pub struct GlobalConfigFinalized {
     pub commit_message: PythonFormatString,
     /// Extra arguments to commit command
     pub commit_args: Option<String>,
+    /// Push the commit and tag to the remote
+    pub push: bool,
+    /// Remote to push to
+    ///
+    /// Defaults to the default remote of the VCS (`origin` for git, `default` for mercurial).
+    pub push_remote: Option<String>,
+    /// Extra arguments to push command
+    pub push_args: Option<String>,
+    /// Version component to bump for breaking changes when bumping automatically
+    pub auto_bump_breaking: String,
+    /// Version component to bump for `feat` commits when bumping automatically
+    pub auto_bump_feat: String,
+    /// Version component to bump for `fix` commits when bumping automatically
+    pub auto_bump_fix: String,
 
     // extra stuff
     /// Setup hooks
@@ -137,6 +163,12 @@ impl GlobalConfig {
             tag_message: None,
             commit_message: None,
             commit_args: None,
+            push: None,
+            push_remote: None,
+            push_args: None,
+            auto_bump_breaking: None,
+            auto_bump_feat: None,
+            auto_bump_fix: None,
             setup_hooks: None,
             pre_commit_hooks: None,
             post_commit_hooks: None,
@@ -188,6 +220,12 @@ impl Default for GlobalConfigFinalized {
             tag_message,
             commit_message,
             commit_args: None,
+            push: false,
+            push_remote: None,
+            push_args: None,
+            auto_bump_breaking: "major".to_string(),
+            auto_bump_feat: "minor".to_string(),
+            auto_bump_fix: "patch".to_string(),
             setup_hooks: vec![],
             pre_commit_hooks: vec![],
             post_commit_hooks: vec![],
@@ -219,6 +257,12 @@ impl Default for GlobalConfig {
             tag_message: Some(default.tag_message),
             commit_message: Some(default.commit_message),
             commit_args: default.commit_args,
+            push: Some(default.push),
+            push_remote: default.push_remote,
+            push_args: default.push_args,
+            auto_bump_breaking: Some(default.auto_bump_breaking),
+            auto_bump_feat: Some(default.auto_bump_feat),
+            auto_bump_fix: Some(default.auto_bump_fix),
             setup_hooks: Some(default.setup_hooks),
             pre_commit_hooks: Some(default.pre_commit_hooks),
             post_commit_hooks: Some(default.post_commit_hooks),
@@ -264,6 +308,14 @@ impl GlobalConfig {
             tag_message: self.tag_message.unwrap_or(default.tag_message),
             commit_message: self.commit_message.unwrap_or(default.commit_message),
             commit_args: self.commit_args.or(default.commit_args),
+            push: self.push.unwrap_or(default.push),
+            push_remote: self.push_remote.or(default.push_remote),
+            push_args: self.push_args.or(default.push_args),
+            auto_bump_breaking: self
+                .auto_bump_breaking
+                .unwrap_or(default.auto_bump_breaking),
+            auto_bump_feat: self.auto_bump_feat.unwrap_or(default.auto_bump_feat),
+            auto_bump_fix: self.auto_bump_fix.unwrap_or(default.auto_bump_fix),
             setup_hooks: self.setup_hooks.unwrap_or(default.setup_hooks),
             pre_commit_hooks: self.pre_commit_hooks.unwrap_or(default.pre_commit_hooks),
             post_commit_hooks: self.post_commit_hooks.unwrap_or(default.post_commit_hooks),
@@ -300,6 +352,14 @@ impl<'a> super::MergeWith<&'a GlobalConfig> for GlobalConfig {
         self.commit_message
             .merge_with(other.commit_message.as_ref());
         self.commit_args.merge_with(other.commit_args.as_ref());
+        self.push.merge_with(other.push.as_ref());
+        self.push_remote.merge_with(other.push_remote.as_ref());
+        self.push_args.merge_with(other.push_args.as_ref());
+        self.auto_bump_breaking
+            .merge_with(other.auto_bump_breaking.as_ref());
+        self.auto_bump_feat
+            .merge_with(other.auto_bump_feat.as_ref());
+        self.auto_bump_fix.merge_with(other.auto_bump_fix.as_ref());
         self.setup_hooks.merge_with(other.setup_hooks.as_ref());
         self.pre_commit_hooks
             .merge_with(other.pre_commit_hooks.as_ref());
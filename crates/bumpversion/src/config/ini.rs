@@ -1,13 +1,17 @@
 use crate::{
     config::{
-        self, FileConfig, GlobalConfig, InputFile, RegexTemplate, VersionComponentSpec,
+        self, ChangelogConfig, FileConfig, GlobalConfig, InputFile, RegexTemplate,
+        VersionComponentSpec,
+        keys::{FILE_KEYS, GLOBAL_KEYS, PART_KEYS},
         pyproject_toml::ValueKind,
     },
     diagnostics::{DiagnosticExt, FileId, Span},
     f_string::{self, PythonFormatString},
-    files::{self, IoError},
+    files,
+    fs::FileSystem,
 };
 use codespan_reporting::diagnostic::{Diagnostic, Label};
+use indexmap::IndexMap;
 use serde_ini_spanned as ini;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -16,6 +20,8 @@ pub use ini::value::Options;
 
 #[derive(thiserror::Error, Debug)]
 pub enum ParseError {
+    #[error("{message}")]
+    InvalidConfiguration { message: String, span: Span },
     #[error("{message}")]
     MissingKey {
         key: String,
@@ -83,6 +89,13 @@ mod diagnostics {
                             Label::secondary(file_id, span.clone()).with_message(message),
                         ]),
                 ],
+                Self::InvalidConfiguration { message, span, .. } => vec![
+                    Diagnostic::error()
+                        .with_message("invalid configuration".to_string())
+                        .with_labels(vec![
+                            Label::secondary(file_id, span.clone()).with_message(message),
+                        ]),
+                ],
                 Self::MissingKey {
                     message, key, span, ..
                 } => vec![
@@ -132,7 +145,7 @@ pub fn as_bool(value: &ini::Spanned<String>) -> Result<bool, ParseError> {
         "false" => Ok(false),
         _ => Err(ParseError::UnexpectedType {
             message: "expected a boolean".to_string(),
-            expected: vec![ValueKind::String],
+            expected: vec![ValueKind::Boolean],
             span: value.span.clone(),
         }),
     }
@@ -243,15 +256,120 @@ pub(crate) fn parse_part_config(
         .map(|value| as_string_array(value, false))
         .transpose()?
         .unwrap_or_default();
+    let first_value = value
+        .remove_option("first_value")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
+    let always_increment = value
+        .remove_option("always_increment")
+        .as_ref()
+        .map(as_bool)
+        .transpose()?
+        .unwrap_or(false);
+    let calver_format = value
+        .remove_option("calver_format")
+        .and_then(as_optional)
+        .map(|value| {
+            // validate the format string but keep it raw
+            let raw = value.as_ref().clone();
+            as_format_string(value).map(|_| raw)
+        })
+        .transpose()?;
+    let depends_on = value
+        .remove_option("depends_on")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
 
     Ok(VersionComponentSpec {
         independent,
         optional_value,
         values,
-        ..VersionComponentSpec::default()
+        first_value,
+        always_increment,
+        calver_format,
+        depends_on,
+    })
+}
+
+pub(crate) fn parse_changelog_config(
+    mut value: ini::SectionProxyMut<'_>,
+) -> Result<ChangelogConfig, ParseError> {
+    let path = value
+        .remove_option("path")
+        .and_then(as_optional)
+        .map(|value| PathBuf::from(value.into_inner()));
+    let heading = value
+        .remove_option("heading")
+        .and_then(as_optional)
+        .map(as_format_string)
+        .transpose()?;
+    let entry = value
+        .remove_option("entry")
+        .and_then(as_optional)
+        .map(as_format_string)
+        .transpose()?;
+    let sections = value
+        .remove_option("sections")
+        .and_then(as_optional)
+        .map(|value| {
+            as_spanned_string_array(value, true)?
+                .into_iter()
+                .filter(|section| !section.as_ref().trim().is_empty())
+                .map(|section| {
+                    let ini::Spanned { inner, span } = section;
+                    let (commit_type, title) =
+                        inner
+                            .split_once(':')
+                            .ok_or_else(|| ParseError::UnexpectedType {
+                                message: format!(
+                                    "changelog section {inner:?} must be of the form `type: title`"
+                                ),
+                                expected: vec![ValueKind::Table],
+                                span,
+                            })?;
+                    Ok((commit_type.trim().to_string(), title.trim().to_string()))
+                })
+                .collect::<Result<IndexMap<String, String>, _>>()
+        })
+        .transpose()?;
+
+    Ok(ChangelogConfig {
+        path,
+        heading,
+        entry,
+        sections,
     })
 }
 
+pub(crate) fn parse_context_config(
+    mut value: ini::SectionProxyMut<'_>,
+) -> Result<crate::context::Variables, ParseError> {
+    let section_span = value.span();
+    let names: Vec<String> = value.keys().map(|key| key.as_ref().clone()).collect();
+    let mut spans = HashMap::new();
+    let mut variables = crate::context::Variables::new();
+    for name in names {
+        let Some(template) = value.remove_option(&name) else {
+            continue;
+        };
+        spans.insert(name.clone(), template.span.clone());
+        variables.insert(name, as_format_string(template)?);
+    }
+
+    if let Err(err) = crate::context::evaluation_order(&variables) {
+        // point to the first variable of the cycle
+        let span = match &err {
+            crate::context::Error::Cycle { cycle } => spans.remove(&cycle[0]),
+            crate::context::Error::Format { .. } => None,
+        };
+        return Err(ParseError::InvalidConfiguration {
+            message: err.to_string(),
+            span: span.unwrap_or(section_span),
+        });
+    }
+    Ok(variables)
+}
+
 fn parse_search_pattern(
     value: &mut ini::SectionProxyMut<'_>,
     is_regex: Option<bool>,
@@ -379,6 +497,31 @@ pub(crate) fn parse_global_config(
         .remove_option("commit_args")
         .and_then(as_optional)
         .map(ini::Spanned::into_inner);
+    let push = value
+        .remove_option("push")
+        .as_ref()
+        .map(as_bool)
+        .transpose()?;
+    let push_remote = value
+        .remove_option("push_remote")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
+    let push_args = value
+        .remove_option("push_args")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
+    let auto_bump_breaking = value
+        .remove_option("auto_bump_breaking")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
+    let auto_bump_feat = value
+        .remove_option("auto_bump_feat")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
+    let auto_bump_fix = value
+        .remove_option("auto_bump_fix")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
 
     // extra stuff
     let setup_hooks = value
@@ -435,6 +578,12 @@ pub(crate) fn parse_global_config(
             tag_message,
             commit_message,
             commit_args,
+            push,
+            push_remote,
+            push_args,
+            auto_bump_breaking,
+            auto_bump_feat,
+            auto_bump_fix,
             // extra stuff
             setup_hooks,
             pre_commit_hooks,
@@ -483,6 +632,10 @@ pub(crate) fn parse_file_config(
         .as_ref()
         .map(as_bool)
         .transpose()?;
+    let key_path = value
+        .remove_option("key_path")
+        .and_then(as_optional)
+        .map(ini::Spanned::into_inner);
 
     Ok(FileConfig {
         parse_version_pattern,
@@ -491,10 +644,48 @@ pub(crate) fn parse_file_config(
         replace,
         ignore_missing_file,
         ignore_missing_version,
+        key_path,
     })
 }
 
+/// Record the spans of the template `keys` of a `section`, or of all keys if `keys` is `None`.
+///
+/// The spans point at the keys, since values are consumed when the section is parsed.
+fn insert_template_spans(
+    spans: &mut config::TemplateSpans,
+    section: &ini::SectionProxyMut<'_>,
+    prefix: &str,
+    keys: Option<&[&str]>,
+) {
+    for key in section.keys() {
+        let name = key.as_ref().as_str();
+        if keys.is_none_or(|keys| keys.contains(&name)) {
+            spans.insert(format!("{prefix}{name}"), key.span.clone());
+        }
+    }
+}
+
+/// Report the keys of a `section` that are not `known` as warnings, or as errors if `strict`.
+fn report_unknown_keys(
+    section: &ini::SectionProxyMut<'_>,
+    known: &[&'static str],
+    table: &'static str,
+    file_id: FileId,
+    strict: bool,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    let keys = section
+        .keys()
+        .map(|key| (key.as_ref().as_str(), key.span.clone()));
+    config::keys::report_unknown_keys(keys, known, table, file_id, strict, diagnostics);
+}
+
 impl config::Config {
+    /// Parse the bumpversion sections of an INI `config`.
+    ///
+    /// Unknown keys, and unknown sections unless `allow_unknown`,
+    /// are reported as warnings, or as errors if `strict`.
+    #[allow(clippy::too_many_lines)]
     pub fn from_ini_value(
         mut config: ini::Value,
         file_id: FileId,
@@ -543,9 +734,46 @@ impl config::Config {
 
             match section_parts[..] {
                 ["bumpversion"] => {
+                    report_unknown_keys(
+                        &section,
+                        &GLOBAL_KEYS,
+                        "global config",
+                        file_id,
+                        strict,
+                        diagnostics,
+                    );
+                    let keys = Some(config::templates::GLOBAL_TEMPLATE_KEYS.as_slice());
+                    insert_template_spans(&mut out.template_spans, &section, "", keys);
                     (search_is_regex_compat, out.global) = parse_global_config(section)?;
                 }
+                ["bumpversion", "changelog"] => {
+                    let keys = Some(config::templates::CHANGELOG_TEMPLATE_KEYS.as_slice());
+                    insert_template_spans(&mut out.template_spans, &section, "changelog.", keys);
+                    out.changelog = Some(parse_changelog_config(section)?);
+                }
+                ["bumpversion", "context"] => {
+                    insert_template_spans(&mut out.template_spans, &section, "context.", None);
+                    out.context = parse_context_config(section)?;
+                }
                 ["bumpversion", prefix, value] => {
+                    if prefix.starts_with("file") || prefix.starts_with("glob") {
+                        report_unknown_keys(
+                            &section,
+                            &FILE_KEYS,
+                            "file config",
+                            file_id,
+                            strict,
+                            diagnostics,
+                        );
+                        let keys = Some(config::templates::FILE_TEMPLATE_KEYS.as_slice());
+                        let file_prefix = format!("files[{}].", out.files.len());
+                        insert_template_spans(
+                            &mut out.template_spans,
+                            &section,
+                            &file_prefix,
+                            keys,
+                        );
+                    }
                     if prefix.starts_with("file") {
                         let config = parse_file_config(section, search_is_regex_compat)?;
                         out.files.push((InputFile::Path(value.into()), config));
@@ -559,6 +787,14 @@ impl config::Config {
                             config,
                         ));
                     } else if prefix.starts_with("part") {
+                        report_unknown_keys(
+                            &section,
+                            &PART_KEYS,
+                            "part config",
+                            file_id,
+                            strict,
+                            diagnostics,
+                        );
                         let config = parse_part_config(section)?;
                         out.components.insert(value.into(), config);
                     } else if !allow_unknown {
@@ -617,11 +853,19 @@ impl config::Config {
     }
 }
 
-static CONFIG_CURRENT_VERSION_REGEX: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(
-    || {
-        regex::RegexBuilder::new(r"(?P<section_prefix>\\[bumpversion]\n[^\[]*current_version\\s*=\\s*)(?P<version>\{current_version\})").multi_line(true).build().unwrap()
-    },
-);
+/// Return a regex matching the `current_version` key of the bumpversion section.
+///
+/// Only `[bumpversion]` and `[tool:bumpversion]` sections are considered,
+/// and the section ends at the next section header.
+fn config_current_version_regex(current_version: &str) -> regex::Regex {
+    regex::RegexBuilder::new(&format!(
+        r"^(?P<section_prefix>\[(?:tool:)?bumpversion\][^\n]*\n(?:[^\[\n][^\n]*\n|\n)*?[ \t]*current_version[ \t]*[=:][ \t]*)(?P<version>{})[ \t]*$",
+        regex::escape(current_version)
+    ))
+    .multi_line(true)
+    .build()
+    .expect("valid regex")
+}
 
 /// Update the `current_version` key in the configuration file.
 ///
@@ -629,67 +873,48 @@ static CONFIG_CURRENT_VERSION_REGEX: std::sync::LazyLock<regex::Regex> = std::sy
 /// it will use a regular expression to just replace the `current_version` value.
 /// The idea is it will avoid unintentional changes (like formatting) to the
 /// config file.
-pub async fn replace_version<K, V, S>(
+pub async fn replace_version<FS, K, V, S>(
+    fs: &FS,
     path: &Path,
     _config: &config::FinalizedConfig,
-    _ctx: &HashMap<K, V, S>,
-    dry_run: bool,
-) -> Result<Option<files::Modification>, IoError>
+    ctx: &HashMap<K, V, S>,
+) -> Result<Option<files::Modification>, files::ReplaceVersionError>
 where
+    FS: FileSystem,
     K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
     V: AsRef<str> + std::fmt::Debug,
     S: std::hash::BuildHasher,
 {
-    let as_io_error = |source: std::io::Error| -> IoError { IoError::new(source, path) };
-    let before = tokio::fs::read_to_string(path).await.map_err(as_io_error)?;
-    // let extension = path.extension().and_then(|ext| ext.to_str());
-    let _matches = CONFIG_CURRENT_VERSION_REGEX.find_iter(&before);
-    // let new_config = if extension == Some("cfg") && matches.count() > 0 {
-
-    // let new_config = if matches.count() > 0 {
-    //     let replacement = format!(r#"\g<section_prefix>{new_version}"#);
-    //     CONFIG_CURRENT_VERSION_REGEX.replace_all(&existing_config, replacement)
-    // } else {
-    //     tracing::info!("could not find current version ({current_version}) in {path:?}");
-    //     return Ok(None);
-    // };
-
-    // if dry_run {
-    //     tracing::info!("Would write to config file {path:?}");
-    // } else {
-    //     tracing::info!("Writing to config file {path:?}");
-    // }
-
-    // let label_existing = format!("{path:?} (before)");
-    // let label_new = format!("{path:?} (after)");
-    // let diff = similar_asserts::SimpleDiff::from_str(
-    //     &existing_config,
-    //     &new_config,
-    //     &label_existing,
-    //     &label_new,
-    // );
-    let after = before.clone();
-
-    if !dry_run {
-        use tokio::io::AsyncWriteExt;
-        let file = tokio::fs::OpenOptions::new()
-            .write(true)
-            .create(false)
-            .truncate(true)
-            .open(path)
-            .await
-            .map_err(as_io_error)?;
-        let mut writer = tokio::io::BufWriter::new(file);
-        writer
-            .write_all(after.as_bytes())
-            .await
-            .map_err(as_io_error)?;
-        writer.flush().await.map_err(as_io_error)?;
+    tracing::info!(config = ?path, "processing config file");
+
+    let before = fs.read_to_string(path).await?;
+
+    let ctx: HashMap<&str, &str> = ctx
+        .iter()
+        .map(|(k, v)| (std::borrow::Borrow::borrow(k), v.as_ref()))
+        .collect();
+    let search_pattern = "{current_version}";
+    let replace_pattern = "{new_version}";
+    let current_version = PythonFormatString::parse(search_pattern)?.format(&ctx, true)?;
+    let new_version = PythonFormatString::parse(replace_pattern)?.format(&ctx, true)?;
+
+    let search_regex = config_current_version_regex(&current_version);
+    if !search_regex.is_match(&before) {
+        tracing::info!("could not find current version ({current_version}) in {path:?}");
+        return Ok(None);
     }
+    let replacement = format!("${{section_prefix}}{}", new_version.replace('$', "$$"));
+    let after = search_regex.replace_all(&before, replacement).to_string();
+
     let modification = files::Modification {
         before,
         after,
-        replacements: vec![],
+        replacements: vec![files::Replacement {
+            search_pattern: search_pattern.to_string(),
+            search: current_version,
+            replace_pattern: replace_pattern.to_string(),
+            replace: new_version,
+        }],
     };
     Ok(Some(modification))
 }
@@ -699,13 +924,17 @@ where
 mod tests {
     use crate::{
         config::{
-            Config, FileConfig, GlobalConfig, InputFile, RegexTemplate, VersionComponentSpec,
+            ChangelogConfig, Config, FileConfig, GlobalConfig, InputFile, RegexTemplate,
+            TemplateSpans, VersionComponentSpec,
         },
         diagnostics::{BufferedPrinter, ToDiagnostics},
         f_string::{PythonFormatString, Value},
+        fs::LocalFileSystem,
     };
     use codespan_reporting::diagnostic::Diagnostic;
     use color_eyre::eyre;
+    use indexmap::IndexMap;
+    use std::collections::HashMap;
 
     use serde_ini_spanned::value::Options;
 
@@ -789,6 +1018,9 @@ mod tests {
                 ),
             ],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         similar_asserts::assert_eq!(config, Some(expected));
         Ok(())
@@ -941,6 +1173,9 @@ mod tests {
                 ),
             ],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         similar_asserts::assert_eq!(config, Some(expected));
         Ok(())
@@ -1065,6 +1300,9 @@ mod tests {
             )]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         similar_asserts::assert_eq!(config, Some(expected));
         Ok(())
@@ -1114,6 +1352,9 @@ mod tests {
                 },
             )],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         similar_asserts::assert_eq!(config, Some(expected));
         Ok(())
@@ -1163,8 +1404,352 @@ mod tests {
                 },
             )],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
+        };
+        similar_asserts::assert_eq!(config, Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cfg_ini_with_full_part_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_cfg = indoc::indoc! {r"
+            [bumpversion]
+            current_version = 2024.01.1
+            parse = (?P<release>\d{4}\.\d{2})\.(?P<build>\d+)
+            serialize = {release}.{build}
+
+            [bumpversion:part:release]
+            calver_format = {YYYY}.{0M}
+            always_increment = True
+
+            [bumpversion:part:build]
+            first_value = 1
+            depends_on = release
+        "};
+
+        let config = parse_ini(
+            bumpversion_cfg,
+            Options::default(),
+            &BufferedPrinter::default(),
+        )
+        .0?;
+        let components = config.map(|config| config.components);
+        similar_asserts::assert_eq!(
+            components,
+            Some(
+                [
+                    (
+                        "release".to_string(),
+                        VersionComponentSpec {
+                            calver_format: Some("{YYYY}.{0M}".to_string()),
+                            always_increment: true,
+                            ..VersionComponentSpec::default()
+                        },
+                    ),
+                    (
+                        "build".to_string(),
+                        VersionComponentSpec {
+                            first_value: Some("1".to_string()),
+                            depends_on: Some("release".to_string()),
+                            ..VersionComponentSpec::default()
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cfg_ini_with_unknown_keys() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_cfg = indoc::indoc! {r"
+            [bumpversion]
+            current_version = 1.2.3
+            tagname = v{new_version}
+
+            [bumpversion:file:README.md]
+            serach = {current_version}
+
+            [bumpversion:part:release]
+            first_valeu = dev
+        "};
+
+        let printer = BufferedPrinter::default();
+        let (config, _file_id, diagnostics) =
+            parse_ini(bumpversion_cfg, Options::default(), &printer);
+        assert!(config?.is_some());
+
+        let messages: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.notes.clone()))
+            .collect();
+        similar_asserts::assert_eq!(
+            messages,
+            [
+                (
+                    "unknown key `tagname` in global config",
+                    vec!["did you mean `tag_name`?".to_string()]
+                ),
+                (
+                    "unknown key `serach` in file config",
+                    vec!["did you mean `search`?".to_string()]
+                ),
+                (
+                    "unknown key `first_valeu` in part config",
+                    vec!["did you mean `first_value`?".to_string()]
+                ),
+            ]
+        );
+        let lines = diagnostics
+            .iter()
+            .map(|diagnostic| printer.lines(diagnostic))
+            .collect::<Result<Vec<_>, _>>()?;
+        similar_asserts::assert_eq!(lines, [[2], [5], [8]]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cfg_ini_with_invalid_part_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_cfg = indoc::indoc! {r"
+            [bumpversion]
+            current_version = 1.0.0
+
+            [bumpversion:part:patch]
+            always_increment = yes
+        "};
+
+        let printer = BufferedPrinter::default();
+        let (config, _file_id, diagnostics) =
+            parse_ini(bumpversion_cfg, Options::default(), &printer);
+        let err = config.unwrap_err();
+        similar_asserts::assert_eq!(&err.to_string(), "expected a boolean");
+        similar_asserts::assert_eq!(printer.lines(&diagnostics[0]).ok(), Some(vec![4]));
+        Ok(())
+    }
+
+    async fn replace_version_in_config(
+        config: &str,
+        file_name: &str,
+        current_version: &str,
+        new_version: &str,
+    ) -> eyre::Result<(String, Option<crate::files::Modification>)> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(file_name);
+        tokio::fs::write(&path, config).await?;
+
+        let finalized = Config::default().finalize();
+        let ctx: HashMap<&str, &str> = [
+            ("current_version", current_version),
+            ("new_version", new_version),
+        ]
+        .into_iter()
+        .collect();
+        let modification =
+            super::replace_version(&LocalFileSystem, &path, &finalized, &ctx).await?;
+        // the config file is not written
+        similar_asserts::assert_eq!(tokio::fs::read_to_string(&path).await?, config);
+        let after = modification.as_ref().map_or_else(
+            || config.to_string(),
+            |modification| modification.after.clone(),
+        );
+        Ok((after, modification))
+    }
+
+    #[tokio::test]
+    async fn test_replace_version_bumpversion_cfg() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_cfg = indoc::indoc! {r"
+            [metadata]
+            current_version = 1.2.3
+
+            [bumpversion]
+            commit = True
+            current_version = 1.2.3
+            tag = True
+
+            [bumpversion:file:setup.py]
+            search = version='{current_version}'
+        "};
+
+        let (after, modification) =
+            replace_version_in_config(bumpversion_cfg, ".bumpversion.cfg", "1.2.3", "1.3.0")
+                .await?;
+        let want = indoc::indoc! {r"
+            [metadata]
+            current_version = 1.2.3
+
+            [bumpversion]
+            commit = True
+            current_version = 1.3.0
+            tag = True
+
+            [bumpversion:file:setup.py]
+            search = version='{current_version}'
+        "};
+        similar_asserts::assert_eq!(after, want);
+
+        let modification = modification.ok_or_else(|| eyre::eyre!("missing modification"))?;
+        similar_asserts::assert_eq!(modification.after, want);
+        similar_asserts::assert_eq!(
+            modification
+                .replacements
+                .iter()
+                .map(|r| (r.search.as_str(), r.replace.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("1.2.3", "1.3.0")]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replace_version_setup_cfg() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let setup_cfg = indoc::indoc! {r"
+            [tool:bumpversion]
+            current_version = 0.1.8
+            commit = True
+
+            [bdist_wheel]
+            universal = 1
+        "};
+
+        let (after, modification) =
+            replace_version_in_config(setup_cfg, "setup.cfg", "0.1.8", "0.1.9").await?;
+        let want = indoc::indoc! {r"
+            [tool:bumpversion]
+            current_version = 0.1.9
+            commit = True
+
+            [bdist_wheel]
+            universal = 1
+        "};
+        similar_asserts::assert_eq!(after, want);
+        assert!(modification.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replace_version_outside_of_bumpversion_section() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let setup_cfg = indoc::indoc! {r"
+            [bumpversion]
+            commit = True
+
+            [metadata]
+            current_version = 0.1.8
+        "};
+
+        let (after, modification) =
+            replace_version_in_config(setup_cfg, "setup.cfg", "0.1.8", "0.1.9").await?;
+        similar_asserts::assert_eq!(after, setup_cfg);
+        assert!(modification.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_cfg_ini_with_changelog_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_cfg = indoc::indoc! {r"
+            [bumpversion]
+            current_version = 1.2.3
+
+            [bumpversion:changelog]
+            heading = ## v{new_version}
+            sections =
+                fix: Bug Fixes
+                feat: Features
+        "};
+
+        let config = parse_ini(
+            bumpversion_cfg,
+            Options::default(),
+            &BufferedPrinter::default(),
+        )
+        .0?;
+        let expected = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                ..GlobalConfig::empty()
+            },
+            files: vec![],
+            components: [].into_iter().collect(),
+            changelog: Some(ChangelogConfig {
+                path: None,
+                heading: Some(PythonFormatString(vec![
+                    Value::String("## v".to_string()),
+                    Value::Argument("new_version".to_string()),
+                ])),
+                entry: None,
+                sections: Some(
+                    [("fix", "Bug Fixes"), ("feat", "Features")]
+                        .into_iter()
+                        .map(|(commit_type, title)| (commit_type.to_string(), title.to_string()))
+                        .collect(),
+                ),
+            }),
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         similar_asserts::assert_eq!(config, Some(expected));
         Ok(())
     }
+
+    #[test]
+    fn parse_cfg_ini_with_context_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_cfg = indoc::indoc! {r"
+            [bumpversion]
+            current_version = 1.2.3
+
+            [bumpversion:context]
+            image = ghcr.io/romnn/bumpversion
+            docker_tag = {image}:{new_version}
+        "};
+
+        let config = parse_ini(
+            bumpversion_cfg,
+            Options::default(),
+            &BufferedPrinter::default(),
+        )
+        .0?
+        .unwrap();
+        similar_asserts::assert_eq!(
+            config.context,
+            [
+                (
+                    "image",
+                    PythonFormatString(vec![Value::String(
+                        "ghcr.io/romnn/bumpversion".to_string()
+                    )]),
+                ),
+                (
+                    "docker_tag",
+                    PythonFormatString(vec![
+                        Value::Argument("image".to_string()),
+                        Value::String(":".to_string()),
+                        Value::Argument("new_version".to_string()),
+                    ]),
+                ),
+            ]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<IndexMap<_, _>>()
+        );
+        Ok(())
+    }
 }
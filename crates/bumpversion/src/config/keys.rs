@@ -0,0 +1,176 @@
+//! Detection of unknown keys in config tables.
+//!
+//! Keys that are not recognized are ignored when parsing, so a typo such as `comit = true`
+//! is reported together with the closest known key.
+use crate::diagnostics::{FileId, Span, ToDiagnostics};
+use codespan_reporting::diagnostic::{Diagnostic, Label, Severity};
+
+/// Keys of the global config.
+pub(crate) const GLOBAL_KEYS: [&str; 32] = [
+    "current_version",
+    "search",
+    "regex",
+    "allow_dirty",
+    "parse",
+    "serialize",
+    "replace",
+    "no_configured_files",
+    "ignore_missing_files",
+    "ignore_missing_version",
+    "dry_run",
+    "commit",
+    "tag",
+    "sign_tag",
+    "sign_tags",
+    "tag_name",
+    "tag_message",
+    "commit_message",
+    "message",
+    "commit_args",
+    "push",
+    "push_remote",
+    "push_args",
+    "auto_bump_breaking",
+    "auto_bump_feat",
+    "auto_bump_fix",
+    "setup_hooks",
+    "pre_commit_hooks",
+    "post_commit_hooks",
+    "included_paths",
+    "excluded_paths",
+    "additional_files",
+];
+
+/// Keys of a file config.
+pub(crate) const FILE_KEYS: [&str; 9] = [
+    "search",
+    "regex",
+    "parse",
+    "serialize",
+    "replace",
+    "ignore_missing_files",
+    "ignore_missing_file",
+    "ignore_missing_version",
+    "key_path",
+];
+
+/// Keys of a part config.
+pub(crate) const PART_KEYS: [&str; 7] = [
+    "independent",
+    "optional_value",
+    "values",
+    "first_value",
+    "always_increment",
+    "calver_format",
+    "depends_on",
+];
+
+/// Minimum similarity of a known key to be suggested for an unknown key.
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+
+/// A key of a config table that is not recognized and therefore ignored.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown key `{key}` in {table}")]
+pub struct UnknownKey {
+    /// The unknown key.
+    pub key: String,
+    /// Description of the table containing the key, e.g. `file config`.
+    pub table: &'static str,
+    /// The most similar known key, if any.
+    pub suggestion: Option<&'static str>,
+    /// Span of the key.
+    pub span: Span,
+}
+
+impl ToDiagnostics for UnknownKey {
+    fn to_diagnostics<F: Copy + PartialEq>(&self, file_id: F) -> Vec<Diagnostic<F>> {
+        let notes = self
+            .suggestion
+            .map(|suggestion| format!("did you mean `{suggestion}`?"))
+            .into_iter()
+            .collect();
+        vec![
+            Diagnostic::error()
+                .with_message(self.to_string())
+                .with_labels(vec![
+                    Label::primary(file_id, self.span.clone())
+                        .with_message("this key will be ignored"),
+                ])
+                .with_notes(notes),
+        ]
+    }
+}
+
+/// Returns the known key most similar to `key`, if any is similar enough.
+#[must_use]
+pub fn suggest<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|candidate| (strsim::jaro_winkler(key, candidate), *candidate))
+        .filter(|(similarity, _)| *similarity >= SUGGESTION_THRESHOLD)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
+}
+
+/// Find the `keys` of a `table` that are not `known`.
+pub fn unknown_keys<'a, S>(
+    keys: impl IntoIterator<Item = (&'a str, S)>,
+    known: &[&'static str],
+    table: &'static str,
+) -> Vec<UnknownKey>
+where
+    S: Into<Span>,
+{
+    keys.into_iter()
+        .filter(|(key, _)| !known.contains(key))
+        .map(|(key, span)| UnknownKey {
+            key: key.to_string(),
+            table,
+            suggestion: suggest(key, known),
+            span: span.into(),
+        })
+        .collect()
+}
+
+/// Report the unknown `keys` of a `table` as warnings, or as errors if `strict`.
+pub(crate) fn report_unknown_keys<'a, S>(
+    keys: impl IntoIterator<Item = (&'a str, S)>,
+    known: &[&'static str],
+    table: &'static str,
+    file_id: FileId,
+    strict: bool,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) where
+    S: Into<Span>,
+{
+    let severity = if strict {
+        Severity::Error
+    } else {
+        Severity::Warning
+    };
+    diagnostics.extend(
+        unknown_keys(keys, known, table)
+            .iter()
+            .flat_map(|unknown| unknown.to_diagnostics(file_id))
+            .map(|diagnostic| Diagnostic {
+                severity,
+                ..diagnostic
+            }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test]
+    fn suggest_similar_keys() {
+        crate::tests::init();
+
+        let known = super::GLOBAL_KEYS;
+        sim_assert_eq!(super::suggest("comit", &known), Some("commit"));
+        sim_assert_eq!(super::suggest("tagname", &known), Some("tag_name"));
+        sim_assert_eq!(super::suggest("allow_dirt", &known), Some("allow_dirty"));
+        sim_assert_eq!(super::suggest("xyz", &known), None);
+    }
+}
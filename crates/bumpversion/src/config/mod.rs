@@ -2,23 +2,30 @@
 //!
 //! Provides support for reading bumpversion configuration from various file formats (TOML, INI),
 //! applying defaults, and finalizing settings for version bump operations.
+pub mod cargo_toml;
 pub mod change;
+pub mod changelog;
 pub mod defaults;
 pub mod file;
 pub mod global;
 pub mod ini;
+pub mod keys;
 pub mod pyproject_toml;
 pub mod regex;
+pub mod templates;
 pub mod toml;
 pub mod version;
 
 pub use change::FileChange;
+pub use changelog::{ChangelogConfig, FinalizedChangelogConfig};
 pub use file::{FileConfig, FinalizedFileConfig};
 pub use global::{GlobalConfig, GlobalConfigFinalized};
 pub use regex::{Regex, RegexTemplate};
+pub use templates::TemplateSpans;
 pub use version::{VersionComponentConfigs, VersionComponentSpec};
 
 use crate::files::IoError;
+use indexmap::IndexMap;
 use std::path::{Path, PathBuf};
 
 #[derive(thiserror::Error, Debug)]
@@ -43,13 +50,41 @@ pub enum Error {
         #[source]
         source: ini::ParseError,
     },
-    /// Cargo.toml parsing not yet supported or failed.
+    /// Cargo.toml parsing error.
     #[error("failed to parse {path:?}")]
     CargoToml {
         /// Path to the Cargo.toml file.
         path: PathBuf,
-        // #[source]
-        // source: ini::ParseError,
+        #[source]
+        source: pyproject_toml::ParseError,
+    },
+    /// The format of the config file could not be inferred.
+    #[error("cannot infer the config file format of {path:?}")]
+    UnknownFormat {
+        /// Path to the config file.
+        path: PathBuf,
+    },
+    /// The config file does not contain a bumpversion configuration.
+    #[error("{path:?} does not contain a bumpversion configuration")]
+    MissingConfig {
+        /// Path to the config file.
+        path: PathBuf,
+    },
+    /// The config file has errors, such as unknown keys when parsing strictly.
+    #[error("found {count} error(s) in {path:?}")]
+    InvalidConfig {
+        /// Path to the config file.
+        path: PathBuf,
+        /// Number of errors found.
+        count: usize,
+    },
+    /// Templates of the config file use unknown arguments.
+    #[error("found {count} problem(s) with the templates in {path:?}")]
+    InvalidTemplates {
+        /// Path to the config file.
+        path: PathBuf,
+        /// Number of problems found.
+        count: usize,
     },
     /// Background task join error.
     #[error("failed to join spawned task")]
@@ -79,7 +114,86 @@ pub enum ConfigFile {
     CargoToml(PathBuf),
 }
 
+/// Format of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigFormat {
+    /// `.bumpversion.toml` format (TOML with a `tool.bumpversion` table).
+    BumpversionToml,
+    /// `pyproject.toml` format (TOML with a `tool.bumpversion` table).
+    PyProject,
+    /// `.bumpversion.cfg` format (INI with `bumpversion` sections).
+    BumpversionCfg,
+    /// `setup.cfg` format (INI with `bumpversion` sections among others).
+    SetupCfg,
+    /// `Cargo.toml` format (TOML with a `package.metadata.bumpversion` table).
+    CargoToml,
+}
+
+impl ConfigFormat {
+    /// Infer the format of a config file from its file name or extension.
+    #[must_use]
+    pub fn infer(path: &Path) -> Option<Self> {
+        let file_name = path.file_name().and_then(|name| name.to_str())?;
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        match file_name {
+            "pyproject.toml" => Some(Self::PyProject),
+            "setup.cfg" => Some(Self::SetupCfg),
+            "Cargo.toml" => Some(Self::CargoToml),
+            _ => match extension.map(str::to_ascii_lowercase).as_deref() {
+                Some("toml") => Some(Self::BumpversionToml),
+                Some("cfg" | "ini") => Some(Self::BumpversionCfg),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error(
+    "invalid config format {0:?} (expected one of `bumpversion-toml`, `pyproject-toml`, `bumpversion-cfg`, `setup-cfg`, `cargo-toml`)"
+)]
+pub struct InvalidConfigFormatError(String);
+
+impl std::str::FromStr for ConfigFormat {
+    type Err = InvalidConfigFormatError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "bumpversion-toml" => Ok(Self::BumpversionToml),
+            "pyproject-toml" => Ok(Self::PyProject),
+            "bumpversion-cfg" => Ok(Self::BumpversionCfg),
+            "setup-cfg" => Ok(Self::SetupCfg),
+            "cargo-toml" => Ok(Self::CargoToml),
+            _ => Err(InvalidConfigFormatError(value.to_string())),
+        }
+    }
+}
+
 impl ConfigFile {
+    /// Create a new `ConfigFile` of the given `format`.
+    #[must_use]
+    pub fn new(format: ConfigFormat, path: PathBuf) -> Self {
+        match format {
+            ConfigFormat::BumpversionToml => Self::BumpversionToml(path),
+            ConfigFormat::PyProject => Self::PyProject(path),
+            ConfigFormat::BumpversionCfg => Self::BumpversionCfg(path),
+            ConfigFormat::SetupCfg => Self::SetupCfg(path),
+            ConfigFormat::CargoToml => Self::CargoToml(path),
+        }
+    }
+
+    /// The format of the config file.
+    #[must_use]
+    pub fn format(&self) -> ConfigFormat {
+        match self {
+            Self::BumpversionToml(_) => ConfigFormat::BumpversionToml,
+            Self::PyProject(_) => ConfigFormat::PyProject,
+            Self::BumpversionCfg(_) => ConfigFormat::BumpversionCfg,
+            Self::SetupCfg(_) => ConfigFormat::SetupCfg,
+            Self::CargoToml(_) => ConfigFormat::CargoToml,
+        }
+    }
+
     #[must_use]
     pub fn path(&self) -> &Path {
         #[allow(clippy::match_same_arms)]
@@ -162,6 +276,12 @@ pub struct Config {
     pub files: Vec<(InputFile, file::FileConfig)>,
     /// Version components to parse and serialize.
     pub components: version::VersionComponentConfigs,
+    /// Changelog to update when bumping, if any.
+    pub changelog: Option<changelog::ChangelogConfig>,
+    /// User-defined context variables available in all templates.
+    pub context: crate::context::Variables,
+    /// Source spans of the templates, used for diagnostics.
+    pub template_spans: TemplateSpans,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -173,6 +293,10 @@ pub struct FinalizedConfig {
     pub files: Vec<(InputFile, file::FinalizedFileConfig)>,
     /// Version component specifications.
     pub components: version::VersionComponentConfigs,
+    /// Changelog to update when bumping, if any.
+    pub changelog: Option<changelog::FinalizedChangelogConfig>,
+    /// User-defined context variables available in all templates.
+    pub context: crate::context::Variables,
 }
 
 impl Default for Config {
@@ -181,6 +305,9 @@ impl Default for Config {
             global: global::GlobalConfig::empty(),
             files: Vec::new(),
             components: version::VersionComponentConfigs::default(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         }
     }
 }
@@ -215,6 +342,8 @@ impl Config {
                 .map(|(path, config)| (path, config.finalize()))
                 .collect(),
             components: self.components,
+            changelog: self.changelog.map(changelog::ChangelogConfig::finalize),
+            context: self.context,
         }
     }
 }
@@ -222,13 +351,34 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::{
-        Config, global,
+        Config, TemplateSpans, global,
         version::{self, VersionComponentConfigs, VersionComponentSpec},
     };
     use color_eyre::eyre;
     use indexmap::IndexMap;
     use similar_asserts::assert_eq as sim_assert_eq;
 
+    #[test]
+    fn test_infer_config_format() {
+        use super::ConfigFormat;
+        use std::path::Path;
+
+        crate::tests::init();
+        let infer = |path: &str| ConfigFormat::infer(Path::new(path));
+        sim_assert_eq!(infer("pyproject.toml"), Some(ConfigFormat::PyProject));
+        sim_assert_eq!(infer("./Cargo.toml"), Some(ConfigFormat::CargoToml));
+        sim_assert_eq!(infer("/a/setup.cfg"), Some(ConfigFormat::SetupCfg));
+        sim_assert_eq!(infer("ci/bump.toml"), Some(ConfigFormat::BumpversionToml));
+        sim_assert_eq!(
+            infer(".bumpversion.cfg"),
+            Some(ConfigFormat::BumpversionCfg)
+        );
+        sim_assert_eq!(infer("bumpversion.ini"), Some(ConfigFormat::BumpversionCfg));
+        sim_assert_eq!(infer("bumpversion.yaml"), None);
+        sim_assert_eq!("setup-cfg".parse(), Ok(ConfigFormat::SetupCfg));
+        assert!("yaml".parse::<ConfigFormat>().is_err());
+    }
+
     #[test]
     fn test_get_all_component_configs_dependent() -> eyre::Result<()> {
         crate::tests::init();
@@ -241,6 +391,9 @@ mod tests {
             },
             files: vec![],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         let config = config.finalize();
         let component_configs = version::version_component_configs(&config);
@@ -307,6 +460,9 @@ mod tests {
             ]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         let config = config.finalize();
         let component_configs = version::version_component_configs(&config);
@@ -1,7 +1,7 @@
 use crate::{
     config::{
-        self, Config, InputFile, file::FileConfig, global::GlobalConfig, regex::RegexTemplate,
-        version::VersionComponentSpec,
+        self, ChangelogConfig, Config, InputFile, file::FileConfig, global::GlobalConfig,
+        regex::RegexTemplate, version::VersionComponentSpec,
     },
     diagnostics::{FileId, Span},
     f_string::PythonFormatString,
@@ -258,12 +258,27 @@ pub fn as_str<'de>(value: &'de toml::Value<'de>) -> Result<&'de str, ParseError>
 pub fn as_bool<'de>(value: &'de toml::Value<'de>) -> Result<bool, ParseError> {
     value.as_bool().ok_or_else(|| ParseError::UnexpectedType {
         message: "expected a boolean".to_string(),
-        expected: vec![ValueKind::String],
+        expected: vec![ValueKind::Boolean],
         found: value.into(),
         span: value.span.into(),
     })
 }
 
+/// Parse a value that is either a string or an integer (e.g. `first_value = 1`) as a string.
+#[inline]
+pub fn as_string_or_integer<'de>(value: &'de toml::Value<'de>) -> Result<String, ParseError> {
+    match value.as_ref() {
+        toml::value::ValueInner::String(s) => Ok(s.to_string()),
+        toml::value::ValueInner::Integer(i) => Ok(i.to_string()),
+        _ => Err(ParseError::UnexpectedType {
+            message: "expected a string or integer".to_string(),
+            expected: vec![ValueKind::String, ValueKind::Integer],
+            found: value.into(),
+            span: value.span.into(),
+        }),
+    }
+}
+
 pub(crate) fn parse_file<'de>(
     value: &'de toml::Value<'de>,
     search_is_regex: Option<bool>,
@@ -311,21 +326,247 @@ pub(crate) fn parse_part_config<'de>(
         span: value.span.into(),
     })?;
     let independent = table.get("independent").map(as_bool).transpose()?;
-    let optional_value = table.get("optional_value").map(as_string).transpose()?;
+    let optional_value = table
+        .get("optional_value")
+        .map(as_string_or_integer)
+        .transpose()?;
     let values = table
         .get("values")
         .map(as_string_array)
         .transpose()?
         .unwrap_or_default();
+    let first_value = table
+        .get("first_value")
+        .map(as_string_or_integer)
+        .transpose()?;
+    let always_increment = table
+        .get("always_increment")
+        .map(as_bool)
+        .transpose()?
+        .unwrap_or(false);
+    let calver_format = table
+        .get("calver_format")
+        .map(|value| {
+            // validate the format string but keep it raw
+            as_format_string(value)?;
+            as_string(value)
+        })
+        .transpose()?;
+    let depends_on = table.get("depends_on").map(as_string).transpose()?;
 
     Ok(VersionComponentSpec {
         independent,
         optional_value,
         values,
-        ..VersionComponentSpec::default()
+        first_value,
+        always_increment,
+        calver_format,
+        depends_on,
+    })
+}
+
+pub(crate) fn parse_changelog_config<'de>(
+    value: &'de toml::value::Value<'de>,
+) -> Result<ChangelogConfig, ParseError> {
+    let table = value.as_table().ok_or_else(|| ParseError::UnexpectedType {
+        message: "changelog config must be a table".to_string(),
+        expected: vec![ValueKind::Table],
+        found: value.into(),
+        span: value.span.into(),
+    })?;
+    let path = table
+        .get("path")
+        .map(as_string)
+        .transpose()?
+        .map(PathBuf::from);
+    let heading = table.get("heading").map(as_format_string).transpose()?;
+    let entry = table.get("entry").map(as_format_string).transpose()?;
+    let sections = table
+        .get("sections")
+        .map(|value| {
+            let sections = value.as_table().ok_or_else(|| ParseError::UnexpectedType {
+                message: "changelog sections must be a table".to_string(),
+                expected: vec![ValueKind::Table],
+                found: value.into(),
+                span: value.span.into(),
+            })?;
+            // keep the order of the sections in the config file
+            let mut sections: Vec<_> = sections.iter().collect();
+            sections.sort_by_key(|(key, _)| key.span.start);
+            sections
+                .into_iter()
+                .map(|(key, title)| Ok((key.name.to_string(), as_string(title)?)))
+                .collect::<Result<IndexMap<String, String>, _>>()
+        })
+        .transpose()?;
+
+    Ok(ChangelogConfig {
+        path,
+        heading,
+        entry,
+        sections,
     })
 }
 
+pub(crate) fn parse_context_config<'de>(
+    value: &'de toml::value::Value<'de>,
+) -> Result<crate::context::Variables, ParseError> {
+    let table = value.as_table().ok_or_else(|| ParseError::UnexpectedType {
+        message: "context must be a table".to_string(),
+        expected: vec![ValueKind::Table],
+        found: value.into(),
+        span: value.span.into(),
+    })?;
+    // keep the order of the variables in the config file
+    let mut entries: Vec<_> = table.iter().collect();
+    entries.sort_by_key(|(key, _)| key.span.start);
+    let variables = entries
+        .iter()
+        .map(|(key, value)| Ok((key.name.to_string(), as_format_string(value)?)))
+        .collect::<Result<crate::context::Variables, _>>()?;
+
+    if let Err(err) = crate::context::evaluation_order(&variables) {
+        // point to the first variable of the cycle
+        let span = match &err {
+            crate::context::Error::Cycle { cycle } => entries
+                .iter()
+                .find(|(key, _)| key.name == cycle[0])
+                .map(|(_, value)| value.span),
+            crate::context::Error::Format { .. } => None,
+        };
+        return Err(ParseError::InvalidConfiguration {
+            message: err.to_string(),
+            span: span.unwrap_or(value.span).into(),
+        });
+    }
+    Ok(variables)
+}
+
+/// Collect the source spans of the templates in a `bumpversion` table.
+fn template_spans<'de>(table: &'de toml::value::Table<'de>) -> config::TemplateSpans {
+    fn insert<'de>(
+        spans: &mut config::TemplateSpans,
+        table: &'de toml::value::Table<'de>,
+        prefix: &str,
+        keys: &[&str],
+    ) {
+        for key in keys {
+            let Some(value) = table.get(*key) else {
+                continue;
+            };
+            spans.insert(format!("{prefix}{key}"), value.span);
+            for (idx, item) in value.as_array().into_iter().flatten().enumerate() {
+                spans.insert(format!("{prefix}{key}[{idx}]"), item.span);
+            }
+        }
+    }
+
+    use config::templates::{CHANGELOG_TEMPLATE_KEYS, FILE_TEMPLATE_KEYS, GLOBAL_TEMPLATE_KEYS};
+
+    let mut spans = config::TemplateSpans::default();
+    insert(&mut spans, table, "", &GLOBAL_TEMPLATE_KEYS);
+    let files = table.get("files").and_then(|files| files.as_array());
+    for (idx, file) in files.into_iter().flatten().enumerate() {
+        if let Some(file) = file.as_table() {
+            insert(
+                &mut spans,
+                file,
+                &format!("files[{idx}]."),
+                &FILE_TEMPLATE_KEYS,
+            );
+        }
+    }
+    if let Some(context) = table.get("context").and_then(|context| context.as_table()) {
+        for (key, value) in context {
+            spans.insert(format!("context.{}", key.name), value.span);
+        }
+    }
+    if let Some(changelog) = table
+        .get("changelog")
+        .and_then(|changelog| changelog.as_table())
+    {
+        insert(
+            &mut spans,
+            changelog,
+            "changelog.",
+            &CHANGELOG_TEMPLATE_KEYS,
+        );
+    }
+    spans
+}
+
+/// Report the unknown keys of the global, file, and part tables of a `bumpversion` table.
+fn report_unknown_keys<'de>(
+    table: &'de toml::value::Table<'de>,
+    file_id: FileId,
+    strict: bool,
+    diagnostics: &mut Vec<Diagnostic<FileId>>,
+) {
+    use config::keys::{FILE_KEYS, GLOBAL_KEYS, PART_KEYS};
+
+    fn report<'de>(
+        table: &'de toml::value::Table<'de>,
+        known: &[&'static str],
+        description: &'static str,
+        file_id: FileId,
+        strict: bool,
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
+    ) {
+        // report in the order of the config file
+        let mut keys: Vec<_> = table.keys().collect();
+        keys.sort_by_key(|key| key.span.start);
+        let keys = keys.into_iter().map(|key| (key.name.as_ref(), key.span));
+        config::keys::report_unknown_keys(keys, known, description, file_id, strict, diagnostics);
+    }
+
+    let global_keys = [
+        GLOBAL_KEYS.as_slice(),
+        &["files", "parts", "changelog", "context"],
+    ]
+    .concat();
+    report(
+        table,
+        &global_keys,
+        "global config",
+        file_id,
+        strict,
+        diagnostics,
+    );
+
+    let file_keys = [FILE_KEYS.as_slice(), &["filename", "glob", "glob_exclude"]].concat();
+    let files = table.get("files").and_then(|files| files.as_array());
+    for file in files
+        .into_iter()
+        .flatten()
+        .filter_map(|file| file.as_table())
+    {
+        report(
+            file,
+            &file_keys,
+            "file config",
+            file_id,
+            strict,
+            diagnostics,
+        );
+    }
+
+    let parts = table.get("parts").and_then(|parts| parts.as_table());
+    for part in parts
+        .into_iter()
+        .flat_map(|parts| parts.values())
+        .filter_map(|part| part.as_table())
+    {
+        report(
+            part,
+            &PART_KEYS,
+            "part config",
+            file_id,
+            strict,
+            diagnostics,
+        );
+    }
+}
+
 fn parse_search_pattern<'de>(
     table: &'de toml::value::Table<'de>,
     is_regex: Option<bool>,
@@ -346,6 +587,7 @@ fn parse_search_pattern<'de>(
     Ok((search_is_regex_compat, search))
 }
 
+#[allow(clippy::too_many_lines)]
 pub(crate) fn parse_global_config<'de>(
     table: &'de toml::value::Table<'de>,
 ) -> Result<(Option<bool>, GlobalConfig), ParseError> {
@@ -389,6 +631,12 @@ pub(crate) fn parse_global_config<'de>(
         .map(as_format_string)
         .transpose()?;
     let commit_args = table.get("commit_args").map(as_string).transpose()?;
+    let push = table.get("push").map(as_bool).transpose()?;
+    let push_remote = table.get("push_remote").map(as_string).transpose()?;
+    let push_args = table.get("push_args").map(as_string).transpose()?;
+    let auto_bump_breaking = table.get("auto_bump_breaking").map(as_string).transpose()?;
+    let auto_bump_feat = table.get("auto_bump_feat").map(as_string).transpose()?;
+    let auto_bump_fix = table.get("auto_bump_fix").map(as_string).transpose()?;
 
     // extra stuff
     let setup_hooks = table.get("setup_hooks").map(as_string_array).transpose()?;
@@ -436,6 +684,12 @@ pub(crate) fn parse_global_config<'de>(
             tag_message,
             commit_message,
             commit_args,
+            push,
+            push_remote,
+            push_args,
+            auto_bump_breaking,
+            auto_bump_feat,
+            auto_bump_fix,
             // extra stuff
             setup_hooks,
             pre_commit_hooks,
@@ -473,6 +727,7 @@ pub(crate) fn parse_file_config<'de>(
         .get("ignore_missing_version")
         .map(as_bool)
         .transpose()?;
+    let key_path = table.get("key_path").map(as_string).transpose()?;
 
     Ok(FileConfig {
         parse_version_pattern,
@@ -481,15 +736,16 @@ pub(crate) fn parse_file_config<'de>(
         replace,
         ignore_missing_file,
         ignore_missing_version,
+        key_path,
     })
 }
 
 impl Config {
     pub fn from_pyproject_value(
         config: &toml::Value,
-        _file_id: FileId,
-        _strict: bool,
-        _diagnostics: &mut [Diagnostic<FileId>],
+        file_id: FileId,
+        strict: bool,
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
     ) -> Result<Option<Self>, ParseError> {
         let Some((_, config)) = config
             .as_table()
@@ -500,6 +756,21 @@ impl Config {
             return Ok(None);
         };
 
+        if config.as_table().is_some_and(toml::value::Table::is_empty) {
+            return Ok(None);
+        }
+        Self::from_bumpversion_table(config, file_id, strict, diagnostics).map(Some)
+    }
+
+    /// Parse the contents of a `bumpversion` configuration table.
+    ///
+    /// Unknown keys are reported as warnings, or as errors if `strict`.
+    pub(crate) fn from_bumpversion_table<'de>(
+        config: &'de toml::Value<'de>,
+        file_id: FileId,
+        strict: bool,
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
+    ) -> Result<Self, ParseError> {
         let table = config
             .as_table()
             .ok_or_else(|| ParseError::UnexpectedType {
@@ -509,9 +780,7 @@ impl Config {
                 span: config.span.into(),
             })?;
 
-        if table.is_empty() {
-            return Ok(None);
-        }
+        report_unknown_keys(table, file_id, strict, diagnostics);
 
         let (is_regex_compat, global_file_config) = parse_global_config(table)?;
 
@@ -556,18 +825,32 @@ impl Config {
             },
         };
 
-        Ok(Some(Self {
+        let changelog = table
+            .get("changelog")
+            .map(parse_changelog_config)
+            .transpose()?;
+
+        let context = table
+            .get("context")
+            .map(parse_context_config)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
             global: global_file_config,
             files,
             components,
-        }))
+            changelog,
+            context,
+            template_spans: template_spans(table),
+        })
     }
 
     pub fn from_pyproject_toml(
         config: &str,
         file_id: FileId,
         strict: bool,
-        diagnostics: &mut [Diagnostic<FileId>],
+        diagnostics: &mut Vec<Diagnostic<FileId>>,
     ) -> Result<Option<Self>, ParseError> {
         let config = toml_span::parse(config).map_err(|source| ParseError::Toml { source })?;
         Self::from_pyproject_value(&config, file_id, strict, diagnostics)
@@ -579,8 +862,8 @@ impl Config {
 pub mod tests {
     use crate::{
         config::{
-            self, Config, InputFile, change::FileChange, file::FileConfig, global::GlobalConfig,
-            regex::RegexTemplate, version::VersionComponentSpec,
+            self, Config, InputFile, TemplateSpans, change::FileChange, file::FileConfig,
+            global::GlobalConfig, regex::RegexTemplate, version::VersionComponentSpec,
         },
         diagnostics::{BufferedPrinter, ToDiagnostics},
         f_string::{PythonFormatString, Value},
@@ -681,6 +964,9 @@ pub mod tests {
             .into_iter()
             .collect(),
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -1105,6 +1391,9 @@ pub mod tests {
                     }
                 )
             ].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -1287,6 +1576,9 @@ pub mod tests {
             ]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
 
         let config = parse_toml(pyproject_toml, &BufferedPrinter::default()).0?;
@@ -1526,11 +1818,118 @@ pub mod tests {
             )]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
     }
 
+    #[test]
+    fn parse_pyproject_toml_with_full_part_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "2024.01.1"
+            parse = "(?P<release>\\d{4}\\.\\d{2})\\.(?P<build>\\d+)"
+            serialize = ["{release}.{build}"]
+
+            [tool.bumpversion.parts.release]
+            calver_format = "{YYYY}.{0M}"
+            always_increment = true
+
+            [tool.bumpversion.parts.build]
+            first_value = 1
+            depends_on = "release"
+        "#};
+
+        let config = parse_toml(pyproject_toml, &BufferedPrinter::default()).0?;
+        let components = config.map(|config| config.components);
+        sim_assert_eq!(
+            components,
+            Some(
+                [
+                    (
+                        "release".to_string(),
+                        VersionComponentSpec {
+                            calver_format: Some("{YYYY}.{0M}".to_string()),
+                            always_increment: true,
+                            ..VersionComponentSpec::default()
+                        },
+                    ),
+                    (
+                        "build".to_string(),
+                        VersionComponentSpec {
+                            first_value: Some("1".to_string()),
+                            depends_on: Some("release".to_string()),
+                            ..VersionComponentSpec::default()
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_pyproject_toml_with_invalid_part_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.0.0"
+
+            [tool.bumpversion.parts.patch]
+            always_increment = "yes"
+        "#};
+
+        let printer = BufferedPrinter::default();
+        let (config, _file_id, diagnostics) = parse_toml(pyproject_toml, &printer);
+        let err = config.unwrap_err();
+        sim_assert_eq!(&err.to_string(), "expected a boolean");
+        sim_assert_eq!(printer.lines(&diagnostics[0]).ok(), Some(vec![4]));
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.0.0"
+
+            [tool.bumpversion.parts.patch]
+            first_value = [1]
+        "#};
+
+        let printer = BufferedPrinter::default();
+        let (config, _file_id, diagnostics) = parse_toml(pyproject_toml, &printer);
+        let err = config.unwrap_err();
+        sim_assert_eq!(&err.to_string(), "expected a string or integer");
+        sim_assert_eq!(printer.lines(&diagnostics[0]).ok(), Some(vec![4]));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_pyproject_toml_with_invalid_format_spec() {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.0.0"
+            serialize = ["{major}.{minor}.{patch:03q}"]
+        "#};
+
+        let printer = BufferedPrinter::default();
+        let (config, _file_id, diagnostics) = parse_toml(pyproject_toml, &printer);
+        let err = config.unwrap_err();
+        sim_assert_eq!(&err.to_string(), "invalid format string");
+        sim_assert_eq!(printer.lines(&diagnostics[0]).ok(), Some(vec![2, 2]));
+        sim_assert_eq!(
+            diagnostics[0].labels[0].message,
+            r#"invalid format field "patch:03q": unsupported format type 'q' (expected one of `s`, `d`, `b`, `o`, `x`, or `X`)"#
+        );
+    }
+
     #[test]
     fn parse_pyproject_toml_of_bump_my_version() -> eyre::Result<()> {
         use crate::config::MergeWith;
@@ -1668,6 +2067,9 @@ pub mod tests {
             )]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(&config, &expected);
 
@@ -1693,6 +2095,10 @@ pub mod tests {
                 )])),
                 commit: Some(true),
                 commit_args: Some("--no-verify".to_string()),
+                push: Some(false),
+                auto_bump_breaking: Some("major".to_string()),
+                auto_bump_feat: Some("minor".to_string()),
+                auto_bump_fix: Some("patch".to_string()),
                 current_version: Some("0.29.0".to_string()),
                 ignore_missing_files: Some(false),
                 ignore_missing_version: Some(false),
@@ -1791,9 +2197,11 @@ pub mod tests {
                                 .into_iter()
                                 .collect()
                         ),
+                        uses_global_search: true,
                         replace: "{new_version}".to_string(),
                         ignore_missing_version: false,
                         ignore_missing_file: false,
+                        key_path: None,
                         include_bumps: Some(include_bumps.clone()),
                         exclude_bumps: None,
                     }]
@@ -1809,9 +2217,11 @@ pub mod tests {
                                     .into_iter()
                                     .collect()
                             ),
+                            uses_global_search: false,
                             replace: "{new_version}".to_string(),
                             ignore_missing_version: false,
                             ignore_missing_file: false,
+                            key_path: None,
                             include_bumps: Some(include_bumps.clone()),
                             exclude_bumps: None,
                         },
@@ -1826,9 +2236,11 @@ pub mod tests {
                                 .into_iter()
                                 .collect()
                             ),
+                            uses_global_search: false,
                             replace: "{current_version}...{new_version}".to_string(),
                             ignore_missing_version: false,
                             ignore_missing_file: false,
+                            key_path: None,
                             include_bumps: Some(include_bumps.clone()),
                             exclude_bumps: None,
                         },
@@ -1847,9 +2259,11 @@ pub mod tests {
                             .into_iter()
                             .collect()
                         ),
+                        uses_global_search: false,
                         replace: "bump-my-version=={new_version}".to_string(),
                         ignore_missing_version: false,
                         ignore_missing_file: false,
+                        key_path: None,
                         include_bumps: Some(include_bumps.clone()),
                         exclude_bumps: None,
                     },],
@@ -1867,9 +2281,11 @@ pub mod tests {
                                 .into_iter()
                                 .collect()
                             ),
+                            uses_global_search: false,
                             replace: r"created={utcnow:%Y-%m-%dT%H:%M:%SZ}".to_string(),
                             ignore_missing_version: false,
                             ignore_missing_file: false,
+                            key_path: None,
                             include_bumps: Some(include_bumps.clone()),
                             exclude_bumps: None,
                         },
@@ -1881,9 +2297,11 @@ pub mod tests {
                                     .into_iter()
                                     .collect()
                             ),
+                            uses_global_search: true,
                             replace: "{new_version}".to_string(),
                             ignore_missing_version: false,
                             ignore_missing_file: false,
+                            key_path: None,
                             include_bumps: Some(include_bumps.clone()),
                             exclude_bumps: None,
                         },
@@ -1894,4 +2312,78 @@ pub mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_pyproject_toml_with_unknown_keys() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+            comit = true
+
+            [[tool.bumpversion.files]]
+            filename = "README.md"
+            serialise = ["{major}.{minor}"]
+
+            [tool.bumpversion.parts.release]
+            value = ["dev", "stable"]
+            colour = "blue"
+        "#};
+
+        let printer = BufferedPrinter::default();
+        let (config, _file_id, diagnostics) = parse_toml(pyproject_toml, &printer);
+        assert!(config?.is_some());
+
+        let messages: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.notes.clone()))
+            .collect();
+        sim_assert_eq!(
+            messages,
+            [
+                (
+                    "unknown key `comit` in global config",
+                    vec!["did you mean `commit`?".to_string()]
+                ),
+                (
+                    "unknown key `serialise` in file config",
+                    vec!["did you mean `serialize`?".to_string()]
+                ),
+                (
+                    "unknown key `value` in part config",
+                    vec!["did you mean `values`?".to_string()]
+                ),
+                ("unknown key `colour` in part config", vec![]),
+            ]
+        );
+        let lines = diagnostics
+            .iter()
+            .map(|diagnostic| printer.lines(diagnostic))
+            .collect::<Result<Vec<_>, _>>()?;
+        sim_assert_eq!(lines, [[2], [6], [9], [10]]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_pyproject_toml_with_unknown_keys_not_strict() -> eyre::Result<()> {
+        use crate::diagnostics::DiagnosticExt;
+
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+            dryrun = true
+        "#};
+
+        let mut diagnostics = vec![];
+        let strict = false;
+        let config = Config::from_pyproject_toml(pyproject_toml, 0, strict, &mut diagnostics)?;
+        assert!(config.is_some());
+        sim_assert_eq!(diagnostics.len(), 1);
+        assert!(!diagnostics[0].is_error());
+        sim_assert_eq!(diagnostics[0].notes, ["did you mean `dry_run`?"]);
+        Ok(())
+    }
 }
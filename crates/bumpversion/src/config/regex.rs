@@ -1,4 +1,4 @@
-use crate::f_string::{MissingArgumentError, PythonFormatString};
+use crate::f_string::{FormatError, PythonFormatString};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -59,7 +59,7 @@ impl From<regex::Regex> for Regex {
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum RegexTemplateError {
     #[error(transparent)]
-    MissingArgument(#[from] MissingArgumentError),
+    Format(#[from] FormatError),
     #[error(transparent)]
     Regex(#[from] regex::Error),
 }
@@ -0,0 +1,364 @@
+//! Static validation of the templates of a config.
+//!
+//! Unknown arguments, e.g. a misspelled `{new_verison}`, are reported when the config is loaded,
+//! instead of failing in the middle of a bump.
+use crate::{
+    diagnostics::Span,
+    f_string::{PythonFormatString, spec::Field},
+};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use indexmap::IndexMap;
+use std::collections::HashSet;
+
+/// Keys of the templates of the global config.
+pub(crate) const GLOBAL_TEMPLATE_KEYS: [&str; 6] = [
+    "search",
+    "replace",
+    "serialize",
+    "tag_name",
+    "tag_message",
+    "commit_message",
+];
+
+/// Keys of the templates of a file config.
+pub(crate) const FILE_TEMPLATE_KEYS: [&str; 3] = ["search", "replace", "serialize"];
+
+/// Keys of the templates of the changelog config.
+pub(crate) const CHANGELOG_TEMPLATE_KEYS: [&str; 2] = ["heading", "entry"];
+
+/// Arguments available in changelog entries, in addition to the context.
+const CHANGELOG_ENTRY_ARGUMENTS: [&str; 6] = [
+    "type",
+    "scope",
+    "description",
+    "subject",
+    "sha",
+    "short_sha",
+];
+
+/// Source spans of the templates of a config file.
+///
+/// Spans are keyed by the path of the template in the config,
+/// e.g. `tag_name`, `serialize[1]`, `files[0].search`, or `context.docker_tag`.
+/// They are only used for diagnostics and are ignored when comparing configs.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateSpans(pub IndexMap<String, Span>);
+
+impl PartialEq for TemplateSpans {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for TemplateSpans {}
+
+impl TemplateSpans {
+    pub fn insert(&mut self, path: impl Into<String>, span: impl Into<Span>) {
+        self.0.insert(path.into(), span.into());
+    }
+
+    /// Forget the spans of the global templates that are overridden by `overrides`.
+    pub fn remove_overridden(&mut self, overrides: &super::GlobalConfig) {
+        let overridden = [
+            ("search", overrides.search.is_some()),
+            ("replace", overrides.replace.is_some()),
+            ("serialize", overrides.serialize_version_patterns.is_some()),
+            ("tag_name", overrides.tag_name.is_some()),
+            ("tag_message", overrides.tag_message.is_some()),
+            ("commit_message", overrides.commit_message.is_some()),
+        ];
+        for (key, _) in overridden.iter().filter(|(_, overridden)| *overridden) {
+            self.0.retain(|path, _| {
+                path.split_once('[').map_or(path.as_str(), |(path, _)| path) != *key
+            });
+        }
+    }
+
+    /// Returns the span of the template at `path`.
+    ///
+    /// Falls back to the span of the whole list for list items without a span,
+    /// e.g. `serialize` for `serialize[1]`.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<Span> {
+        self.0
+            .get(path)
+            .or_else(|| {
+                let (list, _) = path.rsplit_once('[')?;
+                self.0.get(list)
+            })
+            .cloned()
+    }
+}
+
+/// A template of the config.
+struct Template<'a> {
+    /// Path of the template in the config, e.g. `files[0].search`.
+    path: String,
+    value: &'a PythonFormatString,
+    /// Arguments only available in this template.
+    arguments: HashSet<String>,
+}
+
+impl<'a> Template<'a> {
+    fn new(path: impl Into<String>, value: &'a PythonFormatString) -> Self {
+        Self {
+            path: path.into(),
+            value,
+            arguments: HashSet::new(),
+        }
+    }
+}
+
+/// The arguments available for the version `components`.
+fn component_arguments<'a>(components: impl IntoIterator<Item = &'a str>) -> HashSet<String> {
+    components
+        .into_iter()
+        .flat_map(|component| {
+            [
+                component.to_string(),
+                format!("current_{component}"),
+                format!("new_{component}"),
+            ]
+        })
+        .collect()
+}
+
+/// Collect the templates of the finalized `config`.
+///
+/// Templates of files that are inherited from the global config are skipped.
+fn templates<'a>(
+    config: &'a super::FinalizedConfig,
+    replace: &'a [(String, PythonFormatString)],
+) -> Vec<Template<'a>> {
+    let global = &config.global;
+    let mut templates = vec![
+        Template::new("search", global.search.as_ref()),
+        Template::new("tag_name", &global.tag_name),
+        Template::new("tag_message", &global.tag_message),
+        Template::new("commit_message", &global.commit_message),
+    ];
+    templates.extend(
+        global
+            .serialize_version_patterns
+            .iter()
+            .enumerate()
+            .map(|(idx, pattern)| Template::new(format!("serialize[{idx}]"), pattern)),
+    );
+    templates.extend(
+        replace
+            .iter()
+            .map(|(path, replace)| Template::new(path.clone(), replace)),
+    );
+
+    for (idx, (_, file)) in config.files.iter().enumerate() {
+        let arguments = component_arguments(file.parse_version_pattern.capture_names().flatten());
+        if file.search != global.search {
+            templates.push(Template {
+                arguments: arguments.clone(),
+                ..Template::new(format!("files[{idx}].search"), file.search.as_ref())
+            });
+        }
+        if file.serialize_version_patterns != global.serialize_version_patterns {
+            for (pattern_idx, pattern) in file.serialize_version_patterns.iter().enumerate() {
+                templates.push(Template {
+                    arguments: arguments.clone(),
+                    ..Template::new(format!("files[{idx}].serialize[{pattern_idx}]"), pattern)
+                });
+            }
+        }
+    }
+
+    templates.extend(
+        config
+            .context
+            .iter()
+            .map(|(name, value)| Template::new(format!("context.{name}"), value)),
+    );
+
+    if let Some(changelog) = &config.changelog {
+        templates.push(Template::new("changelog.heading", &changelog.heading));
+        templates.push(Template {
+            arguments: CHANGELOG_ENTRY_ARGUMENTS.map(String::from).into(),
+            ..Template::new("changelog.entry", &changelog.entry)
+        });
+    }
+    templates
+}
+
+/// Check that the templates of the finalized `config` only use known arguments.
+///
+/// Known arguments are the built-in context, the user-defined context variables,
+/// and the version components.
+/// Environment variables and arguments with a default value are not checked.
+///
+/// Returns an error diagnostic for each invalid template and unknown argument,
+/// labeled with the span of the template in the config file, if known.
+#[must_use]
+pub fn validate<F>(
+    config: &super::FinalizedConfig,
+    spans: &TemplateSpans,
+    file_id: F,
+) -> Vec<Diagnostic<F>>
+where
+    F: Copy + PartialEq,
+{
+    let mut known: HashSet<String> = crate::context::get_context(None, None, None, None, None)
+        .map(|(key, _)| key)
+        .filter(|key| !key.starts_with('$'))
+        .collect();
+    known.extend(config.context.keys().cloned());
+    known.extend(component_arguments(
+        super::version::version_component_configs(config)
+            .keys()
+            .chain(config.components.keys())
+            .map(String::as_str),
+    ));
+
+    let label = |path: &str, message: String| {
+        spans
+            .get(path)
+            .map(|span| Label::primary(file_id, span).with_message(message))
+            .into_iter()
+            .collect::<Vec<_>>()
+    };
+
+    // `replace` is only parsed when it is formatted
+    let mut diagnostics = vec![];
+    let mut replace = vec![];
+    let replace_templates = std::iter::once(("replace".to_string(), &config.global.replace)).chain(
+        config
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, file))| file.replace != config.global.replace)
+            .map(|(idx, (_, file))| (format!("files[{idx}].replace"), &file.replace)),
+    );
+    for (path, template) in replace_templates {
+        match PythonFormatString::parse(template) {
+            Ok(template) => replace.push((path, template)),
+            Err(err) => diagnostics.push(
+                Diagnostic::error()
+                    .with_message(format!("invalid template `{path}`"))
+                    .with_labels(label(&path, err.to_string())),
+            ),
+        }
+    }
+
+    for template in templates(config, &replace) {
+        let fields = template
+            .value
+            .iter()
+            .filter_map(|value| value.as_argument())
+            .filter_map(|argument| Field::parse(argument).ok());
+        for field in fields {
+            let name = field.name;
+            if field.default.is_some()
+                || name.starts_with('$')
+                || known.contains(name)
+                || template.arguments.contains(name)
+            {
+                continue;
+            }
+            diagnostics.push(
+                Diagnostic::error()
+                    .with_message(format!(
+                        "unknown argument `{name}` in template `{}`",
+                        template.path
+                    ))
+                    .with_labels(label(
+                        &template.path,
+                        format!("`{name}` is not a known argument"),
+                    ))
+                    .with_notes(vec![format!(
+                        "use a default value if the argument may be missing, e.g. `{{{name}|}}`"
+                    )]),
+            );
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::{MergeWith, global::GlobalConfig, pyproject_toml::tests::parse_toml},
+        diagnostics::BufferedPrinter,
+    };
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    fn validate(config: &str, printer: &BufferedPrinter) -> eyre::Result<Vec<Vec<usize>>> {
+        let (config, file_id, _) = parse_toml(config, printer);
+        let mut config = config?.ok_or_else(|| eyre::eyre!("missing config"))?;
+        config.global.merge_with(&GlobalConfig::default());
+        let spans = config.template_spans.clone();
+        let diagnostics = super::validate(&config.finalize(), &spans, file_id);
+        for diagnostic in &diagnostics {
+            printer.emit(diagnostic)?;
+        }
+        printer.print()?;
+        Ok(diagnostics
+            .iter()
+            .map(|diagnostic| printer.lines(diagnostic))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn validate_unknown_arguments() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+            tag_name = "v{new_verison}"
+            serialize = [
+                "{major}.{minor}.{patch}-{pre}",
+                "{major}.{minor}.{patch}",
+            ]
+
+            [[tool.bumpversion.files]]
+            filename = "README.md"
+            replace = "{current_version} -> {new_version} ({unknown})"
+        "#};
+
+        let printer = BufferedPrinter::default();
+        sim_assert_eq!(validate(pyproject_toml, &printer)?, [[2], [4], [10]]);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_known_arguments() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+            tag_name = "v{new_version}{$SUFFIX}"
+            commit_message = "Bump {current_major} to {new_major} on {now:%Y-%m-%d}"
+            tag_message = "{docker_tag} {build|local}"
+
+            [tool.bumpversion.context]
+            docker_tag = "{new_version}-{branch_name|main}"
+        "#};
+
+        let printer = BufferedPrinter::default();
+        sim_assert_eq!(
+            validate(pyproject_toml, &printer)?,
+            Vec::<Vec<usize>>::new()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn validate_bump_my_version_config() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = include_str!("../../test-data/bump-my-version.pyproject.toml");
+        let printer = BufferedPrinter::default();
+        sim_assert_eq!(
+            validate(pyproject_toml, &printer)?,
+            Vec::<Vec<usize>>::new()
+        );
+        Ok(())
+    }
+}
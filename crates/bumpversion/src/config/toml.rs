@@ -1,5 +1,6 @@
 use crate::f_string::PythonFormatString;
-use crate::files::{self, IoError};
+use crate::files;
+use crate::fs::FileSystem;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -20,7 +21,10 @@ pub(crate) fn replace_version_of_document(
 
     let mut item: Option<&mut Item> = Some(document.as_item_mut());
     for k in key_path {
-        item = item.and_then(|doc| doc.get_mut(k));
+        // note: `Item::get_mut` would insert missing keys
+        item = item
+            .and_then(Item::as_table_like_mut)
+            .and_then(|table| table.get_mut(k));
     }
     // let Some(item) = item else {
     //     return Ok(false);
@@ -69,7 +73,10 @@ pub(crate) fn replace_version_of_document(
     // new_value = search_for.sub(replace_with, value_before)
     // log_changes(f"{self.path}:{self.file_change.key_path}", value_before, new_value, dry_run)
     //
+    // keep surrounding whitespace and comments
+    let decor = before.decor().clone();
     *before = Formatted::new(new_value.to_string());
+    *before.decor_mut() = decor;
     // set_nested_value(toml_data, new_value, self.file_change.key_path)
     //
     // self.path.write_text(tomlkit.dumps(toml_data), encoding="utf-8")
@@ -85,21 +92,43 @@ pub(crate) fn replace_version_of_document(
 // }
 
 /// Update the `current_version` key in the configuration file
-pub(crate) async fn replace_version<K, V>(
+pub(crate) async fn replace_version<FS, K, V>(
+    fs: &FS,
     path: &Path,
     config: &super::FinalizedConfig,
     ctx: &HashMap<K, V>,
     // _current_version: &str,
     // _next_version: &str,
-    dry_run: bool,
 ) -> Result<Option<files::Modification>, files::ReplaceVersionError>
 where
+    FS: FileSystem,
     K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
     V: AsRef<str> + std::fmt::Debug,
 {
-    tracing::info!(config = ?path, "processing config file");
+    replace_version_of_keys(
+        fs,
+        path,
+        &[&["tool", "bumpversion", "current_version"]],
+        config,
+        ctx,
+    )
+    .await
+}
 
-    let as_io_error = |source: std::io::Error| IoError::new(source, path);
+/// Update the version at each of the `key_paths` in the TOML configuration file
+pub(crate) async fn replace_version_of_keys<FS, K, V>(
+    fs: &FS,
+    path: &Path,
+    key_paths: &[&[&str]],
+    config: &super::FinalizedConfig,
+    ctx: &HashMap<K, V>,
+) -> Result<Option<files::Modification>, files::ReplaceVersionError>
+where
+    FS: FileSystem,
+    K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
+    V: AsRef<str> + std::fmt::Debug,
+{
+    tracing::info!(config = ?path, "processing config file");
 
     let extension = path.extension().and_then(|ext| ext.to_str());
 
@@ -118,7 +147,7 @@ where
     // }
 
     // parse the document
-    let before = tokio::fs::read_to_string(path).await.map_err(as_io_error)?;
+    let before = fs.read_to_string(path).await?;
     let mut document = before.parse::<toml_edit::DocumentMut>()?;
 
     // let search = &config::defaults::PARSE_VERSION_REGEX; // TODO: change
@@ -137,32 +166,12 @@ where
     let replacement = PythonFormatString::parse(replace_pattern)?;
     let replacement = replacement.format(ctx, true)?;
 
-    let _ = replace_version_of_document(
-        &mut document,
-        &["tool", "bumpversion", "current_version"],
-        &search_regex,
-        &replacement,
-    );
+    for key_path in key_paths {
+        let _ = replace_version_of_document(&mut document, key_path, &search_regex, &replacement);
+    }
 
     let after = document.to_string();
 
-    if !dry_run {
-        use tokio::io::AsyncWriteExt;
-        let file = tokio::fs::OpenOptions::new()
-            .write(true)
-            .create(false)
-            .truncate(true)
-            .open(path)
-            .await
-            .map_err(as_io_error)?;
-        let mut writer = tokio::io::BufWriter::new(file);
-        writer
-            .write_all(after.as_bytes())
-            .await
-            .map_err(as_io_error)?;
-        writer.flush().await.map_err(as_io_error)?;
-    }
-
     let modification = files::Modification {
         before,
         after,
@@ -181,13 +190,14 @@ where
 mod tests {
     use crate::{
         config::{
-            self, Config, InputFile, file::FileConfig, global::GlobalConfig,
+            self, Config, InputFile, TemplateSpans, file::FileConfig, global::GlobalConfig,
             pyproject_toml::tests::parse_toml, regex::RegexTemplate, version::VersionComponentSpec,
         },
         diagnostics::Printer,
         f_string::{PythonFormatString, Value},
     };
     use color_eyre::eyre;
+    use indexmap::IndexMap;
     use similar_asserts::assert_eq as sim_assert_eq;
 
     #[test]
@@ -299,6 +309,9 @@ mod tests {
             },
             files: vec![],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         let config = parse_toml(bumpversion_toml, &Printer::default()).0?;
         sim_assert_eq!(config, Some(expected));
@@ -438,6 +451,9 @@ mod tests {
             )]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -554,6 +570,9 @@ mod tests {
                 ),
             ],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -610,6 +629,9 @@ mod tests {
             },
             files: vec![].into_iter().collect(),
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -810,7 +832,7 @@ mod tests {
                 (
                     "pre_n".to_string(),
                     VersionComponentSpec {
-                        // first_value: Some(1),
+                        first_value: Some("1".to_string()),
                         ..VersionComponentSpec::default()
                     },
                 ),
@@ -824,14 +846,13 @@ mod tests {
                 (
                     "post_n".to_string(),
                     VersionComponentSpec {
-                        // first_value: Some(1),
+                        first_value: Some("1".to_string()),
                         ..VersionComponentSpec::default()
                     },
                 ),
                 (
                     "dev_label".to_string(),
                     VersionComponentSpec {
-                        // first_value: Some(1),
                         values: vec!["final".to_string(), "dev".to_string()],
                         independent: Some(true),
                         ..VersionComponentSpec::default()
@@ -840,7 +861,7 @@ mod tests {
                 (
                     "dev_n".to_string(),
                     VersionComponentSpec {
-                        // first_value: Some(1),
+                        first_value: Some("1".to_string()),
                         ..VersionComponentSpec::default()
                     },
                 ),
@@ -854,6 +875,9 @@ mod tests {
             ]
             .into_iter()
             .collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -898,6 +922,9 @@ mod tests {
                 },
             )],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -943,6 +970,9 @@ mod tests {
                 },
             )],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
@@ -991,8 +1021,131 @@ mod tests {
                 (InputFile::Path("VERSION".into()), FileConfig::empty()),
             ],
             components: [].into_iter().collect(),
+            changelog: None,
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
+        };
+        sim_assert_eq!(config, Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_changelog_config_toml() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+
+            [tool.bumpversion.changelog]
+            path = "docs/CHANGES.md"
+            entry = "- {description}"
+
+            [tool.bumpversion.changelog.sections]
+            fix = "Bug Fixes"
+            feat = "Features"
+        "#};
+
+        let config = parse_toml(bumpversion_toml, &Printer::default()).0?;
+
+        let expected = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                ..GlobalConfig::empty()
+            },
+            files: vec![],
+            components: [].into_iter().collect(),
+            changelog: Some(config::ChangelogConfig {
+                path: Some("docs/CHANGES.md".into()),
+                heading: None,
+                entry: Some(PythonFormatString(vec![
+                    Value::String("- ".to_string()),
+                    Value::Argument("description".to_string()),
+                ])),
+                sections: Some(
+                    [("fix", "Bug Fixes"), ("feat", "Features")]
+                        .into_iter()
+                        .map(|(commit_type, title)| (commit_type.to_string(), title.to_string()))
+                        .collect(),
+                ),
+            }),
+            context: IndexMap::new(),
+            template_spans: TemplateSpans::default(),
+        };
+        sim_assert_eq!(config, Some(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_context_config_toml() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let bumpversion_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+            tag_name = "{docker_tag}"
+
+            [tool.bumpversion.context]
+            release_date = "{now:%Y-%m-%d}"
+            docker_tag = "{new_version}-{short_branch_name}"
+        "#};
+
+        let config = parse_toml(bumpversion_toml, &Printer::default()).0?;
+
+        let expected = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                tag_name: Some(PythonFormatString(vec![Value::Argument(
+                    "docker_tag".to_string(),
+                )])),
+                ..GlobalConfig::empty()
+            },
+            files: vec![],
+            components: [].into_iter().collect(),
+            changelog: None,
+            context: [
+                (
+                    "release_date",
+                    PythonFormatString(vec![Value::Argument("now:%Y-%m-%d".to_string())]),
+                ),
+                (
+                    "docker_tag",
+                    PythonFormatString(vec![
+                        Value::Argument("new_version".to_string()),
+                        Value::String("-".to_string()),
+                        Value::Argument("short_branch_name".to_string()),
+                    ]),
+                ),
+            ]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+            template_spans: TemplateSpans::default(),
         };
         sim_assert_eq!(config, Some(expected));
         Ok(())
     }
+
+    #[test]
+    fn parse_context_config_toml_with_cycle() {
+        crate::tests::init();
+
+        let bumpversion_toml = indoc::indoc! {r#"
+            [tool.bumpversion]
+            current_version = "1.2.3"
+
+            [tool.bumpversion.context]
+            image = "ghcr.io/{docker_tag}"
+            docker_tag = "{image}:{new_version}"
+        "#};
+
+        let printer = Printer::default();
+        let (config, _file_id, diagnostics) = parse_toml(bumpversion_toml, &printer);
+        let err = config.unwrap_err();
+        sim_assert_eq!(
+            &err.to_string(),
+            "context variables reference each other in a cycle: image -> docker_tag -> image"
+        );
+        sim_assert_eq!(printer.lines(&diagnostics[0]).ok(), Some(vec![4]));
+    }
 }
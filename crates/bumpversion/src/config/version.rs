@@ -5,7 +5,7 @@ pub type VersionComponentConfigs = IndexMap<String, VersionComponentSpec>;
 /// Configuration of a version component.
 ///
 /// This is used to read in the configuration from the bumpversion config file.
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
 pub struct VersionComponentSpec {
     /// Is the component independent of the other components?
     pub independent: Option<bool>,
@@ -2,7 +2,12 @@
 //! Context construction for template rendering of version strings and tags.
 //!
 //! Builds a map of variables from environment, VCS info, and version data.
-use crate::{vcs::TagAndRevision, version::Version};
+use crate::{
+    f_string::{FormatError, PythonFormatString, Value},
+    vcs::TagAndRevision,
+    version::Version,
+};
+use indexmap::IndexMap;
 use std::collections::HashMap;
 
 /// A simple environment mapping of variable names to values.
@@ -26,6 +31,10 @@ fn base_context(
         .as_ref()
         .and_then(|v| v.revision.clone())
         .unwrap_or_default();
+    let tool = tag_and_revision
+        .as_ref()
+        .and_then(|v| v.tool.clone())
+        .unwrap_or_default();
 
     [
         ("now".to_string(), chrono::Local::now().to_rfc3339()),
@@ -34,7 +43,7 @@ fn base_context(
     .into_iter()
     .chain(prefixed_env())
     .chain([
-        ("tool".to_string(), "git".to_string()),
+        ("tool".to_string(), tool),
         ("commit_sha".to_string(), tag.commit_sha),
         (
             "distance_to_latest_tag".to_string(),
@@ -98,3 +107,204 @@ pub fn get_context(
                 }),
         )
 }
+
+/// User-defined context variables.
+///
+/// Each variable is a template over the built-in context and the other variables.
+pub type Variables = IndexMap<String, PythonFormatString>;
+
+/// Errors that can occur when rendering user-defined context variables.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The variables reference each other in a cycle.
+    #[error("context variables reference each other in a cycle: {}", .cycle.join(" -> "))]
+    Cycle { cycle: Vec<String> },
+    /// Failed to render a variable.
+    #[error("failed to render context variable {name:?}")]
+    Format {
+        name: String,
+        #[source]
+        source: FormatError,
+    },
+}
+
+/// Return the names of the `variables` in the order they must be rendered.
+///
+/// Each variable comes after all the variables it references.
+///
+/// # Errors
+/// When the variables reference each other in a cycle.
+pub fn evaluation_order(variables: &Variables) -> Result<Vec<&str>, Error> {
+    fn visit<'a>(
+        name: &'a str,
+        variables: &'a Variables,
+        path: &mut Vec<&'a str>,
+        order: &mut Vec<&'a str>,
+    ) -> Result<(), Error> {
+        if order.contains(&name) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|visiting| *visiting == name) {
+            let cycle = path[start..]
+                .iter()
+                .chain([&name])
+                .map(ToString::to_string)
+                .collect();
+            return Err(Error::Cycle { cycle });
+        }
+        path.push(name);
+        for dependency in variables[name].named_arguments() {
+            if variables.contains_key(dependency) {
+                visit(dependency, variables, path, order)?;
+            }
+        }
+        path.pop();
+        order.push(name);
+        Ok(())
+    }
+
+    let mut order = Vec::with_capacity(variables.len());
+    for name in variables.keys() {
+        visit(name, variables, &mut Vec::new(), &mut order)?;
+    }
+    Ok(order)
+}
+
+/// Render the user-defined `variables` and add them to the context `ctx`.
+///
+/// Variables that reference values missing from `ctx` are skipped,
+/// so that only the templates using them fail.
+///
+/// # Errors
+/// - When the variables reference each other in a cycle.
+/// - When a value cannot be formatted according to the format spec of its field.
+#[allow(clippy::implicit_hasher)]
+pub fn render_variables(
+    variables: &Variables,
+    ctx: &mut HashMap<String, String>,
+) -> Result<(), Error> {
+    let strict = true;
+    for name in evaluation_order(variables)? {
+        match variables[name].format(ctx, strict) {
+            Ok(value) => {
+                ctx.insert(name.to_string(), value);
+            }
+            Err(FormatError::MissingArgument(missing)) => {
+                tracing::debug!(name, %missing, "skipping context variable");
+                ctx.remove(name);
+            }
+            Err(source) => {
+                return Err(Error::Format {
+                    name: name.to_string(),
+                    source,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Expand the user-defined `variables` referenced by the `template`.
+///
+/// References without a conversion or format spec are replaced by the template of the variable.
+/// This allows finding existing tags when the `tag_name` references variables.
+///
+/// Variables that reference each other in a cycle are not expanded.
+#[must_use]
+pub fn expand_variables(
+    template: &PythonFormatString,
+    variables: &Variables,
+) -> PythonFormatString {
+    fn expand(template: &PythonFormatString, variables: &Variables) -> Vec<Value> {
+        template
+            .iter()
+            .flat_map(|value| match value {
+                Value::Argument(name) => match variables.get(name) {
+                    Some(variable) => expand(variable, variables),
+                    None => vec![value.clone()],
+                },
+                Value::String(_) => vec![value.clone()],
+            })
+            .collect()
+    }
+
+    if evaluation_order(variables).is_err() {
+        return template.clone();
+    }
+    PythonFormatString(expand(template, variables))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Variables};
+    use crate::f_string::PythonFormatString;
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::HashMap;
+
+    fn parse_variables(values: &[(&str, &str)]) -> eyre::Result<Variables> {
+        values
+            .iter()
+            .map(|(name, value)| Ok((name.to_string(), PythonFormatString::parse(value)?)))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_variables() -> eyre::Result<()> {
+        crate::tests::init();
+        let variables = parse_variables(&[
+            ("docker_tag", "{image}:{new_version}-{short_branch_name}"),
+            ("image", "bumpversion"),
+            ("release_date", "{now:%Y-%m-%d}"),
+            ("missing", "{new_major}"),
+        ])?;
+        let mut ctx: HashMap<String, String> = [
+            ("new_version", "1.3.0"),
+            ("short_branch_name", "main"),
+            ("now", "2025-01-02T03:04:05+00:00"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        super::render_variables(&variables, &mut ctx)?;
+        sim_assert_eq!(ctx["docker_tag"], "bumpversion:1.3.0-main");
+        sim_assert_eq!(ctx["release_date"], "2025-01-02");
+        sim_assert_eq!(ctx.get("missing"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_variables() -> eyre::Result<()> {
+        crate::tests::init();
+        let variables = parse_variables(&[
+            ("release", "v{new_version}"),
+            ("docker_tag", "{release}-{short_branch_name}"),
+        ])?;
+        let tag_name = PythonFormatString::parse("{docker_tag}+{release:>8}")?;
+        sim_assert_eq!(
+            super::expand_variables(&tag_name, &variables).to_string(),
+            "v{new_version}-{short_branch_name}+{release:>8}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluation_order_detects_cycles() -> eyre::Result<()> {
+        crate::tests::init();
+        let variables = parse_variables(&[("a", "{b}"), ("b", "{new_version}-{c}"), ("c", "{a}")])?;
+        sim_assert_eq!(
+            super::evaluation_order(&variables),
+            Err(Error::Cycle {
+                cycle: ["a", "b", "c", "a"].map(String::from).to_vec()
+            })
+        );
+        let variables = parse_variables(&[("a", "{a}")])?;
+        sim_assert_eq!(
+            super::evaluation_order(&variables),
+            Err(Error::Cycle {
+                cycle: ["a", "a"].map(String::from).to_vec()
+            })
+        );
+        Ok(())
+    }
+}
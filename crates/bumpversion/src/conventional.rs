@@ -0,0 +1,364 @@
+//! Automatic bump selection from conventional commits.
+//!
+//! Classifies the commits since the latest tag following the
+//! [conventional commits](https://www.conventionalcommits.org) specification
+//! and selects the version component to bump.
+use crate::{
+    Bump,
+    config::GlobalConfigFinalized,
+    vcs::{CommitInfo, TagAndRevision, VersionControlSystem},
+};
+use std::sync::LazyLock;
+
+static SUBJECT_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::RegexBuilder::new(
+        r"^(?P<type>[a-zA-Z]+)(?:\((?P<scope>[^()]*)\))?(?P<breaking>!)?:\s(?P<description>.*)$",
+    )
+    .build()
+    .unwrap()
+});
+
+static BREAKING_CHANGE_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::RegexBuilder::new(r"^BREAKING[ -]CHANGE:\s")
+        .multi_line(true)
+        .build()
+        .unwrap()
+});
+
+/// The kind of change introduced by a conventional commit, ordered by significance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeKind {
+    /// A `fix` commit.
+    Fix,
+    /// A `feat` commit.
+    Feature,
+    /// A commit marked with `!` or a `BREAKING CHANGE` footer.
+    Breaking,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fix => write!(f, "fix"),
+            Self::Feature => write!(f, "feature"),
+            Self::Breaking => write!(f, "breaking change"),
+        }
+    }
+}
+
+impl ChangeKind {
+    /// The version component configured to be bumped for this kind of change.
+    #[must_use]
+    pub fn component(self, config: &GlobalConfigFinalized) -> &str {
+        match self {
+            Self::Fix => &config.auto_bump_fix,
+            Self::Feature => &config.auto_bump_feat,
+            Self::Breaking => &config.auto_bump_breaking,
+        }
+    }
+}
+
+/// The subject of a conventional commit, e.g. `feat(cli)!: add auto bump`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Subject<'a> {
+    /// The commit type, e.g. `feat`.
+    pub commit_type: &'a str,
+    /// The optional scope, e.g. `cli`.
+    pub scope: Option<&'a str>,
+    /// Whether the commit is marked as a breaking change using `!`.
+    pub breaking: bool,
+    /// The description following the colon.
+    pub description: &'a str,
+}
+
+/// Parse the subject of a conventional commit.
+///
+/// Returns `None` if the `subject` does not follow the conventional commit format.
+#[must_use]
+pub fn parse_subject(subject: &str) -> Option<Subject<'_>> {
+    let captures = SUBJECT_REGEX.captures(subject)?;
+    Some(Subject {
+        commit_type: captures.name("type")?.as_str(),
+        scope: captures.name("scope").map(|scope| scope.as_str()),
+        breaking: captures.name("breaking").is_some(),
+        description: captures.name("description")?.as_str().trim(),
+    })
+}
+
+/// Classify a commit by its conventional commit message.
+///
+/// Returns `None` for commits that do not follow the conventional commit format
+/// or whose type does not require a version bump (e.g. `docs` or `chore`).
+#[must_use]
+pub fn classify(commit: &CommitInfo) -> Option<ChangeKind> {
+    let subject = parse_subject(&commit.subject);
+    let marked_breaking = subject.is_some_and(|subject| subject.breaking);
+    if marked_breaking || BREAKING_CHANGE_REGEX.is_match(&commit.body) {
+        return Some(ChangeKind::Breaking);
+    }
+    let commit_type = subject?.commit_type;
+    if commit_type.eq_ignore_ascii_case("feat") {
+        Some(ChangeKind::Feature)
+    } else if commit_type.eq_ignore_ascii_case("fix") {
+        Some(ChangeKind::Fix)
+    } else {
+        None
+    }
+}
+
+/// The version component selected from the conventional commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoBump {
+    /// The version component to bump.
+    pub component: String,
+    /// The most significant kind of change.
+    pub kind: ChangeKind,
+    /// The commits that introduce the most significant kind of change.
+    pub commits: Vec<CommitInfo>,
+}
+
+impl AutoBump {
+    /// The bump to perform.
+    #[must_use]
+    pub fn bump(&self) -> Bump<'_> {
+        Bump::Component(&self.component)
+    }
+}
+
+impl std::fmt::Display for AutoBump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bumping {} because of {} {} commit(s):",
+            self.component,
+            self.commits.len(),
+            self.kind
+        )?;
+        for commit in &self.commits {
+            let short_sha = commit.sha.get(..7).unwrap_or(&commit.sha);
+            write!(f, "\n  {short_sha} {}", commit.subject)?;
+        }
+        Ok(())
+    }
+}
+
+/// Select the version component to bump from the given `commits`.
+///
+/// The most significant kind of change decides the component, as configured by
+/// `auto_bump_breaking`, `auto_bump_feat`, and `auto_bump_fix`.
+///
+/// Returns `None` if none of the commits require a version bump.
+#[must_use]
+pub fn select<'a>(
+    commits: impl IntoIterator<Item = &'a CommitInfo>,
+    config: &GlobalConfigFinalized,
+) -> Option<AutoBump> {
+    let classified: Vec<_> = commits
+        .into_iter()
+        .filter_map(|commit| Some((classify(commit)?, commit)))
+        .collect();
+    let kind = classified.iter().map(|(kind, _)| *kind).max()?;
+    let commits = classified
+        .into_iter()
+        .filter(|(commit_kind, _)| *commit_kind == kind)
+        .map(|(_, commit)| commit.clone())
+        .collect();
+    Some(AutoBump {
+        component: kind.component(config).to_string(),
+        kind,
+        commits,
+    })
+}
+
+/// Select the version component to bump from the commits since the latest tag.
+///
+/// If there is no latest tag, all commits are considered.
+pub async fn select_since_latest_tag<VCS>(
+    repo: &VCS,
+    tag_and_revision: &TagAndRevision,
+    config: &GlobalConfigFinalized,
+) -> Result<Option<AutoBump>, VCS::Error>
+where
+    VCS: VersionControlSystem,
+{
+    let latest_tag = tag_and_revision
+        .tag
+        .as_ref()
+        .map(|tag| tag.current_tag.as_str());
+    let commits = repo.commits_since(latest_tag).await?;
+    tracing::debug!(?latest_tag, num_commits = commits.len(), "auto bump");
+    Ok(select(&commits, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeKind, classify};
+    use crate::{
+        config::{GlobalConfig, GlobalConfigFinalized},
+        vcs::{CommitInfo, VersionControlSystem, git::GitRepository, temp::EphemeralRepository},
+    };
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    fn commit(subject: &str, body: &str) -> CommitInfo {
+        CommitInfo {
+            sha: format!("{:0>40}", subject.len()),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        crate::tests::init();
+        let cases = [
+            ("feat: add auto bump", "", Some(ChangeKind::Feature)),
+            ("feat(cli): add auto bump", "", Some(ChangeKind::Feature)),
+            ("Feat: add auto bump", "", Some(ChangeKind::Feature)),
+            ("fix: handle empty repos", "", Some(ChangeKind::Fix)),
+            ("fix(git)!: drop old tags", "", Some(ChangeKind::Breaking)),
+            ("refactor!: remove dry run", "", Some(ChangeKind::Breaking)),
+            (
+                "feat: new config format",
+                "Details.\n\nBREAKING CHANGE: old format is rejected",
+                Some(ChangeKind::Breaking),
+            ),
+            (
+                "fix: parse tags",
+                "BREAKING-CHANGE: tags require a prefix",
+                Some(ChangeKind::Breaking),
+            ),
+            ("docs: document auto bump", "", None),
+            ("feature: not conventional", "", None),
+            ("feat:missing space", "", None),
+            ("Update README", "mentions a BREAKING CHANGE: inline", None),
+        ];
+        for (subject, body, expected) in cases {
+            sim_assert_eq!(classify(&commit(subject, body)), expected, "{subject:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_subject() {
+        crate::tests::init();
+        sim_assert_eq!(
+            super::parse_subject("feat(cli)!: add  auto bump "),
+            Some(super::Subject {
+                commit_type: "feat",
+                scope: Some("cli"),
+                breaking: true,
+                description: "add  auto bump",
+            })
+        );
+        sim_assert_eq!(
+            super::parse_subject("fix: handle empty repos"),
+            Some(super::Subject {
+                commit_type: "fix",
+                scope: None,
+                breaking: false,
+                description: "handle empty repos",
+            })
+        );
+        sim_assert_eq!(super::parse_subject("Update README"), None);
+    }
+
+    #[test]
+    fn test_select() {
+        crate::tests::init();
+        let config = GlobalConfigFinalized::default();
+        let commits = [
+            commit("fix: first", ""),
+            commit("chore: second", ""),
+            commit("feat: third", ""),
+            commit("feat(cli): fourth", ""),
+        ];
+        let auto = super::select(&commits, &config).unwrap();
+        sim_assert_eq!(auto.component, "minor");
+        sim_assert_eq!(auto.kind, ChangeKind::Feature);
+        sim_assert_eq!(auto.commits, vec![commits[2].clone(), commits[3].clone()]);
+        sim_assert_eq!(
+            auto.to_string(),
+            indoc::indoc! {"
+                bumping minor because of 2 feature commit(s):
+                  0000000 feat: third
+                  0000000 feat(cli): fourth"
+            }
+        );
+
+        sim_assert_eq!(super::select(&commits[1..2], &config), None);
+    }
+
+    #[test]
+    fn test_select_with_configured_components() {
+        crate::tests::init();
+        let config = GlobalConfig {
+            auto_bump_breaking: Some("minor".to_string()),
+            auto_bump_fix: Some("build".to_string()),
+            ..GlobalConfig::empty()
+        }
+        .finalize();
+        let commits = [commit("fix: first", "")];
+        sim_assert_eq!(
+            super::select(&commits, &config).map(|auto| auto.component),
+            Some("build".to_string())
+        );
+        let commits = [commit("feat!: second", "")];
+        sim_assert_eq!(
+            super::select(&commits, &config).map(|auto| auto.component),
+            Some("minor".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_since_latest_tag() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let commit = |message: &'static str| {
+            repo.commit(
+                message,
+                ["--allow-empty"],
+                [
+                    ("GIT_AUTHOR_NAME", "bumpversion"),
+                    ("GIT_AUTHOR_EMAIL", "bumpversion@example.com"),
+                    ("GIT_COMMITTER_NAME", "bumpversion"),
+                    ("GIT_COMMITTER_EMAIL", "bumpversion@example.com"),
+                ],
+            )
+        };
+        let config = GlobalConfigFinalized::default();
+
+        // no commits yet
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&config.tag_name, &config.parse_version_pattern)
+            .await?;
+        sim_assert_eq!(
+            super::select_since_latest_tag(&*repo, &tag_and_revision, &config).await?,
+            None
+        );
+
+        commit("feat!: initial release").await?;
+        repo.tag("v1.2.3", None, false).await?;
+        commit("fix: first fix").await?;
+        commit("docs: document it").await?;
+        commit("fix(cli): second fix\n\nwith a body").await?;
+
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&config.tag_name, &config.parse_version_pattern)
+            .await?;
+        let auto = super::select_since_latest_tag(&*repo, &tag_and_revision, &config)
+            .await?
+            .unwrap();
+        sim_assert_eq!(auto.component, "patch");
+        sim_assert_eq!(
+            auto.commits
+                .iter()
+                .map(|commit| (commit.subject.as_str(), commit.body.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("fix(cli): second fix", "with a body"),
+                ("fix: first fix", "")
+            ]
+        );
+        Ok(())
+    }
+}
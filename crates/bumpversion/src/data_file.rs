@@ -0,0 +1,758 @@
+//! Structured replacement of a single value in TOML, JSON and YAML data files.
+//!
+//! Only the string value at the configured key path is rewritten,
+//! leaving the remaining contents (formatting, comments, and other values) untouched.
+
+use std::ops::Range;
+use std::path::Path;
+
+/// Format of a data file that supports editing values by key path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl DataFormat {
+    /// Infer the data format from the file extension of `path`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that can occur when parsing a data file.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Toml(#[from] toml_edit::TomlError),
+    #[error("invalid JSON at line {line}, column {column}: {message}")]
+    Json {
+        message: &'static str,
+        line: usize,
+        column: usize,
+    },
+}
+
+/// Replace all matches of `search` in the string value at the dot-separated `key_path`.
+///
+/// Returns `None` if the key does not exist, its value is not a string,
+/// or the value does not match `search`.
+///
+/// # Errors
+/// Returns an `Error` if the TOML or JSON contents are invalid.
+pub fn replace_value(
+    format: DataFormat,
+    contents: &str,
+    key_path: &str,
+    search: &regex::Regex,
+    replacement: &str,
+) -> Result<Option<String>, Error> {
+    let key_path: Vec<&str> = key_path.split('.').collect();
+    let scalar = match format {
+        DataFormat::Toml => {
+            let mut document: toml_edit::DocumentMut = contents.parse()?;
+            let replaced = crate::config::toml::replace_version_of_document(
+                &mut document,
+                &key_path,
+                search,
+                replacement,
+            );
+            return Ok(replaced.then(|| document.to_string()));
+        }
+        DataFormat::Json => json::find_string(contents, &key_path)?,
+        DataFormat::Yaml => yaml::find_string(contents, &key_path),
+    };
+    let Some(scalar) = scalar else {
+        return Ok(None);
+    };
+    if !search.is_match(&scalar.value) {
+        return Ok(None);
+    }
+    let new_value = search.replace_all(&scalar.value, replacement);
+
+    // keep the original escapes of the value if the version can be replaced in the source text
+    let original = &contents[scalar.span.clone()];
+    let spliced = search.replace_all(original, replacement);
+    let encoded = if decode(format, scalar.style, &spliced).as_deref() == Some(&*new_value) {
+        spliced.into_owned()
+    } else {
+        scalar.style.encode(&new_value)
+    };
+    let after = [
+        &contents[..scalar.span.start],
+        &encoded,
+        &contents[scalar.span.end..],
+    ]
+    .concat();
+    Ok(Some(after))
+}
+
+/// Decode the source `text` of a single string value.
+///
+/// Returns `None` if `text` is not exactly one valid string value.
+fn decode(format: DataFormat, style: Style, text: &str) -> Option<String> {
+    let scalar = match (format, style) {
+        (_, Style::Plain) => return Some(text.to_string()),
+        (DataFormat::Json, _) => json::find_string(text, &[]).ok()??,
+        (DataFormat::Yaml, Style::SingleQuoted) => yaml::parse_quoted(text, '\'')?,
+        (DataFormat::Yaml, Style::DoubleQuoted) => yaml::parse_quoted(text, '"')?,
+        (DataFormat::Toml, _) => return None,
+    };
+    (scalar.span == (0..text.len())).then_some(scalar.value)
+}
+
+/// Quote and escape a string for JSON.
+pub(crate) fn json_string(value: &str) -> String {
+    use std::fmt::Write;
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// How a string value is written in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+}
+
+impl Style {
+    fn encode(self, value: &str) -> String {
+        match self {
+            Self::Plain => value.to_string(),
+            Self::SingleQuoted => format!("'{}'", value.replace('\'', "''")),
+            // JSON strings are valid double-quoted YAML scalars
+            Self::DoubleQuoted => json_string(value),
+        }
+    }
+}
+
+/// A string value located in the source text of a data file.
+#[derive(Debug, PartialEq, Eq)]
+struct Scalar {
+    /// Span of the value in the source text, including quotes.
+    span: Range<usize>,
+    /// The unescaped value.
+    value: String,
+    style: Style,
+}
+
+mod json {
+    use super::{Error, Scalar, Style};
+
+    /// Find the string value at `key_path`.
+    ///
+    /// Numeric path segments index into arrays.
+    pub(super) fn find_string(src: &str, key_path: &[&str]) -> Result<Option<Scalar>, Error> {
+        let mut parser = Parser { src, pos: 0 };
+        let found = parser.value(Some(key_path))?;
+        parser.whitespace();
+        if parser.pos < src.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(found)
+    }
+
+    struct Parser<'a> {
+        src: &'a str,
+        pos: usize,
+    }
+
+    impl Parser<'_> {
+        fn peek(&self) -> Option<char> {
+            self.src[self.pos..].chars().next()
+        }
+
+        fn next(&mut self) -> Option<char> {
+            let c = self.peek()?;
+            self.pos += c.len_utf8();
+            Some(c)
+        }
+
+        fn eat(&mut self, expected: char) -> bool {
+            let matches = self.peek() == Some(expected);
+            if matches {
+                self.pos += expected.len_utf8();
+            }
+            matches
+        }
+
+        fn expect(&mut self, expected: char, message: &'static str) -> Result<(), Error> {
+            if self.eat(expected) {
+                Ok(())
+            } else {
+                Err(self.error(message))
+            }
+        }
+
+        fn whitespace(&mut self) {
+            while self
+                .peek()
+                .is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+            {
+                self.pos += 1;
+            }
+        }
+
+        fn error(&self, message: &'static str) -> Error {
+            let before = &self.src[..self.pos];
+            let line = before.matches('\n').count() + 1;
+            let column = before.len() - before.rfind('\n').map_or(0, |idx| idx + 1) + 1;
+            Error::Json {
+                message,
+                line,
+                column,
+            }
+        }
+
+        /// Parse a value, returning the string at `path` if it is contained in this value.
+        fn value(&mut self, path: Option<&[&str]>) -> Result<Option<Scalar>, Error> {
+            self.whitespace();
+            match self.peek() {
+                Some('{') => self.object(path),
+                Some('[') => self.array(path),
+                Some('"') => {
+                    let (span, value) = self.string()?;
+                    let is_target = path.is_some_and(<[&str]>::is_empty);
+                    Ok(is_target.then_some(Scalar {
+                        span,
+                        value,
+                        style: Style::DoubleQuoted,
+                    }))
+                }
+                Some('t') => self.literal("true"),
+                Some('f') => self.literal("false"),
+                Some('n') => self.literal("null"),
+                Some('-' | '0'..='9') => {
+                    while self
+                        .peek()
+                        .is_some_and(|c| matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+                    {
+                        self.pos += 1;
+                    }
+                    Ok(None)
+                }
+                Some(_) => Err(self.error("expected value")),
+                None => Err(self.error("unexpected end of input")),
+            }
+        }
+
+        fn literal(&mut self, literal: &'static str) -> Result<Option<Scalar>, Error> {
+            if self.src[self.pos..].starts_with(literal) {
+                self.pos += literal.len();
+                Ok(None)
+            } else {
+                Err(self.error("expected value"))
+            }
+        }
+
+        fn object(&mut self, path: Option<&[&str]>) -> Result<Option<Scalar>, Error> {
+            self.expect('{', "expected `{`")?;
+            self.whitespace();
+            if self.eat('}') {
+                return Ok(None);
+            }
+            let mut found = None;
+            loop {
+                self.whitespace();
+                if self.peek() != Some('"') {
+                    return Err(self.error("expected key"));
+                }
+                let (_, key) = self.string()?;
+                self.whitespace();
+                self.expect(':', "expected `:`")?;
+                let child_path = path
+                    .and_then(<[&str]>::split_first)
+                    .filter(|(first, _)| **first == key)
+                    .map(|(_, rest)| rest);
+                let value = self.value(child_path)?;
+                found = found.or(value);
+                self.whitespace();
+                if !self.eat(',') {
+                    self.expect('}', "expected `,` or `}`")?;
+                    return Ok(found);
+                }
+            }
+        }
+
+        fn array(&mut self, path: Option<&[&str]>) -> Result<Option<Scalar>, Error> {
+            self.expect('[', "expected `[`")?;
+            self.whitespace();
+            if self.eat(']') {
+                return Ok(None);
+            }
+            let mut found = None;
+            for index in 0.. {
+                let child_path = path
+                    .and_then(<[&str]>::split_first)
+                    .filter(|(first, _)| first.parse::<usize>().ok() == Some(index))
+                    .map(|(_, rest)| rest);
+                let value = self.value(child_path)?;
+                found = found.or(value);
+                self.whitespace();
+                if !self.eat(',') {
+                    break;
+                }
+            }
+            self.expect(']', "expected `,` or `]`")?;
+            Ok(found)
+        }
+
+        /// Parse a string, returning its span including quotes and the unescaped value.
+        fn string(&mut self) -> Result<(std::ops::Range<usize>, String), Error> {
+            let start = self.pos;
+            self.expect('"', "expected `\"`")?;
+            let mut value = String::new();
+            loop {
+                match self.next() {
+                    None => return Err(self.error("unterminated string")),
+                    Some('"') => break,
+                    Some('\\') => {
+                        let c = match self.next() {
+                            Some('"') => '"',
+                            Some('\\') => '\\',
+                            Some('/') => '/',
+                            Some('b') => '\u{8}',
+                            Some('f') => '\u{c}',
+                            Some('n') => '\n',
+                            Some('r') => '\r',
+                            Some('t') => '\t',
+                            Some('u') => self.unicode_escape()?,
+                            _ => return Err(self.error("invalid escape sequence")),
+                        };
+                        value.push(c);
+                    }
+                    Some(c) if c.is_control() => {
+                        return Err(self.error("control character in string"));
+                    }
+                    Some(c) => value.push(c),
+                }
+            }
+            Ok((start..self.pos, value))
+        }
+
+        fn hex4(&mut self) -> Result<u32, Error> {
+            let hex = self
+                .src
+                .get(self.pos..self.pos + 4)
+                .ok_or_else(|| self.error("invalid unicode escape"))?;
+            let code =
+                u32::from_str_radix(hex, 16).map_err(|_| self.error("invalid unicode escape"))?;
+            self.pos += 4;
+            Ok(code)
+        }
+
+        fn unicode_escape(&mut self) -> Result<char, Error> {
+            let mut code = self.hex4()?;
+            if (0xD800..0xDC00).contains(&code) {
+                // high surrogate, must be followed by a low surrogate
+                if !self.src[self.pos..].starts_with("\\u") {
+                    return Err(self.error("invalid unicode escape"));
+                }
+                self.pos += 2;
+                let low = self.hex4()?;
+                if !(0xDC00..0xE000).contains(&low) {
+                    return Err(self.error("invalid unicode escape"));
+                }
+                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+            }
+            char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+        }
+    }
+}
+
+mod yaml {
+    use super::{Scalar, Style};
+
+    /// Find the scalar value at `key_path` in nested block mappings.
+    ///
+    /// Flow collections, block scalars, and sequences are not supported.
+    pub(super) fn find_string(src: &str, key_path: &[&str]) -> Option<Scalar> {
+        let mut depth = 0;
+        let mut parent_indent: Option<usize> = None;
+        let mut level_indent: Option<usize> = None;
+        let mut offset = 0;
+        for line in src.split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+
+            let content = line.trim_end_matches(['\n', '\r']);
+            let trimmed = content.trim_start_matches(' ');
+            let indent = content.len() - trimmed.len();
+            let is_document_marker =
+                indent == 0 && (trimmed.starts_with("---") || trimmed.starts_with("..."));
+            if trimmed.is_empty() || trimmed.starts_with('#') || is_document_marker {
+                continue;
+            }
+            if parent_indent.is_some_and(|parent_indent| indent <= parent_indent) {
+                // left the mapping of the parent key
+                return None;
+            }
+            if indent != *level_indent.get_or_insert(indent) {
+                // nested below a sibling key
+                continue;
+            }
+            let Some((key, value_offset)) = parse_key(trimmed) else {
+                continue;
+            };
+            if key != *key_path.get(depth)? {
+                continue;
+            }
+            if depth + 1 == key_path.len() {
+                let value_start = line_start + indent + value_offset;
+                let value_end = line_start + content.len();
+                return parse_scalar(src, value_start..value_end);
+            }
+            depth += 1;
+            parent_indent = Some(indent);
+            level_indent = None;
+        }
+        None
+    }
+
+    /// Parse the key of a mapping entry, returning the key and the offset after the `:`.
+    fn parse_key(line: &str) -> Option<(String, usize)> {
+        let (key, rest_offset) = match line.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let scalar = parse_quoted(line, quote)?;
+                (scalar.value, scalar.span.end)
+            }
+            '-' | '[' | '{' | '?' | '|' | '>' => return None,
+            _ => {
+                let end = line.match_indices(':').map(|(idx, _)| idx).find(|&idx| {
+                    line[idx + 1..]
+                        .chars()
+                        .next()
+                        .is_none_or(|c| matches!(c, ' ' | '\t'))
+                })?;
+                (line[..end].trim_end().to_string(), end)
+            }
+        };
+        let rest = &line[rest_offset..];
+        let colon = rest.len() - rest.trim_start().len();
+        rest[colon..]
+            .starts_with(':')
+            .then_some((key, rest_offset + colon + 1))
+    }
+
+    /// Parse a quoted scalar at the start of `text`.
+    pub(super) fn parse_quoted(text: &str, quote: char) -> Option<Scalar> {
+        let mut value = String::new();
+        let mut chars = text.char_indices().skip(1).peekable();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '\\' if quote == '"' => {
+                    let (_, escaped) = chars.next()?;
+                    value.push(match escaped {
+                        '0' => '\0',
+                        'a' => '\u{7}',
+                        'b' => '\u{8}',
+                        't' | '\t' => '\t',
+                        'n' => '\n',
+                        'v' => '\u{b}',
+                        'f' => '\u{c}',
+                        'r' => '\r',
+                        'e' => '\u{1b}',
+                        ' ' => ' ',
+                        '"' => '"',
+                        '/' => '/',
+                        '\\' => '\\',
+                        'N' => '\u{85}',
+                        '_' => '\u{a0}',
+                        'L' => '\u{2028}',
+                        'P' => '\u{2029}',
+                        'x' => hex_escape(&mut chars, 2)?,
+                        'u' => hex_escape(&mut chars, 4)?,
+                        'U' => hex_escape(&mut chars, 8)?,
+                        // invalid escape sequence
+                        _ => return None,
+                    });
+                }
+                '\'' if quote == '\'' && chars.peek().is_some_and(|(_, c)| *c == '\'') => {
+                    chars.next();
+                    value.push('\'');
+                }
+                c if c == quote => {
+                    let style = if quote == '"' {
+                        Style::DoubleQuoted
+                    } else {
+                        Style::SingleQuoted
+                    };
+                    return Some(Scalar {
+                        span: 0..idx + 1,
+                        value,
+                        style,
+                    });
+                }
+                c => value.push(c),
+            }
+        }
+        None
+    }
+
+    /// Parse the `digits` hexadecimal digits of a `\x`, `\u` or `\U` escape sequence.
+    fn hex_escape(chars: &mut impl Iterator<Item = (usize, char)>, digits: usize) -> Option<char> {
+        let hex: String = chars.take(digits).map(|(_, c)| c).collect();
+        if hex.len() != digits || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+    }
+
+    /// Parse the scalar value of a mapping entry within `span` of `src`.
+    fn parse_scalar(src: &str, span: std::ops::Range<usize>) -> Option<Scalar> {
+        let text = &src[span.clone()];
+        let leading = text.len() - text.trim_start().len();
+        let start = span.start + leading;
+        let text = &text[leading..];
+        match text.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let scalar = parse_quoted(text, quote)?;
+                Some(Scalar {
+                    span: start + scalar.span.start..start + scalar.span.end,
+                    ..scalar
+                })
+            }
+            '#' | '|' | '>' | '[' | '{' | '&' | '*' | '!' | '%' | '@' | '`' => None,
+            _ => {
+                // a comment must be separated from the value by whitespace
+                let end = text
+                    .match_indices(" #")
+                    .chain(text.match_indices("\t#"))
+                    .map(|(idx, _)| idx)
+                    .min()
+                    .unwrap_or(text.len());
+                let value = text[..end].trim_end();
+                Some(Scalar {
+                    span: start..start + value.len(),
+                    value: value.to_string(),
+                    style: Style::Plain,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataFormat, replace_value};
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    fn bump(format: DataFormat, contents: &str, key_path: &str) -> eyre::Result<Option<String>> {
+        let search = regex::Regex::new(&regex::escape("1.2.3"))?;
+        Ok(replace_value(format, contents, key_path, &search, "1.3.0")?)
+    }
+
+    #[test]
+    fn test_data_format_from_path() {
+        use std::path::Path;
+        sim_assert_eq!(
+            DataFormat::from_path(Path::new("package.json")),
+            Some(DataFormat::Json)
+        );
+        sim_assert_eq!(
+            DataFormat::from_path(Path::new("chart/Chart.YML")),
+            Some(DataFormat::Yaml)
+        );
+        sim_assert_eq!(
+            DataFormat::from_path(Path::new("Cargo.toml")),
+            Some(DataFormat::Toml)
+        );
+        sim_assert_eq!(DataFormat::from_path(Path::new("setup.cfg")), None);
+    }
+
+    #[test]
+    fn test_replace_json_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let package_json = indoc::indoc! {r#"
+            {
+              "name": "my-package",
+              "version":   "1.2.3",
+              "dependencies": {
+                "other-package": "1.2.3"
+              },
+              "files": ["dist", {"version": "1.2.3"}]
+            }
+        "#};
+        sim_assert_eq!(
+            bump(DataFormat::Json, package_json, "version")?,
+            Some(
+                indoc::indoc! {r#"
+                {
+                  "name": "my-package",
+                  "version":   "1.3.0",
+                  "dependencies": {
+                    "other-package": "1.2.3"
+                  },
+                  "files": ["dist", {"version": "1.2.3"}]
+                }
+            "#}
+                .to_string()
+            )
+        );
+        sim_assert_eq!(
+            bump(DataFormat::Json, package_json, "files.1.version")?,
+            Some(package_json.replacen(r#"{"version": "1.2.3"}"#, r#"{"version": "1.3.0"}"#, 1))
+        );
+        sim_assert_eq!(bump(DataFormat::Json, package_json, "name")?, None);
+        sim_assert_eq!(bump(DataFormat::Json, package_json, "missing")?, None);
+        sim_assert_eq!(bump(DataFormat::Json, package_json, "files")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_json_escaped_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let json = r#"{"version": "v1.2.3 \"stable\""}"#;
+        sim_assert_eq!(
+            bump(DataFormat::Json, json, "version")?,
+            Some(r#"{"version": "v1.3.0 \"stable\""}"#.to_string())
+        );
+
+        // escapes outside of the version are kept
+        let json = r#"{"version": "caf\u00e9\/1.2.3"}"#;
+        sim_assert_eq!(
+            bump(DataFormat::Json, json, "version")?,
+            Some(r#"{"version": "caf\u00e9\/1.3.0"}"#.to_string())
+        );
+
+        // escapes within the version are re-encoded
+        let json = r#"{"version": "caf\u00e9 1.2\u002e3"}"#;
+        sim_assert_eq!(
+            bump(DataFormat::Json, json, "version")?,
+            Some(r#"{"version": "café 1.3.0"}"#.to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_invalid_json_value() {
+        crate::tests::init();
+
+        let json = "{\n  \"version\": \"1.2.3\"\n  \"name\": \"my-package\"\n}";
+        let err = bump(DataFormat::Json, json, "version").unwrap_err();
+        sim_assert_eq!(
+            err.to_string(),
+            "invalid JSON at line 3, column 3: expected `,` or `}`"
+        );
+    }
+
+    #[test]
+    fn test_replace_yaml_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let chart_yaml = indoc::indoc! {r#"
+            # the chart
+            apiVersion: v2
+            version: 1.2.3 # the chart version
+            dependencies:
+              - name: other-chart
+                version: 1.2.3
+            image:
+              tag: "1.2.3"
+              "digest": none
+            app:
+              meta:
+                version: '1.2.3'
+              version: 1.2.3
+        "#};
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, chart_yaml, "version")?,
+            Some(chart_yaml.replacen(
+                "version: 1.2.3 # the chart version",
+                "version: 1.3.0 # the chart version",
+                1
+            ))
+        );
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, chart_yaml, "image.tag")?,
+            Some(chart_yaml.replacen(r#"tag: "1.2.3""#, r#"tag: "1.3.0""#, 1))
+        );
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, chart_yaml, "app.meta.version")?,
+            Some(chart_yaml.replacen("version: '1.2.3'", "version: '1.3.0'", 1))
+        );
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, chart_yaml, "app.version")?,
+            Some(chart_yaml.replacen("'\n  version: 1.2.3", "'\n  version: 1.3.0", 1))
+        );
+        sim_assert_eq!(bump(DataFormat::Yaml, chart_yaml, "image.version")?, None);
+        sim_assert_eq!(bump(DataFormat::Yaml, chart_yaml, "apiVersion")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_yaml_escaped_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        // escapes outside of the version are kept
+        let yaml = r#"version: "caf\u00e9 \x41\t1.2.3 \"stable\"""#;
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, yaml, "version")?,
+            Some(yaml.replacen("1.2.3", "1.3.0", 1))
+        );
+
+        // escapes within the version are re-encoded
+        let yaml = r#"version: "caf\u00e9 1\x2e2.3""#;
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, yaml, "version")?,
+            Some(r#"version: "café 1.3.0""#.to_string())
+        );
+
+        let yaml = "version: 'it''s 1.2.3'";
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, yaml, "version")?,
+            Some("version: 'it''s 1.3.0'".to_string())
+        );
+
+        // invalid escape sequences are not a string value
+        sim_assert_eq!(
+            bump(DataFormat::Yaml, r#"version: "\q1.2.3""#, "version")?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_toml_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let pyproject_toml = indoc::indoc! {r#"
+            [project]
+            version = "1.2.3"  # the version
+            dependencies = ["other-package==1.2.3"]
+        "#};
+        sim_assert_eq!(
+            bump(DataFormat::Toml, pyproject_toml, "project.version")?,
+            Some(pyproject_toml.replacen(r#""1.2.3""#, r#""1.3.0""#, 1))
+        );
+        sim_assert_eq!(bump(DataFormat::Toml, pyproject_toml, "version")?, None);
+        Ok(())
+    }
+}
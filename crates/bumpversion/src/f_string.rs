@@ -1,7 +1,7 @@
 //! Parsing support for Python-style format strings used in version templates.
 //!
 //! Provides utilities to split format strings into literal text and argument placeholders,
-//! and to unescape double curly braces.
+//! to unescape double curly braces, and to format arguments using conversions and format specs.
 pub use parser::ParseError;
 use std::collections::HashMap;
 
@@ -125,9 +125,15 @@ pub mod parser {
     }
 
     #[derive(thiserror::Error, Debug, PartialEq, Eq)]
-    #[error("invalid format: {format_string:?}")]
-    pub struct ParseError {
-        pub format_string: String,
+    pub enum ParseError {
+        #[error("invalid format: {format_string:?}")]
+        InvalidFormat { format_string: String },
+        #[error("invalid format field {field:?}: {source}")]
+        InvalidField {
+            field: String,
+            #[source]
+            source: super::spec::FieldError,
+        },
     }
 
     /// Unescape doubled braces (`{{` -> `{`, `}}` -> `}`) in `value`.
@@ -135,14 +141,32 @@ pub mod parser {
     /// # Errors
     /// Returns `ParseError` if the input is not valid.
     pub fn escape_double_curly_braces(value: &str) -> Result<String, ParseError> {
-        let test = text_including_escaped_brackets
-            .parse(value)
-            .map_err(|_| ParseError {
+        let test = text_including_escaped_brackets.parse(value).map_err(|_| {
+            ParseError::InvalidFormat {
                 format_string: value.to_string(),
-            })?;
+            }
+        })?;
         Ok(test)
     }
 
+    /// Split the name of a replacement field into the argument name and its default value.
+    ///
+    /// The default follows the first `|`, e.g. `$BUILD_NUMBER|0` or `distance_to_latest_tag|`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bumpversion::f_string::parser::split_default;
+    /// assert_eq!(split_default("$BUILD_NUMBER|0"), ("$BUILD_NUMBER", Some("0")));
+    /// assert_eq!(split_default("patch"), ("patch", None));
+    /// ```
+    #[must_use]
+    pub fn split_default(name: &str) -> (&str, Option<&str>) {
+        match name.split_once('|') {
+            Some((name, default)) => (name, Some(default)),
+            None => (name, None),
+        }
+    }
+
     /// Parse a format string into a sequence of `Value` segments.
     ///
     /// # Examples
@@ -151,12 +175,13 @@ pub mod parser {
     /// let parts = parse_format_arguments("v{major}.{minor}.{patch}")?;
     /// # Ok::<(), bumpversion::f_string::ParseError>(())
     /// ```
-    pub fn parse_format_arguments(value: &str) -> Result<Vec<Value>, ParseError> {
-        let test = repeat(0.., text_or_argument)
-            .parse(value)
-            .map_err(|_| ParseError {
-                format_string: value.to_string(),
-            })?;
+    pub fn parse_format_arguments(value: &str) -> Result<Vec<Value<'_>>, ParseError> {
+        let test =
+            repeat(0.., text_or_argument)
+                .parse(value)
+                .map_err(|_| ParseError::InvalidFormat {
+                    format_string: value.to_string(),
+                })?;
         Ok(test)
     }
 
@@ -278,6 +303,490 @@ pub mod parser {
     }
 }
 
+pub mod spec {
+    //! Replacement fields with conversions and format specs, e.g. `{patch!s:0>3}`.
+    //!
+    //! Supports the standard format spec mini-language of Python for strings and integers,
+    //! and `strftime` formats (containing `%`) for timestamps.
+    //!
+    //! Since all template values are strings, integer presentation types (e.g. `d` or `x`)
+    //! parse the value as an integer first.
+
+    /// Errors when parsing a replacement field.
+    #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum FieldError {
+        #[error("invalid conversion {0:?} (expected one of `!s`, `!r`, or `!a`)")]
+        InvalidConversion(String),
+        #[error("invalid format spec {0:?}")]
+        InvalidSpec(String),
+        #[error("unsupported format type {0:?} (expected one of `s`, `d`, `b`, `o`, `x`, or `X`)")]
+        UnsupportedType(char),
+        #[error("{option} not allowed with format type {format_type:?}")]
+        NotAllowed {
+            option: &'static str,
+            format_type: char,
+        },
+        #[error("invalid strftime format {0:?}")]
+        InvalidStrftime(String),
+    }
+
+    /// Errors when formatting a value.
+    #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum ValueError {
+        #[error("cannot format {value:?} as an integer")]
+        NotAnInteger { value: String },
+        #[error("cannot format {value:?} as a timestamp")]
+        NotATimestamp { value: String },
+    }
+
+    /// Conversion applied to a value before formatting it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Conversion {
+        /// `!s`
+        Str,
+        /// `!r`
+        Repr,
+        /// `!a`
+        Ascii,
+    }
+
+    /// Alignment of a formatted value within its width.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Align {
+        /// `<`
+        Left,
+        /// `>`
+        Right,
+        /// `^`
+        Center,
+        /// `=`, padding after the sign of a number.
+        AfterSign,
+    }
+
+    /// Sign of a formatted number.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Sign {
+        /// `+`
+        Always,
+        /// `-`
+        OnlyNegative,
+        /// ` `
+        Space,
+    }
+
+    /// Presentation type of a formatted value.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum FormatType {
+        /// `s`
+        String,
+        /// `d`
+        Decimal,
+        /// `b`
+        Binary,
+        /// `o`
+        Octal,
+        /// `x`
+        LowerHex,
+        /// `X`
+        UpperHex,
+    }
+
+    impl FormatType {
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                's' => Some(Self::String),
+                'd' => Some(Self::Decimal),
+                'b' => Some(Self::Binary),
+                'o' => Some(Self::Octal),
+                'x' => Some(Self::LowerHex),
+                'X' => Some(Self::UpperHex),
+                _ => None,
+            }
+        }
+
+        fn as_char(self) -> char {
+            match self {
+                Self::String => 's',
+                Self::Decimal => 'd',
+                Self::Binary => 'b',
+                Self::Octal => 'o',
+                Self::LowerHex => 'x',
+                Self::UpperHex => 'X',
+            }
+        }
+
+        fn radix(self) -> Option<(u32, &'static str)> {
+            match self {
+                Self::String => None,
+                Self::Decimal => Some((10, "")),
+                Self::Binary => Some((2, "0b")),
+                Self::Octal => Some((8, "0o")),
+                Self::LowerHex | Self::UpperHex => Some((16, "0x")),
+            }
+        }
+    }
+
+    /// A standard format spec, e.g. `0>4` or `+03d`.
+    ///
+    /// Follows `[[fill]align][sign][#][0][width][grouping][.precision][type]`.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+    pub struct StandardSpec {
+        pub fill: Option<char>,
+        pub align: Option<Align>,
+        pub sign: Option<Sign>,
+        pub alternate: bool,
+        pub zero: bool,
+        pub width: usize,
+        pub grouping: Option<char>,
+        pub precision: Option<usize>,
+        pub format_type: Option<FormatType>,
+    }
+
+    /// The format spec of a replacement field.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum FormatSpec<'a> {
+        /// A standard format spec for strings and integers.
+        Standard(StandardSpec),
+        /// A `strftime` format for timestamps, e.g. `%Y-%m-%d`.
+        Strftime(&'a str),
+    }
+
+    /// A replacement field, e.g. `{patch!s:0>3}` or `{$BUILD_NUMBER|0}`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Field<'a> {
+        /// The name of the argument.
+        pub name: &'a str,
+        /// The value used when the argument is missing, if any.
+        pub default: Option<&'a str>,
+        /// The conversion applied before formatting, if any.
+        pub conversion: Option<Conversion>,
+        /// The format spec, if any.
+        pub spec: Option<FormatSpec<'a>>,
+    }
+
+    fn parse_align(c: char) -> Option<Align> {
+        match c {
+            '<' => Some(Align::Left),
+            '>' => Some(Align::Right),
+            '^' => Some(Align::Center),
+            '=' => Some(Align::AfterSign),
+            _ => None,
+        }
+    }
+
+    fn parse_number(chars: &[char], idx: &mut usize) -> Option<usize> {
+        let start = *idx;
+        while chars.get(*idx).is_some_and(char::is_ascii_digit) {
+            *idx += 1;
+        }
+        chars[start..*idx].iter().collect::<String>().parse().ok()
+    }
+
+    impl StandardSpec {
+        /// Parse a standard format spec.
+        ///
+        /// # Errors
+        /// When the spec is invalid or uses options that are not allowed for its type.
+        pub fn parse(spec: &str) -> Result<Self, FieldError> {
+            let invalid = || FieldError::InvalidSpec(spec.to_string());
+            let chars: Vec<char> = spec.chars().collect();
+            let mut parsed = Self::default();
+            let mut idx = 0;
+
+            if let Some(align) = chars.get(1).copied().and_then(parse_align) {
+                parsed.fill = Some(chars[0]);
+                parsed.align = Some(align);
+                idx = 2;
+            } else if let Some(align) = chars.first().copied().and_then(parse_align) {
+                parsed.align = Some(align);
+                idx = 1;
+            }
+            parsed.sign = match chars.get(idx) {
+                Some('+') => Some(Sign::Always),
+                Some('-') => Some(Sign::OnlyNegative),
+                Some(' ') => Some(Sign::Space),
+                _ => None,
+            };
+            if parsed.sign.is_some() {
+                idx += 1;
+            }
+            if chars.get(idx) == Some(&'#') {
+                parsed.alternate = true;
+                idx += 1;
+            }
+            if chars.get(idx) == Some(&'0') {
+                parsed.zero = true;
+                idx += 1;
+            }
+            parsed.width = parse_number(&chars, &mut idx).unwrap_or_default();
+            if let Some(grouping @ (',' | '_')) = chars.get(idx).copied() {
+                parsed.grouping = Some(grouping);
+                idx += 1;
+            }
+            if chars.get(idx) == Some(&'.') {
+                idx += 1;
+                parsed.precision = Some(parse_number(&chars, &mut idx).ok_or_else(invalid)?);
+            }
+            if idx + 1 == chars.len() {
+                let format_type = chars[idx];
+                parsed.format_type = Some(
+                    FormatType::from_char(format_type)
+                        .ok_or(FieldError::UnsupportedType(format_type))?,
+                );
+                idx += 1;
+            }
+            if idx != chars.len() {
+                return Err(invalid());
+            }
+            parsed.validate()?;
+            Ok(parsed)
+        }
+
+        fn validate(&self) -> Result<(), FieldError> {
+            let format_type = self.format_type.unwrap_or(FormatType::String);
+            let not_allowed = |option| FieldError::NotAllowed {
+                option,
+                format_type: format_type.as_char(),
+            };
+            if format_type == FormatType::String {
+                if self.sign.is_some() {
+                    return Err(not_allowed("sign"));
+                }
+                if self.alternate {
+                    return Err(not_allowed("alternate form (#)"));
+                }
+                if self.align == Some(Align::AfterSign) {
+                    return Err(not_allowed("'=' alignment"));
+                }
+                if self.grouping.is_some() {
+                    return Err(not_allowed("grouping"));
+                }
+            } else {
+                if self.precision.is_some() {
+                    return Err(not_allowed("precision"));
+                }
+                if self.grouping == Some(',') && format_type != FormatType::Decimal {
+                    return Err(not_allowed("',' grouping"));
+                }
+            }
+            Ok(())
+        }
+
+        /// Format a `value` according to this spec.
+        ///
+        /// # Errors
+        /// When the spec has an integer type and the value is not an integer.
+        pub fn format(&self, value: &str) -> Result<String, ValueError> {
+            let Some((radix, prefix)) = self.format_type.and_then(FormatType::radix) else {
+                let value: String = match self.precision {
+                    Some(precision) => value.chars().take(precision).collect(),
+                    None => value.to_string(),
+                };
+                let fill = self.fill.unwrap_or(if self.zero { '0' } else { ' ' });
+                return Ok(pad(
+                    "",
+                    &value,
+                    self.width,
+                    fill,
+                    self.align.unwrap_or(Align::Left),
+                ));
+            };
+
+            let number: i128 = value.parse().map_err(|_| ValueError::NotAnInteger {
+                value: value.to_string(),
+            })?;
+            let mut digits = to_radix(number.unsigned_abs(), radix);
+            if self.format_type == Some(FormatType::UpperHex) {
+                digits = digits.to_uppercase();
+            }
+            if let Some(grouping) = self.grouping {
+                let group_size = if radix == 10 { 3 } else { 4 };
+                digits = group_digits(&digits, grouping, group_size);
+            }
+
+            let mut sign_and_prefix = match self.sign {
+                _ if number < 0 => "-".to_string(),
+                Some(Sign::Always) => "+".to_string(),
+                Some(Sign::Space) => " ".to_string(),
+                Some(Sign::OnlyNegative) | None => String::new(),
+            };
+            if self.alternate {
+                sign_and_prefix.push_str(if self.format_type == Some(FormatType::UpperHex) {
+                    "0X"
+                } else {
+                    prefix
+                });
+            }
+
+            let (fill, align) = if self.zero {
+                (
+                    self.fill.unwrap_or('0'),
+                    self.align.unwrap_or(Align::AfterSign),
+                )
+            } else {
+                (self.fill.unwrap_or(' '), self.align.unwrap_or(Align::Right))
+            };
+            Ok(pad(&sign_and_prefix, &digits, self.width, fill, align))
+        }
+    }
+
+    fn to_radix(mut number: u128, radix: u32) -> String {
+        if number == 0 {
+            return "0".to_string();
+        }
+        let mut digits = Vec::new();
+        while number > 0 {
+            let digit = u32::try_from(number % u128::from(radix)).unwrap_or_default();
+            digits.push(char::from_digit(digit, radix).unwrap_or('0'));
+            number /= u128::from(radix);
+        }
+        digits.iter().rev().collect()
+    }
+
+    fn group_digits(digits: &str, separator: char, group_size: usize) -> String {
+        let len = digits.chars().count();
+        let mut grouped = String::with_capacity(len + len / group_size);
+        for (idx, digit) in digits.chars().enumerate() {
+            if idx > 0 && (len - idx).is_multiple_of(group_size) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Pad the `sign` and `value` with `fill` to `width` characters.
+    fn pad(sign: &str, value: &str, width: usize, fill: char, align: Align) -> String {
+        let len = sign.chars().count() + value.chars().count();
+        let padding = width.saturating_sub(len);
+        let fill = |count: usize| std::iter::repeat_n(fill, count).collect::<String>();
+        match align {
+            Align::Left => format!("{sign}{value}{}", fill(padding)),
+            Align::Right => format!("{}{sign}{value}", fill(padding)),
+            Align::Center => format!(
+                "{}{sign}{value}{}",
+                fill(padding / 2),
+                fill(padding - padding / 2)
+            ),
+            Align::AfterSign => format!("{sign}{}{value}", fill(padding)),
+        }
+    }
+
+    /// Python `repr` of a string value.
+    fn repr(value: &str, ascii: bool) -> String {
+        use std::fmt::Write;
+
+        let quote = if value.contains('\'') && !value.contains('"') {
+            '"'
+        } else {
+            '\''
+        };
+        let mut out = String::with_capacity(value.len() + 2);
+        out.push(quote);
+        for c in value.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c == quote => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                c if c.is_control() || (ascii && !c.is_ascii()) => {
+                    let code = u32::from(c);
+                    let _ = if code <= 0xff {
+                        write!(out, "\\x{code:02x}")
+                    } else if code <= 0xffff {
+                        write!(out, "\\u{code:04x}")
+                    } else {
+                        write!(out, "\\U{code:08x}")
+                    };
+                }
+                c => out.push(c),
+            }
+        }
+        out.push(quote);
+        out
+    }
+
+    impl<'a> Field<'a> {
+        /// Parse a replacement field, without the surrounding curly braces.
+        ///
+        /// # Errors
+        /// When the conversion or format spec is invalid.
+        pub fn parse(field: &'a str) -> Result<Self, FieldError> {
+            let mut depth = 0usize;
+            let name_end = field
+                .char_indices()
+                .find(|(_, c)| {
+                    match c {
+                        '[' => depth += 1,
+                        ']' => depth = depth.saturating_sub(1),
+                        '!' | ':' if depth == 0 => return true,
+                        _ => {}
+                    }
+                    false
+                })
+                .map_or(field.len(), |(idx, _)| idx);
+            let (name, mut rest) = field.split_at(name_end);
+            let (name, default) = super::parser::split_default(name);
+
+            let mut conversion = None;
+            if let Some(after_bang) = rest.strip_prefix('!') {
+                let (raw, after) = after_bang
+                    .find(':')
+                    .map_or((after_bang, ""), |idx| after_bang.split_at(idx));
+                conversion = Some(match raw {
+                    "s" => Conversion::Str,
+                    "r" => Conversion::Repr,
+                    "a" => Conversion::Ascii,
+                    other => return Err(FieldError::InvalidConversion(format!("!{other}"))),
+                });
+                rest = after;
+            }
+
+            let spec = match rest.strip_prefix(':') {
+                None => None,
+                Some(spec) if spec.contains('%') => {
+                    chrono::format::StrftimeItems::new(spec)
+                        .parse()
+                        .map_err(|_| FieldError::InvalidStrftime(spec.to_string()))?;
+                    Some(FormatSpec::Strftime(spec))
+                }
+                Some(spec) => Some(FormatSpec::Standard(StandardSpec::parse(spec)?)),
+            };
+            Ok(Self {
+                name,
+                default,
+                conversion,
+                spec,
+            })
+        }
+
+        /// Format the `value` of the argument.
+        ///
+        /// # Errors
+        /// When the value cannot be formatted according to the format spec.
+        pub fn format(&self, value: &str) -> Result<String, ValueError> {
+            let value = match self.conversion {
+                None | Some(Conversion::Str) => value.to_string(),
+                Some(Conversion::Repr) => repr(value, false),
+                Some(Conversion::Ascii) => repr(value, true),
+            };
+            match &self.spec {
+                None => Ok(value),
+                Some(FormatSpec::Standard(spec)) => spec.format(&value),
+                Some(FormatSpec::Strftime(format)) => {
+                    let timestamp = chrono::DateTime::parse_from_rfc3339(&value)
+                        .map_err(|_| ValueError::NotATimestamp { value })?;
+                    Ok(timestamp.format(format).to_string())
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PythonFormatString(pub Vec<Value>);
 
@@ -322,17 +831,49 @@ impl AsRef<Vec<Value>> for PythonFormatString {
 #[error("missing argument {0:?}")]
 pub struct MissingArgumentError(String);
 
+/// Errors when formatting a [`PythonFormatString`].
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum FormatError {
+    #[error(transparent)]
+    MissingArgument(#[from] MissingArgumentError),
+    #[error(transparent)]
+    InvalidField(#[from] ParseError),
+    #[error("failed to format argument {argument:?}: {source}")]
+    InvalidValue {
+        argument: String,
+        #[source]
+        source: spec::ValueError,
+    },
+}
+
 impl PythonFormatString {
+    /// Parse a format string.
+    ///
+    /// # Errors
+    /// When the format string or the conversion or format spec of one of its fields is invalid.
     pub fn parse(value: &str) -> Result<Self, parser::ParseError> {
         let arguments = parser::parse_format_arguments(value)?;
+        for argument in &arguments {
+            if let parser::Value::Argument(field) = argument {
+                spec::Field::parse(field).map_err(|source| ParseError::InvalidField {
+                    field: (*field).to_string(),
+                    source,
+                })?;
+            }
+        }
         Ok(Self(arguments.into_iter().map(Into::into).collect()))
     }
 
-    pub fn format<K, V>(
-        &self,
-        values: &HashMap<K, V>,
-        strict: bool,
-    ) -> Result<String, MissingArgumentError>
+    /// Format the string using the argument `values`.
+    ///
+    /// Missing arguments use their default value if they have one, e.g. `{$BUILD_NUMBER|0}`.
+    /// Conversions and format specs of the fields are applied, e.g. `{patch:03d}`.
+    /// Format specs containing `%` format timestamps using `strftime`, e.g. `{now:%Y-%m-%d}`.
+    ///
+    /// # Errors
+    /// - When an argument without a default value is missing and `strict` is enabled.
+    /// - When a value cannot be formatted according to the format spec of its field.
+    pub fn format<K, V>(&self, values: &HashMap<K, V>, strict: bool) -> Result<String, FormatError>
     where
         K: std::borrow::Borrow<str>,
         K: std::hash::Hash + Eq,
@@ -341,24 +882,21 @@ impl PythonFormatString {
         self.0.iter().try_fold(String::new(), |mut acc, value| {
             let value = match value {
                 Value::Argument(arg) => {
-                    let as_timestamp = || {
-                        // try to parse as timestamp of format "utcnow:%Y-%m-%dT%H:%M:%SZ"
-                        arg.split_once(':').and_then(|(arg, format)| {
-                            values.get(arg).and_then(|value| {
-                                let timestamp =
-                                    chrono::DateTime::parse_from_rfc3339(value.as_ref()).ok()?;
-                                Some(timestamp.format(format).to_string())
-                            })
-                        })
-                    };
-                    let value = values
-                        .get(arg)
-                        .map(|value| value.as_ref().to_string())
-                        .or_else(as_timestamp);
-
-                    match value {
-                        Some(value) => Ok(value),
-                        None if strict => Err(MissingArgumentError(arg.to_string())),
+                    let field =
+                        spec::Field::parse(arg).map_err(|source| ParseError::InvalidField {
+                            field: arg.clone(),
+                            source,
+                        })?;
+                    match values.get(field.name).map(AsRef::as_ref).or(field.default) {
+                        Some(value) => {
+                            field
+                                .format(value)
+                                .map_err(|source| FormatError::InvalidValue {
+                                    argument: field.name.to_string(),
+                                    source,
+                                })
+                        }
+                        None if strict => Err(MissingArgumentError(field.name.to_string()).into()),
                         None => Ok(String::new()),
                     }
                 }
@@ -369,8 +907,12 @@ impl PythonFormatString {
         })
     }
 
+    /// The names of the arguments, without their default values, conversions, and format specs.
     pub fn named_arguments(&self) -> impl Iterator<Item = &str> {
-        self.0.iter().filter_map(|value| value.as_argument())
+        self.0
+            .iter()
+            .filter_map(|value| value.as_argument())
+            .map(|field| spec::Field::parse(field).map_or(field, |field| field.name))
     }
 
     pub fn iter(&self) -> std::slice::Iter<'_, Value> {
@@ -523,7 +1065,168 @@ mod tests {
                 .collect::<HashMap<&str, &str>>(),
                 strict
             ),
-            Err(super::MissingArgumentError("$value1".to_string())),
+            Err(super::MissingArgumentError("$value1".to_string()).into()),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_with_format_spec() -> eyre::Result<()> {
+        crate::tests::init();
+        let values: HashMap<&str, &str> = [
+            ("patch", "7"),
+            ("build", "42"),
+            ("negative", "-42"),
+            ("big", "1234567"),
+            ("label", "rc"),
+            ("quoted", "it's"),
+            ("unicode", "ä"),
+            ("now", "2025-03-01T12:30:00+00:00"),
+        ]
+        .into_iter()
+        .collect();
+        let cases = [
+            ("{patch:03d}", "007"),
+            ("{build:0>4}", "0042"),
+            ("{build:*^6}", "**42**"),
+            ("{build:<4}|", "42  |"),
+            ("{build:4}", "42  "),
+            ("{build:4d}", "  42"),
+            ("{build:+d}", "+42"),
+            ("{negative:05d}", "-0042"),
+            ("{negative:=+6d}", "-   42"),
+            ("{big:,d}", "1,234,567"),
+            ("{big:_x}", "12_d687"),
+            ("{build:#x}", "0x2a"),
+            ("{build:#X}", "0X2A"),
+            ("{build:#010b}", "0b00101010"),
+            ("{build:o}", "52"),
+            ("{label:.1}", "r"),
+            ("{label!s}", "rc"),
+            ("{label!r}", "'rc'"),
+            ("{quoted!r}", "\"it's\""),
+            ("{unicode!r}", "'ä'"),
+            ("{unicode!a}", "'\\xe4'"),
+            ("{label!r:>6}", "  'rc'"),
+            ("{now:%Y-%m-%d}", "2025-03-01"),
+            ("{now:%H:%M}", "12:30"),
+        ];
+        for (format_string, expected) in cases {
+            let fstring = PythonFormatString::parse(format_string)?;
+            sim_assert_eq!(
+                fstring.format(&values, true).as_deref(),
+                Ok(expected),
+                "{format_string}"
+            );
+        }
+
+        let fstring = PythonFormatString::parse("{label:03d}")?;
+        sim_assert_eq!(
+            fstring.format(&values, true),
+            Err(super::FormatError::InvalidValue {
+                argument: "label".to_string(),
+                source: super::spec::ValueError::NotAnInteger {
+                    value: "rc".to_string()
+                },
+            })
+        );
+        let fstring = PythonFormatString::parse("{missing:03d}")?;
+        sim_assert_eq!(
+            fstring.format(&values, true),
+            Err(super::MissingArgumentError("missing".to_string()).into())
+        );
+        sim_assert_eq!(fstring.format(&values, false).as_deref(), Ok(""));
+        Ok(())
+    }
+
+    #[test]
+    fn format_with_default_values() -> eyre::Result<()> {
+        crate::tests::init();
+        let values: HashMap<&str, &str> = [("major", "1"), ("minor", "2"), ("patch", "3")]
+            .into_iter()
+            .collect();
+        let cases = [
+            ("{major}.{minor}.{patch}.dev{$PR_NUMBER|0}", "1.2.3.dev0"),
+            ("{major}.{minor}.{patch}{distance_to_latest_tag|}", "1.2.3"),
+            ("{patch|0}", "3"),
+            ("{build|7:03d}", "007"),
+            ("{label|rc!r}", "'rc'"),
+            ("{url|a|b}", "a|b"),
+        ];
+        for (format_string, expected) in cases {
+            let fstring = PythonFormatString::parse(format_string)?;
+            sim_assert_eq!(
+                fstring.format(&values, true).as_deref(),
+                Ok(expected),
+                "{format_string}"
+            );
+        }
+
+        let fstring = PythonFormatString::parse("{build|x:d}")?;
+        sim_assert_eq!(
+            fstring.format(&values, true),
+            Err(super::FormatError::InvalidValue {
+                argument: "build".to_string(),
+                source: super::spec::ValueError::NotAnInteger {
+                    value: "x".to_string()
+                },
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_invalid_format_spec() {
+        use super::spec::FieldError;
+
+        crate::tests::init();
+        let cases = [
+            ("{patch:03q}", FieldError::UnsupportedType('q')),
+            ("{patch:.2f}", FieldError::UnsupportedType('f')),
+            ("{patch!x}", FieldError::InvalidConversion("!x".to_string())),
+            ("{patch:4.}", FieldError::InvalidSpec("4.".to_string())),
+            ("{patch:4d4}", FieldError::InvalidSpec("4d4".to_string())),
+            (
+                "{patch:+}",
+                FieldError::NotAllowed {
+                    option: "sign",
+                    format_type: 's',
+                },
+            ),
+            (
+                "{patch:.2d}",
+                FieldError::NotAllowed {
+                    option: "precision",
+                    format_type: 'd',
+                },
+            ),
+            (
+                "{now:%Y-%}",
+                FieldError::InvalidStrftime("%Y-%".to_string()),
+            ),
+        ];
+        for (format_string, expected) in cases {
+            let field = &format_string[1..format_string.len() - 1];
+            sim_assert_eq!(
+                PythonFormatString::parse(format_string),
+                Err(super::ParseError::InvalidField {
+                    field: field.to_string(),
+                    source: expected,
+                }),
+                "{format_string}"
+            );
+        }
+    }
+
+    #[test]
+    fn named_arguments_without_format_spec() -> eyre::Result<()> {
+        crate::tests::init();
+        let fstring = PythonFormatString::parse(
+            "{major}.{minor!s}.{patch:03d}-{now:%Y-%m-%dT%H:%M}.dev{$PR_NUMBER|0}",
+        )?;
+        sim_assert_eq!(
+            fstring.named_arguments().collect::<Vec<_>>(),
+            vec!["major", "minor", "patch", "now", "$PR_NUMBER"]
         );
         Ok(())
     }
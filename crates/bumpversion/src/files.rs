@@ -3,32 +3,15 @@
 //! Handles reading, modifying, and writing files based on configuration.
 use crate::{
     config::{self, FileChange, InputFile, VersionComponentConfigs},
+    data_file::{self, DataFormat},
     f_string::{self, PythonFormatString},
+    fs::FileSystem,
     version::{self, Version},
 };
 use indexmap::IndexMap;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-// /// Does the search pattern match any part of the contents?
-// fn contains_pattern(contents: &str, search_pattern: &regex::Regex) -> bool {
-//     let matches = search_pattern.captures_iter(contents);
-//     let Some(m) = matches.into_iter().next() else {
-//         return false;
-//     };
-//     let Some(m) = m.iter().next().flatten() else {
-//         return false;
-//     };
-//     let line_num = contents[..m.start()].chars().filter(|c| *c == '\n').count() + 1;
-//     tracing::info!(
-//         "found {:?} at line {}: {:?}",
-//         search_pattern.as_str(),
-//         line_num,
-//         m.as_str(),
-//     );
-//     true
-// }
-
 /// Errors that can occur when replacing version strings in files.
 #[derive(thiserror::Error, Debug)]
 pub enum ReplaceVersionError {
@@ -37,7 +20,7 @@ pub enum ReplaceVersionError {
     #[error(transparent)]
     Serialize(#[from] version::SerializeError),
     #[error(transparent)]
-    MissingArgument(#[from] f_string::MissingArgumentError),
+    Format(#[from] f_string::FormatError),
     #[error(transparent)]
     InvalidFormatString(#[from] f_string::ParseError),
 
@@ -45,14 +28,31 @@ pub enum ReplaceVersionError {
     RegexTemplate(#[from] config::regex::RegexTemplateError),
     #[error(transparent)]
     Toml(#[from] toml_edit::TomlError),
+    #[error(transparent)]
+    DataFile(#[from] data_file::Error),
+    #[error("did not find {search:?} in file {path:?}")]
+    VersionNotFound {
+        /// Path of the file that was searched.
+        path: PathBuf,
+        /// The search regex that did not match.
+        search: String,
+    },
 }
 
-/// Apply a list of `changes` to the input `before` content,
+/// Apply a list of `changes` to the input `before` content of the file at `path`,
 /// producing the modified text and a record of replacements.
 ///
+/// If the global `search` of a change does not match, the original `current_version`
+/// of the context is tried instead, since it represents the same version.
+///
+/// Changes with a `key_path` only replace the value at that key of TOML, JSON and YAML files.
+///
 /// # Errors
-/// Returns `ReplaceVersionError` if serialization, I/O, or formatting fails.
+/// Returns `ReplaceVersionError` if serialization, I/O, or formatting fails,
+/// or `ReplaceVersionError::VersionNotFound` if the `search` of a change does not match
+/// and missing versions are not ignored.
 pub fn replace_version<'a, K, V, S>(
+    path: &Path,
     before: String,
     changes: &'a [FileChange],
     current_version: &'a Version,
@@ -64,6 +64,7 @@ where
     V: AsRef<str> + std::fmt::Debug,
     S: std::hash::BuildHasher,
 {
+    let original_version: Option<&str> = ctx.get("current_version").map(AsRef::as_ref);
     let mut after = before.to_string();
     let mut replacements = vec![];
     for change in changes {
@@ -88,46 +89,65 @@ where
             ])
             .collect();
 
+        let data_file = change.key_path.as_deref().and_then(|key_path| {
+            let format = DataFormat::from_path(path);
+            if format.is_none() {
+                tracing::warn!(
+                    ?path,
+                    key_path,
+                    "key path is only supported for TOML, JSON and YAML files"
+                );
+            }
+            format.map(|format| (format, key_path))
+        });
+
         let search_pattern = &change.search;
-        let search_regex = search_pattern.format(&ctx, true)?;
+        let mut search_regex = search_pattern.format(&ctx, true)?;
 
         let replace_pattern = &change.replace;
         let replacement = PythonFormatString::parse(replace_pattern)?;
         let replacement = replacement.format(&ctx, true)?;
 
-        // TODO(roman): i don't think we need to check if the change pattern is present?
-        // // does the file contain the change pattern?
-        // let contains_change_pattern: bool = {
-        //     if contains_pattern(&before, &search_regex) {
-        //         return Ok(true);
-        //     }
-        //
-        //     // The `search` pattern did not match, but the original supplied
-        //     // version number (representing the same version component values) might
-        //     // match instead. This is probably the case if environment variables are used.
-        //     let file_uses_global_search_pattern = file_change.search == version_config.search;
-        //
-        //     let pattern = regex::RegexBuilder::new(regex::escape(version.original)).build()?;
-        //
-        //     if file_uses_global_search_pattern && contains_pattern(&before, &pattern) {
-        //         // The original version is present, and we're not looking for something
-        //         // more specific -> this is accepted as a match
-        //         return Ok(true);
-        //     }
-        //
-        //     Ok(false)
-        // }?;
-        //
-        // if !contains_change_pattern {
-        //     if file_change.ignore_missing_version {
-        //         tracing::warn!("did not find {:?} in file {path:?}", search_regex.as_str());
-        //     } else {
-        //         eyre::bail!("did not find {:?} in file {path:?}", search_regex.as_str());
-        //     }
-        //     return Ok(());
-        // }
-
-        after = search_regex.replace_all(&after, &replacement).to_string();
+        let mut replaced = replace_matches(&after, data_file, &search_regex, &replacement)?;
+        if replaced.is_none() && change.uses_global_search {
+            // The `search` pattern did not match, but the original version
+            // (representing the same version component values) might match instead.
+            // This is probably the case if the file serializes versions differently.
+            // A search configured for the file is more specific and must match as is.
+            let original_search_regex = original_version
+                .filter(|original| *original != current_version_serialized)
+                .map(|original| {
+                    let mut original_ctx = ctx.clone();
+                    original_ctx.insert("current_version", original);
+                    search_pattern.format(&original_ctx, true)
+                })
+                .transpose()?;
+            if let Some(original_search_regex) = original_search_regex {
+                replaced =
+                    replace_matches(&after, data_file, &original_search_regex, &replacement)?;
+                if replaced.is_some() {
+                    search_regex = original_search_regex;
+                }
+            }
+        }
+
+        match replaced {
+            Some(replaced) => after = replaced,
+            None if change.ignore_missing_version => {
+                tracing::info!(
+                    ?path,
+                    search = search_regex.as_str(),
+                    "did not find version"
+                );
+                continue;
+            }
+            None => {
+                return Err(ReplaceVersionError::VersionNotFound {
+                    path: path.to_path_buf(),
+                    search: search_regex.as_str().to_string(),
+                });
+            }
+        }
 
         replacements.push(Replacement {
             search_pattern: search_pattern.to_string(),
@@ -145,8 +165,32 @@ where
     Ok(modification)
 }
 
+/// Replace all matches of `search` in `contents`,
+/// or only in the value at the key path if the file is a data file.
+///
+/// Returns `None` if `search` does not match.
+fn replace_matches(
+    contents: &str,
+    data_file: Option<(DataFormat, &str)>,
+    search: &regex::Regex,
+    replacement: &str,
+) -> Result<Option<String>, ReplaceVersionError> {
+    match data_file {
+        Some((format, key_path)) => Ok(data_file::replace_value(
+            format,
+            contents,
+            key_path,
+            search,
+            replacement,
+        )?),
+        None => Ok(search
+            .is_match(contents)
+            .then(|| search.replace_all(contents, replacement).to_string())),
+    }
+}
+
 /// A single substitution made during version replacement.
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct Replacement {
     /// The regex string used to search for the existing version.
     pub search: String,
@@ -159,7 +203,7 @@ pub struct Replacement {
 }
 
 /// Represents the overall result of modifying a file.
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 pub struct Modification {
     /// Original file content before any replacements.
     pub before: String,
@@ -194,63 +238,172 @@ impl Modification {
     }
 }
 
-/// Read a file at `path`, apply version replacement, and write back if changed.
+/// Read a file at `path` and apply version replacement to its contents.
 ///
-/// Honors `dry_run` to skip writing. Returns `None` if file unchanged or missing (when allowed).
-pub async fn replace_version_in_file<K, V, S>(
+/// The file is not written, see [`FileTransaction`].
+/// Returns `None` if the file is missing (when allowed).
+pub async fn replace_version_in_file<FS, K, V, S>(
+    fs: &FS,
     path: &Path,
     changes: &[FileChange],
     current_version: &Version,
     new_version: &Version,
     ctx: &HashMap<K, V, S>,
-    dry_run: bool,
 ) -> Result<Option<Modification>, ReplaceVersionError>
 where
+    FS: FileSystem,
     K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
     V: AsRef<str> + std::fmt::Debug,
     S: std::hash::BuildHasher,
 {
-    let as_io_error = |source: std::io::Error| -> IoError { IoError::new(source, path) };
-    if !path.is_file() {
+    if !fs.is_file(path).await {
         if changes.iter().all(|change| change.ignore_missing_file) {
             tracing::info!(?path, "file not found");
             return Ok(None);
         }
         let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
-        return Err(ReplaceVersionError::from(as_io_error(not_found)));
+        return Err(ReplaceVersionError::from(IoError::new(not_found, path)));
     }
 
-    let before = tokio::fs::read_to_string(path).await.map_err(as_io_error)?;
-    let modification = replace_version(before, changes, current_version, new_version, ctx)?;
-
-    if modification.before == modification.after {
-        // tracing::warn!(?path, "no change after version replacement");
-        // TODO(roman): can we also not do this?
-        // && current_version.original {
-        // og_context = deepcopy(context)
-        // og_context["current_version"] = current_version.original
-        // search_for_og, _ = self.file_change.get_search_pattern(og_context)
-        // file_content_after = search_for_og.sub(replace_with, file_content_before)
-        // return Ok(());
-    }
+    let before = fs.read_to_string(path).await?;
+    let modification = replace_version(path, before, changes, current_version, new_version, ctx)?;
+    Ok(Some(modification))
+}
 
-    if !dry_run {
-        use tokio::io::AsyncWriteExt;
-        let file = tokio::fs::OpenOptions::new()
-            .write(true)
-            .create(false)
-            .truncate(true)
-            .open(path)
-            .await
-            .map_err(as_io_error)?;
-        let mut writer = tokio::io::BufWriter::new(file);
-        writer
-            .write_all(modification.after.as_bytes())
+/// Write `contents` to a temporary file next to the existing file at `path`.
+///
+/// The temporary file has the permissions of the file at `path` and is removed when dropped,
+/// unless it is persisted.
+async fn write_temp_file(path: &Path, contents: &str) -> Result<tempfile::TempPath, IoError> {
+    let as_io_error = |source: std::io::Error| -> IoError { IoError::new(source, path) };
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    // keep the permissions of an existing file
+    let permissions = match tokio::fs::metadata(path).await {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(as_io_error(err)),
+    };
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(".bumpversion-");
+    #[cfg(unix)]
+    if permissions.is_none() {
+        // created files get the default permissions instead of those of a temporary file
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(std::fs::Permissions::from_mode(0o666));
+    }
+    let temp_path = builder
+        .tempfile_in(dir)
+        .map_err(as_io_error)?
+        .into_temp_path();
+    let as_temp_io_error =
+        |source: std::io::Error| -> IoError { IoError::new(source, temp_path.to_path_buf()) };
+    tokio::fs::write(&temp_path, contents)
+        .await
+        .map_err(as_temp_io_error)?;
+    if let Some(permissions) = permissions {
+        tokio::fs::set_permissions(&temp_path, permissions)
             .await
-            .map_err(as_io_error)?;
-        writer.flush().await.map_err(as_io_error)?;
+            .map_err(as_temp_io_error)?;
+    }
+    Ok(temp_path)
+}
+
+/// A file staged by a [`FileTransaction`].
+#[derive(Debug)]
+struct StagedFile {
+    path: PathBuf,
+    temp_path: tempfile::TempPath,
+    /// The original contents, or `None` if the file is created.
+    original: Option<String>,
+}
+
+/// Writes multiple files all at once.
+///
+/// The new contents are first staged to temporary files next to their targets,
+/// which are only renamed into place after all files were staged successfully.
+#[derive(Debug, Default)]
+pub struct FileTransaction {
+    staged: Vec<StagedFile>,
+}
+
+impl FileTransaction {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stage `contents` to be written to the file at `path`.
+    ///
+    /// Missing files are created.
+    pub async fn stage(&mut self, path: &Path, contents: &str) -> Result<(), IoError> {
+        let original = match tokio::fs::read_to_string(path).await {
+            Ok(original) => Some(original),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(IoError::new(err, path)),
+        };
+        let temp_path = write_temp_file(path, contents).await?;
+        self.staged.push(StagedFile {
+            path: path.to_path_buf(),
+            temp_path,
+            original,
+        });
+        Ok(())
+    }
+
+    /// Rename all staged files into place.
+    ///
+    /// If a file cannot be renamed, the files written so far are restored.
+    pub async fn commit(self) -> Result<WrittenFiles, IoError> {
+        let mut written = WrittenFiles::default();
+        for StagedFile {
+            path,
+            temp_path,
+            original,
+        } in self.staged
+        {
+            tracing::info!(?path, "writing");
+            if let Err(err) = temp_path.persist(&path) {
+                written.rollback().await;
+                return Err(IoError::new(err.error, path));
+            }
+            written.originals.push((path, original));
+        }
+        Ok(written)
+    }
+}
+
+/// Files written by a [`FileTransaction`].
+#[derive(Debug, Default)]
+pub struct WrittenFiles {
+    originals: Vec<(PathBuf, Option<String>)>,
+}
+
+impl WrittenFiles {
+    /// Restore the original contents of the written files and remove the created files.
+    ///
+    /// Files that cannot be restored are reported and skipped.
+    pub async fn rollback(self) {
+        for (path, original) in self.originals.into_iter().rev() {
+            tracing::info!(?path, "restoring");
+            let restored = match original {
+                Some(original) => match write_temp_file(&path, &original).await {
+                    Ok(temp_path) => temp_path
+                        .persist(&path)
+                        .map_err(|err| IoError::new(err.error, &path)),
+                    Err(err) => Err(err),
+                },
+                None => tokio::fs::remove_file(&path)
+                    .await
+                    .map_err(|err| IoError::new(err, &path)),
+            };
+            if let Err(err) = restored {
+                tracing::error!("failed to restore {path:?}: {err}");
+            }
+        }
     }
-    Ok(Some(modification))
 }
 
 /// Errors encountered when resolving glob patterns to file paths.
@@ -334,6 +487,7 @@ pub fn resolve_files_from_config(
     parts: &VersionComponentConfigs,
     base_dir: Option<&Path>,
 ) -> Result<FileMap, Error> {
+    let global_search = &config.global.search;
     let files = config.files.drain(..);
     let new_files: Vec<_> = files
         .into_iter()
@@ -346,7 +500,10 @@ pub fn resolve_files_from_config(
                 InputFile::Path(path) => Ok(vec![path.clone()]),
             }?;
 
-            let file_change = FileChange::new(file_config, parts);
+            let file_change = FileChange {
+                uses_global_search: file_config.search == *global_search,
+                ..FileChange::new(file_config, parts)
+            };
             Ok(new_files
                 .into_iter()
                 .map(|file| {
@@ -413,3 +570,259 @@ pub fn files_to_modify(
         .into_iter()
         .filter(move |(file, _)| included_files.contains(file))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{FileChange, FinalizedFileConfig, RegexTemplate, VersionComponentConfigs};
+    use crate::f_string::PythonFormatString;
+    use crate::version::{Version, VersionSpec};
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    fn versions(current: &str, new: &str) -> eyre::Result<(Version, Version)> {
+        let components: VersionComponentConfigs = ["major", "minor", "patch"]
+            .into_iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    crate::config::VersionComponentSpec::default(),
+                )
+            })
+            .collect();
+        let spec = VersionSpec::from_components(components);
+        let parse_regex = &FinalizedFileConfig::default().parse_version_pattern;
+        let parse = |version: &str| {
+            Version::parse(version, parse_regex, &spec)
+                .ok_or_else(|| eyre::eyre!("failed to parse {version:?}"))
+        };
+        Ok((parse(current)?, parse(new)?))
+    }
+
+    fn file_change(ignore_missing_version: bool) -> FileChange {
+        let file_config = FinalizedFileConfig {
+            ignore_missing_version,
+            ..FinalizedFileConfig::default()
+        };
+        FileChange::new(file_config, &VersionComponentConfigs::default())
+    }
+
+    #[test]
+    fn test_replace_version() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let (current_version, new_version) = versions("1.2.3", "1.3.0")?;
+        let ctx: HashMap<&str, &str> = [("current_version", "1.2.3")].into_iter().collect();
+        let modification = super::replace_version(
+            Path::new("VERSION"),
+            "version = 1.2.3\n".to_string(),
+            &[file_change(false)],
+            &current_version,
+            &new_version,
+            &ctx,
+        )?;
+        sim_assert_eq!(modification.after, "version = 1.3.0\n");
+        sim_assert_eq!(modification.replacements.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_version_not_found() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let (current_version, new_version) = versions("1.2.3", "1.3.0")?;
+        let ctx: HashMap<&str, &str> = [("current_version", "1.2.3")].into_iter().collect();
+        let err = super::replace_version(
+            Path::new("VERSION"),
+            "version = 1.0.0\n".to_string(),
+            &[file_change(false)],
+            &current_version,
+            &new_version,
+            &ctx,
+        )
+        .unwrap_err();
+        sim_assert_eq!(
+            err.to_string(),
+            r#"did not find "1\\.2\\.3" in file "VERSION""#
+        );
+
+        // missing versions can be ignored
+        let modification = super::replace_version(
+            Path::new("VERSION"),
+            "version = 1.0.0\n".to_string(),
+            &[file_change(true)],
+            &current_version,
+            &new_version,
+            &ctx,
+        )?;
+        sim_assert_eq!(modification.after, modification.before);
+        assert!(modification.replacements.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_original_version() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let (current_version, new_version) = versions("1.2.0", "1.3.0")?;
+        // the configured version is serialized differently than in the file
+        let ctx: HashMap<&str, &str> = [("current_version", "1.2")].into_iter().collect();
+        let modification = super::replace_version(
+            Path::new("VERSION"),
+            "version = 1.2\n".to_string(),
+            &[file_change(false)],
+            &current_version,
+            &new_version,
+            &ctx,
+        )?;
+        sim_assert_eq!(modification.after, "version = 1.3.0\n");
+
+        // a search configured for the file must match the current version
+        let change = FileChange {
+            search: RegexTemplate::Escaped(PythonFormatString::parse(
+                "version = {current_version}",
+            )?),
+            uses_global_search: false,
+            ..file_change(false)
+        };
+        let err = super::replace_version(
+            Path::new("VERSION"),
+            "version = 1.2\n".to_string(),
+            &[change],
+            &current_version,
+            &new_version,
+            &ctx,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, super::ReplaceVersionError::VersionNotFound { .. }),
+            "unexpected error: {err:?}"
+        );
+        sim_assert_eq!(
+            err.to_string(),
+            r#"did not find "version = 1\\.2\\.0" in file "VERSION""#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_version_of_key_path() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let (current_version, new_version) = versions("1.2.3", "1.3.0")?;
+        let ctx: HashMap<&str, &str> = [("current_version", "1.2.3")].into_iter().collect();
+        let change = FileChange {
+            key_path: Some("version".to_string()),
+            ..file_change(false)
+        };
+        let package_json = indoc::indoc! {r#"
+            {
+              "name": "my-package",
+              "version": "1.2.3",
+              "dependencies": { "other-package": "1.2.3" }
+            }
+        "#};
+        let modification = super::replace_version(
+            Path::new("package.json"),
+            package_json.to_string(),
+            std::slice::from_ref(&change),
+            &current_version,
+            &new_version,
+            &ctx,
+        )?;
+        sim_assert_eq!(
+            modification.after,
+            package_json.replacen(r#""version": "1.2.3""#, r#""version": "1.3.0""#, 1)
+        );
+
+        // the key path is ignored for files that are not data files
+        let modification = super::replace_version(
+            Path::new("VERSION"),
+            "1.2.3 1.2.3\n".to_string(),
+            &[change],
+            &current_version,
+            &new_version,
+            &ctx,
+        )?;
+        sim_assert_eq!(modification.after, "1.3.0 1.3.0\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_file_transaction() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        let (first, second) = (dir.path().join("first.txt"), dir.path().join("second.txt"));
+        tokio::fs::write(&first, "1.2.3").await?;
+        tokio::fs::write(&second, "1.2.3").await?;
+
+        let mut transaction = super::FileTransaction::new();
+        transaction.stage(&first, "1.3.0").await?;
+        transaction.stage(&second, "1.3.0").await?;
+        // staged files are not written yet
+        sim_assert_eq!(tokio::fs::read_to_string(&first).await?, "1.2.3");
+
+        let written = transaction.commit().await?;
+        sim_assert_eq!(tokio::fs::read_to_string(&first).await?, "1.3.0");
+        sim_assert_eq!(tokio::fs::read_to_string(&second).await?, "1.3.0");
+
+        written.rollback().await;
+        sim_assert_eq!(tokio::fs::read_to_string(&first).await?, "1.2.3");
+        sim_assert_eq!(tokio::fs::read_to_string(&second).await?, "1.2.3");
+
+        // no temporary files are left behind
+        let mut entries = tokio::fs::read_dir(dir.path()).await?;
+        let mut num_entries = 0;
+        while entries.next_entry().await?.is_some() {
+            num_entries += 1;
+        }
+        sim_assert_eq!(num_entries, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_file_transaction_missing_file() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        let first = dir.path().join("first.txt");
+        tokio::fs::write(&first, "1.2.3").await?;
+
+        let mut transaction = super::FileTransaction::new();
+        transaction.stage(&first, "1.3.0").await?;
+        assert!(
+            transaction
+                .stage(&dir.path().join("missing/missing.txt"), "1.3.0")
+                .await
+                .is_err()
+        );
+        drop(transaction);
+        sim_assert_eq!(tokio::fs::read_to_string(&first).await?, "1.2.3");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_file_transaction_new_file() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        let changelog = dir.path().join("CHANGELOG.md");
+
+        let mut transaction = super::FileTransaction::new();
+        transaction.stage(&changelog, "## 1.3.0\n").await?;
+        let written = transaction.commit().await?;
+        sim_assert_eq!(tokio::fs::read_to_string(&changelog).await?, "## 1.3.0\n");
+
+        // created files get the same permissions as any other new file
+        let other = dir.path().join("other");
+        tokio::fs::write(&other, "").await?;
+        sim_assert_eq!(
+            tokio::fs::metadata(&changelog).await?.permissions(),
+            tokio::fs::metadata(&other).await?.permissions()
+        );
+
+        // created files are removed
+        written.rollback().await;
+        assert!(!changelog.exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+//! Filesystem abstraction used to read and write the files of a bump.
+//!
+//! [`LocalFileSystem`] accesses the local filesystem, while [`MemoryFileSystem`]
+//! keeps all files in memory and records every write, which is useful for testing.
+use crate::files::{FileTransaction, IoError};
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// Abstract interface for reading and writing files.
+pub trait FileSystem {
+    /// Returns `true` if `path` points to an existing file.
+    fn is_file(&self, path: &Path) -> impl Future<Output = bool>;
+
+    /// Read the contents of the file at `path`.
+    fn read_to_string(&self, path: &Path) -> impl Future<Output = Result<String, IoError>>;
+
+    /// Write the contents of multiple `files` all at once.
+    ///
+    /// Missing files are created.
+    /// Either all files are written, or none of them.
+    fn write_files(&self, files: &[(&Path, &str)]) -> impl Future<Output = Result<(), IoError>>;
+
+    /// Remove the `files`.
+    fn remove_files(&self, files: &[&Path]) -> impl Future<Output = Result<(), IoError>>;
+}
+
+/// The local filesystem.
+///
+/// Files are written using a [`FileTransaction`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LocalFileSystem;
+
+impl FileSystem for LocalFileSystem {
+    async fn is_file(&self, path: &Path) -> bool {
+        tokio::fs::metadata(path)
+            .await
+            .is_ok_and(|metadata| metadata.is_file())
+    }
+
+    async fn read_to_string(&self, path: &Path) -> Result<String, IoError> {
+        tokio::fs::read_to_string(path)
+            .await
+            .map_err(|source| IoError::new(source, path))
+    }
+
+    async fn write_files(&self, files: &[(&Path, &str)]) -> Result<(), IoError> {
+        let mut transaction = FileTransaction::new();
+        for (path, contents) in files {
+            transaction.stage(path, contents).await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn remove_files(&self, files: &[&Path]) -> Result<(), IoError> {
+        for path in files {
+            tokio::fs::remove_file(path)
+                .await
+                .map_err(|source| IoError::new(source, path))?;
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory filesystem that records all writes.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    files: Mutex<BTreeMap<PathBuf, String>>,
+    writes: Mutex<Vec<(PathBuf, String)>>,
+}
+
+impl MemoryFileSystem {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create or overwrite the file at `path` without recording a write.
+    pub fn insert(&self, path: impl Into<PathBuf>, contents: impl Into<String>) {
+        self.files.lock().insert(path.into(), contents.into());
+    }
+
+    /// Returns the current contents of the file at `path`.
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<String> {
+        self.files.lock().get(path).cloned()
+    }
+
+    /// Returns all files and their current contents, sorted by path.
+    #[must_use]
+    pub fn files(&self) -> BTreeMap<PathBuf, String> {
+        self.files.lock().clone()
+    }
+
+    /// Returns all writes in the order they happened.
+    #[must_use]
+    pub fn writes(&self) -> Vec<(PathBuf, String)> {
+        self.writes.lock().clone()
+    }
+}
+
+impl FromIterator<(PathBuf, String)> for MemoryFileSystem {
+    fn from_iter<I: IntoIterator<Item = (PathBuf, String)>>(iter: I) -> Self {
+        Self {
+            files: Mutex::new(iter.into_iter().collect()),
+            writes: Mutex::default(),
+        }
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    async fn is_file(&self, path: &Path) -> bool {
+        self.files.lock().contains_key(path)
+    }
+
+    async fn read_to_string(&self, path: &Path) -> Result<String, IoError> {
+        self.get(path).ok_or_else(|| {
+            let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+            IoError::new(not_found, path)
+        })
+    }
+
+    async fn write_files(&self, files: &[(&Path, &str)]) -> Result<(), IoError> {
+        let mut stored = self.files.lock();
+        let mut writes = self.writes.lock();
+        for (path, contents) in files {
+            stored.insert(path.to_path_buf(), (*contents).to_string());
+            writes.push((path.to_path_buf(), (*contents).to_string()));
+        }
+        Ok(())
+    }
+
+    async fn remove_files(&self, files: &[&Path]) -> Result<(), IoError> {
+        let mut stored = self.files.lock();
+        if let Some(missing) = files.iter().find(|path| !stored.contains_key(**path)) {
+            let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+            return Err(IoError::new(not_found, *missing));
+        }
+        for path in files {
+            stored.remove(*path);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileSystem, LocalFileSystem, MemoryFileSystem};
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::{Path, PathBuf};
+
+    #[tokio::test]
+    async fn test_memory_file_system() -> eyre::Result<()> {
+        crate::tests::init();
+        let fs: MemoryFileSystem = [(PathBuf::from("/repo/VERSION"), "1.2.3".to_string())]
+            .into_iter()
+            .collect();
+        let version_file = Path::new("/repo/VERSION");
+        assert!(fs.is_file(version_file).await);
+        assert!(!fs.is_file(Path::new("/repo")).await);
+        sim_assert_eq!(fs.read_to_string(version_file).await?, "1.2.3");
+
+        // missing files are created
+        let changelog = Path::new("/repo/CHANGELOG.md");
+        fs.write_files(&[(version_file, "1.3.0"), (changelog, "## 1.3.0")])
+            .await?;
+        sim_assert_eq!(fs.read_to_string(version_file).await?, "1.3.0");
+        sim_assert_eq!(fs.read_to_string(changelog).await?, "## 1.3.0");
+        sim_assert_eq!(
+            fs.writes(),
+            vec![
+                (version_file.to_path_buf(), "1.3.0".to_string()),
+                (changelog.to_path_buf(), "## 1.3.0".to_string()),
+            ]
+        );
+
+        fs.remove_files(&[changelog]).await?;
+        assert!(!fs.is_file(changelog).await);
+        let err = fs.remove_files(&[changelog]).await.unwrap_err();
+        sim_assert_eq!(err.path.as_deref(), Some(changelog));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_file_system() -> eyre::Result<()> {
+        crate::tests::init();
+        let dir = tempfile::tempdir()?;
+        let version_file = dir.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3").await?;
+
+        let fs = LocalFileSystem;
+        assert!(fs.is_file(&version_file).await);
+        assert!(!fs.is_file(dir.path()).await);
+        fs.write_files(&[(&version_file, "1.3.0")]).await?;
+        sim_assert_eq!(fs.read_to_string(&version_file).await?, "1.3.0");
+        Ok(())
+    }
+}
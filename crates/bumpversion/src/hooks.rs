@@ -3,7 +3,7 @@
 //! Runs user-defined shell commands with enriched environment variables.
 use crate::{
     command::{self, Error as CommandError, Output},
-    logging::LogExt,
+    context,
     vcs::{RevisionInfo, TagAndRevision},
     version::Version,
 };
@@ -31,7 +31,7 @@ fn base_env() -> impl Iterator<Item = (String, String)> {
 
 /// Provide the VCS environment variables.
 fn vcs_env(tag_and_revision: &TagAndRevision) -> impl Iterator<Item = (String, String)> {
-    let TagAndRevision { tag, revision } = tag_and_revision;
+    let TagAndRevision { tag, revision, .. } = tag_and_revision;
     let tag = tag.clone().unwrap_or(crate::vcs::TagInfo {
         dirty: false,
         commit_sha: String::new(),
@@ -124,36 +124,65 @@ fn pre_and_post_commit_hook_env<'a>(
         .chain(new_version_env(new_version_serialized, tag))
 }
 
-impl<VCS, L> crate::BumpVersion<VCS, L>
+impl<VCS, L, FS> crate::BumpVersion<VCS, L, FS>
 where
     VCS: crate::vcs::VersionControlSystem,
     L: crate::logging::Log,
 {
-    /// Run the setup hooks
+    /// Provide the environment variables for the user-defined context variables.
+    fn context_env(
+        &self,
+        current_version: Option<&Version>,
+        new_version: Option<&Version>,
+        new_version_serialized: Option<&str>,
+    ) -> Result<impl Iterator<Item = (String, String)> + use<VCS, L, FS>, Error> {
+        let mut ctx: HashMap<String, String> = context::get_context(
+            Some(&self.tag_and_revision),
+            current_version,
+            new_version,
+            self.config.global.current_version.as_deref(),
+            new_version_serialized,
+        )
+        .collect();
+        context::render_variables(&self.config.context, &mut ctx)?;
+        let env: Vec<_> = self
+            .config
+            .context
+            .keys()
+            .filter_map(|name| {
+                let value = ctx.remove(name)?;
+                Some((format!("{ENV_PREFIX}{}", name.to_uppercase()), value))
+            })
+            .collect();
+        Ok(env.into_iter())
+    }
+
+    /// Run the setup `hooks`
     ///
     /// # Errors
     /// When one of the user-provided setup hooks exits with a non-zero exit code.
-    pub async fn run_setup_hooks(&self, current_version: Option<&Version>) -> Result<(), Error> {
-        let env = setup_hook_env(&self.tag_and_revision, current_version);
-
-        let setup_hooks = &self.config.global.setup_hooks;
-        self.logger.log_hooks("setup", setup_hooks);
-
-        run_hooks(
-            setup_hooks,
-            self.repo.path(),
-            env,
-            self.config.global.dry_run,
-        )
-        .await
+    /// When the user-defined context variables cannot be rendered.
+    pub async fn run_setup_hooks(
+        &self,
+        hooks: &[String],
+        current_version: Option<&Version>,
+    ) -> Result<(), Error> {
+        let env = setup_hook_env(&self.tag_and_revision, current_version).chain(self.context_env(
+            current_version,
+            None,
+            None,
+        )?);
+        run_hooks(hooks, self.repo.path(), env).await
     }
 
-    /// Run the pre-commit hooks
+    /// Run the pre-commit `hooks`
     ///
     /// # Errors
     /// When one of the user-provided pre-commit hooks exits with a non-zero exit code.
+    /// When the user-defined context variables cannot be rendered.
     pub async fn run_pre_commit_hooks(
         &self,
+        hooks: &[String],
         current_version: Option<&Version>,
         new_version: Option<&Version>,
         new_version_serialized: &str,
@@ -163,26 +192,23 @@ where
             current_version,
             new_version,
             new_version_serialized,
-        );
-
-        let pre_commit_hooks = &self.config.global.pre_commit_hooks;
-        self.logger.log_hooks("pre-commit", pre_commit_hooks);
-
-        run_hooks(
-            pre_commit_hooks,
-            self.repo.path(),
-            env,
-            self.config.global.dry_run,
         )
-        .await
+        .chain(self.context_env(
+            current_version,
+            new_version,
+            Some(new_version_serialized),
+        )?);
+        run_hooks(hooks, self.repo.path(), env).await
     }
 
-    /// Run the post-commit hooks
+    /// Run the post-commit `hooks`
     ///
     /// # Errors
     /// When one of the user-provided post-commit hooks exits with a non-zero exit code.
+    /// When the user-defined context variables cannot be rendered.
     pub async fn run_post_commit_hooks(
         &self,
+        hooks: &[String],
         current_version: Option<&Version>,
         new_version: Option<&Version>,
         new_version_serialized: &str,
@@ -192,18 +218,13 @@ where
             current_version,
             new_version,
             new_version_serialized,
-        );
-
-        let post_commit_hooks = &self.config.global.post_commit_hooks;
-        self.logger.log_hooks("post-commit", post_commit_hooks);
-
-        run_hooks(
-            post_commit_hooks,
-            self.repo.path(),
-            env,
-            self.config.global.dry_run,
         )
-        .await
+        .chain(self.context_env(
+            current_version,
+            new_version,
+            Some(new_version_serialized),
+        )?);
+        run_hooks(hooks, self.repo.path(), env).await
     }
 }
 
@@ -216,6 +237,9 @@ pub enum Error {
     /// Failed to parse the hook script into shell tokens.
     #[error("failed to split shell script {0:?}")]
     Shell(String),
+    /// Failed to render the user-defined context variables.
+    #[error(transparent)]
+    Context(#[from] context::Error),
 }
 
 /// Runs command-line programs using the shell
@@ -238,14 +262,9 @@ async fn run_hooks(
     hooks: &[String],
     working_dir: &Path,
     env: impl Iterator<Item = (String, String)>,
-    dry_run: bool,
 ) -> Result<(), Error> {
     let env = env.collect();
     for script in hooks {
-        if dry_run {
-            tracing::info!(?script, "would run hook");
-            continue;
-        }
         tracing::info!(?script, "running");
         match run_hook(script, working_dir, &env).await {
             Ok(output) => {
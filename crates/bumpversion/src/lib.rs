@@ -10,7 +10,7 @@
 //!   BumpVersion,
 //!   Bump,
 //!   diagnostics::Printer,
-//!   vcs::{TagAndRevision, VersionControlSystem, git::GitRepository},
+//!   vcs::{VersionControlSystem, git::GitRepository},
 //!   logging,
 //!   config,
 //! };
@@ -25,6 +25,7 @@
 //! let (config_file, mut config) = bumpversion::find_config(
 //!   &repo_path,
 //!   &Default::default(),
+//!   false,
 //!   &printer,
 //! ).await?.unwrap();
 //!
@@ -39,7 +40,7 @@
 //!   Some(repo.path()),
 //! )?;
 //!
-//! let TagAndRevision { tag, revision } = repo.latest_tag_and_revision(
+//! let tag_and_revision = repo.latest_tag_and_revision(
 //!   &config.global.tag_name,
 //!   &config.global.parse_version_pattern,
 //! )
@@ -48,50 +49,75 @@
 //! let logger = logging::TracingLogger::new(logging::Verbosity::High);
 //! let manager = BumpVersion {
 //!   repo,
+//!   fs: bumpversion::fs::LocalFileSystem,
 //!   config,
 //!   logger,
-//!   tag_and_revision: TagAndRevision { tag, revision },
+//!   tag_and_revision,
 //!   file_map,
 //!   components,
 //!   config_file: Some(config_file),
 //! };
 //! manager.bump(Bump::Component("patch")).await?;
+//!
+//! // alternatively, inspect the plan before applying it
+//! manager.setup().await?;
+//! let plan = manager.plan(Bump::Component("patch")).await?;
+//! for (path, modification) in plan.modifications() {
+//!   println!("{}", modification.diff(Some(path)).unwrap_or_default());
+//! }
+//! manager.apply(&plan).await?;
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Testing
+//!
+//! To test a release flow without a repository or touching any files, use a
+//! [`vcs::memory::MemoryRepository`] and a [`fs::MemoryFileSystem`] for the `repo` and `fs`.
+//! Both record every change, so tests can assert which files were written
+//! and which commits and tags were made.
+//! Configure the files using absolute paths, as relative paths are resolved on the local filesystem.
 #![forbid(unsafe_code)]
 #![allow(clippy::missing_errors_doc)]
 // #![warn(missing_docs)]
 
+pub mod changelog;
+pub mod check;
 pub mod command;
 pub mod config;
 pub mod context;
+pub mod conventional;
+pub mod data_file;
 pub mod diagnostics;
 pub mod f_string;
 pub mod files;
+pub mod fs;
 pub mod hooks;
 pub mod logging;
+pub mod plan;
+pub mod show;
 pub mod vcs;
 pub mod version;
 
+pub use plan::BumpPlan;
+
 use crate::{
     files::FileMap,
+    fs::{FileSystem, LocalFileSystem},
     vcs::{TagAndRevision, VersionControlSystem},
 };
-use colored::{Color, Colorize};
 use files::IoError;
 use futures::stream::{StreamExt, TryStreamExt};
 use indexmap::IndexMap;
-use logging::{LogExt, Verbosity};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 
 /// Specifies which version bump to perform.
 ///
 /// Variants:
 /// - `Component(name)`: increment the named component (e.g., "major", "minor", "patch").
 /// - `NewVersion(version)`: set the version to the given value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Bump<'a> {
     /// Increment the named version component (e.g., "major", "minor", "patch").
     Component(&'a str),
@@ -99,19 +125,166 @@ pub enum Bump<'a> {
     NewVersion(&'a str),
 }
 
+/// A parsed configuration file, its source file id, and the diagnostics emitted while parsing it.
+type ParsedConfigFile = (
+    config::ConfigFile,
+    config::Config,
+    diagnostics::FileId,
+    Vec<codespan_reporting::diagnostic::Diagnostic<diagnostics::FileId>>,
+);
+
+/// Read and parse a config file.
+///
+/// Unknown keys are reported as warnings, or as errors if `strict`.
+/// Returns `None` if the config file does not contain a bumpversion configuration.
+async fn parse_config_file<W>(
+    config_file: config::ConfigFile,
+    strict: bool,
+    printer: &diagnostics::Printer<W>,
+) -> Result<Option<ParsedConfigFile>, config::Error>
+where
+    W: codespan_reporting::term::termcolor::WriteColor + Send + Sync + 'static,
+{
+    use diagnostics::ToDiagnostics;
+
+    let path = config_file.path();
+    let config = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|source| IoError::new(source, path))
+        .map_err(config::Error::from)?;
+
+    let file_id = printer.add_source_file(path, config.to_string());
+
+    let parse_config_task = tokio::task::spawn_blocking(move || {
+        let mut diagnostics = vec![];
+
+        let config_res = match &config_file {
+            config::ConfigFile::BumpversionToml(path) | config::ConfigFile::PyProject(path) => {
+                let res =
+                    config::Config::from_pyproject_toml(&config, file_id, strict, &mut diagnostics);
+                if let Err(ref err) = res {
+                    diagnostics.extend(err.to_diagnostics(file_id));
+                }
+                res.map_err(|source| config::Error::Toml {
+                    source,
+                    path: path.clone(),
+                })
+            }
+            config::ConfigFile::BumpversionCfg(path) => {
+                let options = config::ini::Options::default();
+                let res =
+                    config::Config::from_ini(&config, options, file_id, strict, &mut diagnostics);
+                if let Err(ref err) = res {
+                    diagnostics.extend(err.to_diagnostics(file_id));
+                }
+                res.map_err(|source| config::Error::Ini {
+                    source,
+                    path: path.clone(),
+                })
+            }
+            config::ConfigFile::SetupCfg(path) => {
+                let options = config::ini::Options::default();
+                let res = config::Config::from_setup_cfg_ini(
+                    &config,
+                    options,
+                    file_id,
+                    strict,
+                    &mut diagnostics,
+                );
+                if let Err(ref err) = res {
+                    diagnostics.extend(err.to_diagnostics(file_id));
+                }
+                res.map_err(|source| config::Error::Ini {
+                    source,
+                    path: path.clone(),
+                })
+            }
+            config::ConfigFile::CargoToml(path) => {
+                let res =
+                    config::Config::from_cargo_toml(&config, file_id, strict, &mut diagnostics);
+                if let Err(ref err) = res {
+                    diagnostics.extend(err.to_diagnostics(file_id));
+                }
+                res.map_err(|source| config::Error::CargoToml {
+                    source,
+                    path: path.clone(),
+                })
+            }
+        };
+
+        config_res.map(|c| c.map(|c| (config_file.clone(), c, file_id, diagnostics)))
+    });
+
+    parse_config_task.await?
+}
+
+/// Emit the diagnostics of a parsed config file and finalize it with the `config_overrides`.
+///
+/// Fails if any of the diagnostics is an error.
+/// The templates of the finalized config are checked for unknown arguments.
+fn finalize_config_file<W>(
+    (config_file, mut config, file_id, diagnostics): ParsedConfigFile,
+    config_overrides: &config::GlobalConfig,
+    printer: &diagnostics::Printer<W>,
+) -> Result<(config::ConfigFile, config::FinalizedConfig), config::Error>
+where
+    W: codespan_reporting::term::termcolor::WriteColor + Send + Sync + 'static,
+{
+    use crate::config::MergeWith;
+
+    // emit diagnostics
+    for diagnostic in &diagnostics {
+        printer.emit(diagnostic).map_err(diagnostics::Error::from)?;
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity >= codespan_reporting::diagnostic::Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(config::Error::InvalidConfig {
+            path: config_file.path().to_path_buf(),
+            count: errors,
+        });
+    }
+
+    let mut template_spans = std::mem::take(&mut config.template_spans);
+    template_spans.remove_overridden(config_overrides);
+
+    let mut global_config = config_overrides.clone();
+    global_config.merge_with(&config.global);
+    config.global = global_config;
+    let config = config.finalize();
+
+    let diagnostics = config::templates::validate(&config, &template_spans, file_id);
+    for diagnostic in &diagnostics {
+        printer.emit(diagnostic).map_err(diagnostics::Error::from)?;
+    }
+    if !diagnostics.is_empty() {
+        return Err(config::Error::InvalidTemplates {
+            path: config_file.path().to_path_buf(),
+            count: diagnostics.len(),
+        });
+    }
+
+    Ok((config_file, config))
+}
+
 /// Find config file in one of the default config file locations.
 ///
+/// Unknown keys of the config file are reported as warnings, or as errors if `strict`.
+///
 /// # Errors
-/// When the config file cannot be read or parsed.
+/// - When the config file cannot be read or parsed.
+/// - When the config file has errors, such as unknown keys if `strict`.
 pub async fn find_config<W>(
     dir: &Path,
     config_overrides: &config::GlobalConfig,
+    strict: bool,
     printer: &diagnostics::Printer<W>,
 ) -> Result<Option<(config::ConfigFile, config::FinalizedConfig)>, config::Error>
 where
     W: codespan_reporting::term::termcolor::WriteColor + Send + Sync + 'static,
 {
-    use diagnostics::ToDiagnostics;
     let config_files = config::config_file_locations(dir);
 
     let config_files = futures::stream::iter(config_files)
@@ -123,78 +296,8 @@ where
             let Ok(path) = path.canonicalize() else {
                 return Ok(None);
             };
-            let config = tokio::fs::read_to_string(&path)
-                .await
-                .map_err(|source| IoError::new(source, &path))
-                .map_err(config::Error::from)?;
-
-            let file_id = printer.add_source_file(&path, config.to_string());
-
-            let parse_config_task = tokio::task::spawn_blocking(move || {
-                let mut diagnostics = vec![];
-                let strict = true;
-
-                let config_res = match &config_file {
-                    config::ConfigFile::BumpversionToml(path)
-                    | config::ConfigFile::PyProject(path) => {
-                        let res = config::Config::from_pyproject_toml(
-                            &config,
-                            file_id,
-                            strict,
-                            &mut diagnostics,
-                        );
-                        if let Err(ref err) = res {
-                            diagnostics.extend(err.to_diagnostics(file_id));
-                        }
-                        res.map_err(|source| config::Error::Toml {
-                            source,
-                            path: path.clone(),
-                        })
-                    }
-                    config::ConfigFile::BumpversionCfg(path) => {
-                        let options = config::ini::Options::default();
-                        let res = config::Config::from_ini(
-                            &config,
-                            options,
-                            file_id,
-                            strict,
-                            &mut diagnostics,
-                        );
-                        if let Err(ref err) = res {
-                            diagnostics.extend(err.to_diagnostics(file_id));
-                        }
-                        res.map_err(|source| config::Error::Ini {
-                            source,
-                            path: path.clone(),
-                        })
-                    }
-                    config::ConfigFile::SetupCfg(path) => {
-                        let options = config::ini::Options::default();
-                        let res = config::Config::from_setup_cfg_ini(
-                            &config,
-                            options,
-                            file_id,
-                            strict,
-                            &mut diagnostics,
-                        );
-                        if let Err(ref err) = res {
-                            diagnostics.extend(err.to_diagnostics(file_id));
-                        }
-                        res.map_err(|source| config::Error::Ini {
-                            source,
-                            path: path.clone(),
-                        })
-                    }
-                    config::ConfigFile::CargoToml(_) => {
-                        todo!("cargo");
-                        // Ok(None)
-                    }
-                };
-
-                config_res.map(|c| c.map(|c| (config_file.clone(), c, diagnostics)))
-            });
-
-            parse_config_task.await?
+            let config_file = config::ConfigFile::new(config_file.format(), path);
+            parse_config_file(config_file, strict, printer).await
         })
         .filter_map(|res| async move { res.transpose() });
 
@@ -204,23 +307,46 @@ where
         .next()
         .await
         .transpose()?
-        .map(|(config_file, mut config, diagnostics)| {
-            use crate::config::MergeWith;
-
-            // emit diagnostics
-            for diagnostic in &diagnostics {
-                printer.emit(diagnostic).map_err(diagnostics::Error::from)?;
-            }
-
-            let mut global_config = config_overrides.clone();
-            global_config.merge_with(&config.global);
-            config.global = global_config;
-
-            Ok::<_, config::Error>((config_file, config.finalize()))
-        })
+        .map(|parsed| finalize_config_file(parsed, config_overrides, printer))
         .transpose()
 }
 
+/// Load the config file at an explicit `path`.
+///
+/// The format of the config file is inferred from its file name or extension,
+/// unless an explicit `format` is given.
+/// Unknown keys of the config file are reported as warnings, or as errors if `strict`.
+///
+/// # Errors
+/// - When the format of the config file cannot be inferred.
+/// - When the config file cannot be read or parsed.
+/// - When the config file does not contain a bumpversion configuration.
+/// - When the config file has errors, such as unknown keys if `strict`.
+pub async fn load_config<W>(
+    path: &Path,
+    format: Option<config::ConfigFormat>,
+    config_overrides: &config::GlobalConfig,
+    strict: bool,
+    printer: &diagnostics::Printer<W>,
+) -> Result<(config::ConfigFile, config::FinalizedConfig), config::Error>
+where
+    W: codespan_reporting::term::termcolor::WriteColor + Send + Sync + 'static,
+{
+    let path = path
+        .canonicalize()
+        .map_err(|source| IoError::new(source, path))?;
+    let format = format
+        .or_else(|| config::ConfigFormat::infer(&path))
+        .ok_or_else(|| config::Error::UnknownFormat { path: path.clone() })?;
+    let config_file = config::ConfigFile::new(format, path);
+    let parsed = parse_config_file(config_file.clone(), strict, printer)
+        .await?
+        .ok_or_else(|| config::Error::MissingConfig {
+            path: config_file.path().to_path_buf(),
+        })?;
+    finalize_config_file(parsed, config_overrides, printer)
+}
+
 /// Errors that can occur when performing a version bump.
 ///
 /// This includes missing versions, hook failures, serialization errors,
@@ -248,9 +374,18 @@ where
     /// Error replacing version in project files.
     #[error("failed to replace version")]
     ReplaceVersion(#[from] crate::files::ReplaceVersionError),
-    /// A required template argument was missing.
+    /// Failed to write a modified file.
+    #[error("failed to write file")]
+    Io(#[from] IoError),
+    /// A required template argument was missing or could not be formatted.
     #[error(transparent)]
-    MissingArgument(#[from] f_string::MissingArgumentError),
+    Format(#[from] f_string::FormatError),
+    /// Failed to render the user-defined context variables.
+    #[error(transparent)]
+    Context(#[from] crate::context::Error),
+    /// A file changed after the bump was planned.
+    #[error("file {path:?} changed after the bump was planned")]
+    ChangedSincePlanned { path: PathBuf },
     /// Underlying version control system error.
     #[error(transparent)]
     VCS(VCS::Error),
@@ -261,9 +396,11 @@ where
 /// Holds the VCS interface, configuration, and file mappings needed to
 /// update version numbers, run hooks, and commit/tag changes.
 #[derive(Debug)]
-pub struct BumpVersion<VCS, L> {
+pub struct BumpVersion<VCS, L, FS = LocalFileSystem> {
     /// Interface to the version control system (e.g., Git).
     pub repo: VCS,
+    /// Filesystem used to read and write the modified files.
+    pub fs: FS,
     /// Finalized bumpversion configuration.
     pub config: config::FinalizedConfig,
     /// Logger for outputting messages and diffs.
@@ -278,15 +415,17 @@ pub struct BumpVersion<VCS, L> {
     pub config_file: Option<config::ConfigFile>,
 }
 
-pub async fn apply_modifications<'a, VCS, S>(
+/// Compute the modifications of the `configured_files` without writing them.
+pub async fn compute_modifications<'a, VCS, FS, S>(
+    fs: &FS,
     configured_files: &'a IndexMap<PathBuf, Vec<config::change::FileChange>>,
     current_version: &version::Version,
     new_version: &version::Version,
     ctx_with_new_version: &HashMap<String, String, S>,
-    dry_run: bool,
 ) -> Result<Vec<(&'a PathBuf, Option<files::Modification>)>, BumpError<VCS>>
 where
     VCS: VersionControlSystem,
+    FS: FileSystem,
     S: std::hash::BuildHasher + Clone,
 {
     let mut modifications: Vec<(&PathBuf, Option<files::Modification>)> =
@@ -300,12 +439,12 @@ where
                 async move {
                     debug_assert!(path.is_absolute());
                     let modification = files::replace_version_in_file(
+                        fs,
                         path,
                         change,
                         &current_version,
                         &new_version,
                         &ctx_with_new_version,
-                        dry_run,
                     )
                     .await?;
                     Ok::<_, BumpError<VCS>>((path, modification))
@@ -318,121 +457,18 @@ where
     Ok(modifications)
 }
 
-impl<VCS, L> BumpVersion<VCS, L>
+impl<VCS, L, FS> BumpVersion<VCS, L, FS>
 where
     VCS: VersionControlSystem,
     L: logging::Log,
+    FS: FileSystem,
 {
-    async fn apply_version_bump(
-        &self,
-        bump: Bump<'_>,
-        current_version: version::Version,
-        current_version_serialized: &str,
-        new_version: version::Version,
-        new_version_serialized: String,
-    ) -> Result<(), BumpError<VCS>> {
-        if current_version_serialized == new_version_serialized {
-            tracing::info!(
-                version = new_version_serialized,
-                "next version matches current version"
-            );
-            return Ok(());
-        }
-
-        if self.config.global.dry_run {
-            tracing::info!("dry run active, won't touch any files.");
-        }
-
-        let mut configured_files: IndexMap<PathBuf, Vec<config::change::FileChange>> =
-            files::files_to_modify(&self.config, self.file_map.clone()).collect();
-
-        // filter the files that are not valid for this bump
-        if let Bump::Component(version_component_to_bump) = bump {
-            for changes in configured_files.values_mut() {
-                changes.retain(|change| change.will_bump_component(version_component_to_bump));
-                changes.retain(|change| !change.will_not_bump_component(version_component_to_bump));
-            }
-        }
-
-        let ctx_with_new_version: HashMap<String, String> = context::get_context(
-            Some(&self.tag_and_revision),
-            Some(&current_version),
-            Some(&new_version),
-            Some(current_version_serialized),
-            Some(&new_version_serialized),
-        )
-        .collect();
-
-        let configured_files = Arc::new(configured_files);
-
-        let modifications = apply_modifications(
-            &configured_files,
-            &current_version,
-            &new_version,
-            &ctx_with_new_version,
-            self.config.global.dry_run,
-        )
-        .await?;
-
-        for (path, modification) in modifications {
-            self.logger.log(Verbosity::Low, "");
-            self.logger.log_modification(path, modification);
-        }
-
-        if let Some(ref config_file) = self.config_file {
-            let modification = self
-                .update_config_file(config_file, &ctx_with_new_version)
-                .await?;
-            self.logger.log(Verbosity::Low, "");
-            self.logger
-                .log_modification(config_file.path(), modification);
-        }
-
-        self.run_pre_commit_hooks(
-            Some(&current_version),
-            Some(&new_version),
-            &new_version_serialized,
-        )
-        .await?;
-
-        let additional_files: Vec<_> = self
-            .config
-            .global
-            .additional_files
-            .as_deref()
-            .unwrap_or_default()
-            .iter()
-            .map(|path| {
-                if path.is_absolute() {
-                    path.clone()
-                } else {
-                    self.repo.path().join(path)
-                }
-            })
-            .collect();
-
-        // TODO: warn for files that dirty but not in either configured or additional files
-        self.commit_changes(
-            &configured_files,
-            &additional_files,
-            current_version_serialized.to_string(),
-            new_version_serialized.clone(),
-            &ctx_with_new_version,
-        )
-        .await?;
-
-        self.run_post_commit_hooks(
-            Some(&current_version),
-            Some(&new_version),
-            &new_version_serialized,
-        )
-        .await?;
-
-        Ok(())
-    }
-
     /// Bump the desired version component to the next value or set the version to `new_version`.
     ///
+    /// Runs the setup hooks, plans the bump, logs the plan, and applies it.
+    /// If `dry_run` is enabled, the setup hooks are not run and the plan is not applied.
+    /// See [`BumpVersion::setup`], [`BumpVersion::plan`] and [`BumpVersion::apply`].
+    ///
     /// # Errors
     /// - When the no current version is present.
     /// - When the current or next version are empty.
@@ -441,103 +477,28 @@ where
     /// - When the next version cannot be serialized.
     /// - When a version in a file cannot be replaced.
     pub async fn bump(&self, bump: Bump<'_>) -> Result<(), BumpError<VCS>> {
-        let current_version_serialized = self
-            .config
-            .global
-            .current_version
-            .as_ref()
-            .ok_or_else(|| BumpError::MissingCurrentVersion)?;
-
-        tracing::debug!(
-            version = current_version_serialized,
-            "parsing current version"
-        );
-
-        let parse_version_pattern = &self.config.global.parse_version_pattern;
-        let version_spec = version::VersionSpec::from_components(self.components.clone());
-        let current_version = version::Version::parse(
-            current_version_serialized,
-            parse_version_pattern,
-            &version_spec,
-        );
-        // let current_version = self.current_version()?;
-        let current_version = current_version.ok_or_else(|| BumpError::EmptyVersion)?;
+        use crate::logging::LogExt;
 
         self.logger
-            .log(Verbosity::Low, &format!("{}", "[current version]".blue()));
-        self.logger.log(
-            Verbosity::Low,
-            &format!("\t{}", current_version_serialized.yellow().bold()),
-        );
-        self.logger.log(
-            Verbosity::Medium,
-            &format!(
-                "\t{}",
-                crate::logging::format_version(&current_version, Color::Cyan)
-            ),
-        );
-
-        self.run_setup_hooks(Some(&current_version)).await?;
-
-        let new_version = match bump {
-            Bump::Component(comp_name) => {
-                tracing::info!(
-                    component = comp_name.to_string(),
-                    "attempting to increment version component"
-                );
-                current_version.bump(comp_name).map_err(Into::into)
-            }
-            Bump::NewVersion(new_version) => {
-                tracing::info!(new_version, "parse new version");
-                version::Version::parse(new_version, parse_version_pattern, &version_spec)
-                    .ok_or_else(|| BumpError::EmptyVersion)
-            }
-        }?;
-
-        tracing::info!(new_version = new_version.to_string(), "next version");
-
-        let ctx_without_new_version: HashMap<String, String> = context::get_context(
-            Some(&self.tag_and_revision),
-            Some(&current_version),
-            None,
-            Some(current_version_serialized),
-            None,
-        )
-        .collect();
+            .log_hooks("setup", &self.config.global.setup_hooks);
+        if !self.config.global.dry_run {
+            self.setup().await?;
+        }
 
-        let serialize_version_patterns = &self.config.global.serialize_version_patterns;
-        let new_version_serialized =
-            new_version.serialize(serialize_version_patterns, &ctx_without_new_version)?;
-        tracing::info!(version = new_version_serialized, "next version");
+        let plan = self.plan(bump).await?;
+        plan::log_plan(&self.logger, &plan);
 
-        self.logger
-            .log(Verbosity::Low, &format!("{}", "[new version]".blue()));
-        self.logger.log(
-            Verbosity::Low,
-            &format!("\t{}", new_version_serialized.yellow().bold()),
-        );
-        self.logger.log(
-            Verbosity::Medium,
-            &format!(
-                "\t{}",
-                crate::logging::format_version(&new_version, Color::Cyan)
-            ),
-        );
-
-        self.apply_version_bump(
-            bump,
-            current_version,
-            current_version_serialized,
-            new_version,
-            new_version_serialized,
-        )
-        .await
+        if self.config.global.dry_run {
+            tracing::info!("dry run active, won't touch any files.");
+            return Ok(());
+        }
+        self.apply(&plan).await
     }
 
     /// Update the version string in the bumpversion configuration file.
     ///
-    /// Detects the file format (INI or TOML), applies version replacement using the provided
-    /// template context, and writes the file if modified (unless dry-run is enabled).
+    /// Detects the file format (INI or TOML) and applies version replacement using the provided
+    /// template context. The file is not written.
     /// Supports:
     /// - `.bumpversion.cfg` and `setup.cfg` (INI)
     /// - `.bumpversion.toml` and `pyproject.toml` (TOML)
@@ -561,6 +522,23 @@ where
     where
         K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
         V: AsRef<str> + std::fmt::Debug,
+    {
+        self.update_config_file_in(&self.fs, config_file, ctx).await
+    }
+
+    /// Update the version string in the bumpversion configuration file read from `fs`.
+    ///
+    /// See [`BumpVersion::update_config_file`].
+    pub(crate) async fn update_config_file_in<K, V, F>(
+        &self,
+        fs: &F,
+        config_file: &config::ConfigFile,
+        ctx: &HashMap<K, V>,
+    ) -> Result<Option<files::Modification>, BumpError<VCS>>
+    where
+        K: std::borrow::Borrow<str> + std::hash::Hash + Eq + std::fmt::Debug,
+        V: AsRef<str> + std::fmt::Debug,
+        F: FileSystem,
     {
         let config_path = config_file.path();
 
@@ -572,26 +550,13 @@ where
         if config_path.starts_with(working_dir) {
             let modification = match config_file {
                 config::ConfigFile::SetupCfg(_) | config::ConfigFile::BumpversionCfg(_) => {
-                    config::ini::replace_version(
-                        config_path,
-                        &self.config,
-                        ctx,
-                        self.config.global.dry_run,
-                    )
-                    .await
-                    .map_err(files::ReplaceVersionError::from)
+                    config::ini::replace_version(fs, config_path, &self.config, ctx).await
                 }
                 config::ConfigFile::PyProject(_) | config::ConfigFile::BumpversionToml(_) => {
-                    config::toml::replace_version(
-                        config_path,
-                        &self.config,
-                        ctx,
-                        self.config.global.dry_run,
-                    )
-                    .await
+                    config::toml::replace_version(fs, config_path, &self.config, ctx).await
                 }
                 config::ConfigFile::CargoToml(_) => {
-                    todo!("cargo support")
+                    config::cargo_toml::replace_version(fs, config_path, &self.config, ctx).await
                 }
             }?;
 
@@ -603,140 +568,6 @@ where
             Ok(None)
         }
     }
-
-    /// Stage and commit versioned files, and optionally create a VCS tag.
-    ///
-    /// This will:
-    /// 1. Collect files modified according to `configured_files` and any `additional_files`.
-    /// 2. Stage these files along with the configuration file if present.
-    /// 3. Commit with the configured commit message and arguments.
-    /// 4. If tagging is enabled in config, create a new tag with the configured name and message.
-    ///
-    /// # Arguments
-    /// * `configured_files` - Map of file paths to their version change instructions.
-    /// * `additional_files` - Extra file paths to include in the commit (e.g., config file).
-    /// * `current_version_serialized` - Previous version string before bump.
-    /// * `next_version_serialized` - New version string after bump.
-    /// * `ctx` - Context map used to format commit and tag templates.
-    ///
-    /// # Errors
-    /// Returns a [`BumpError<VCS>`] if staging, committing, or tagging fails,
-    /// or if message/tag formatting encounters an error.
-    pub async fn commit_changes(
-        &self,
-        configured_files: &IndexMap<PathBuf, Vec<config::change::FileChange>>,
-        additional_files: &[impl AsRef<Path>],
-        current_version_serialized: String,
-        next_version_serialized: String,
-        ctx: &HashMap<String, String>,
-    ) -> Result<(), BumpError<VCS>> {
-        let extra_args = self
-            .config
-            .global
-            .commit_args
-            .as_deref()
-            .and_then(shlex::split)
-            .unwrap_or_default();
-
-        let mut files_to_commit: HashSet<&Path> = configured_files
-            .keys()
-            .map(PathBuf::as_path)
-            .chain(additional_files.iter().map(std::convert::AsRef::as_ref))
-            .collect();
-
-        if let Some(ref config_file) = self.config_file {
-            // add config file
-            files_to_commit.insert(config_file.path());
-        }
-
-        if self.config.global.commit {
-            self.logger
-                .log(Verbosity::Low, &format!("{}", "[commit]".magenta()));
-
-            for path in &files_to_commit {
-                self.logger.log(
-                    Verbosity::Low,
-                    &format!("\t{} {}", "   add".dimmed(), path.to_string_lossy().cyan()),
-                );
-            }
-            if !self.config.global.dry_run {
-                self.repo
-                    .add(&files_to_commit)
-                    .await
-                    .map_err(BumpError::VCS)?;
-            }
-
-            let commit_message = self.config.global.commit_message.format(ctx, true)?;
-            tracing::info!(msg = commit_message, "commit");
-
-            self.logger.log(
-                Verbosity::Low,
-                &format!("\t{} {}", "commit".dimmed(), commit_message.cyan()),
-            );
-
-            if !self.config.global.dry_run {
-                let env = std::env::vars().chain([
-                    ("HGENCODING".to_string(), "utf-8".to_string()),
-                    (
-                        "BUMPVERSION_CURRENT_VERSION".to_string(),
-                        current_version_serialized,
-                    ),
-                    (
-                        "BUMPVERSION_NEW_VERSION".to_string(),
-                        next_version_serialized,
-                    ),
-                ]);
-                self.repo
-                    .commit(commit_message.as_str(), extra_args.as_slice(), env)
-                    .await
-                    .map_err(BumpError::VCS)?;
-            }
-        }
-
-        if self.config.global.tag {
-            let sign_tag = self.config.global.sign_tags;
-            let tag_name = self.config.global.tag_name.format(ctx, true)?;
-            let tag_message = self.config.global.tag_message.format(ctx, true)?;
-
-            tracing::info!(msg = tag_message, name = tag_name, "tag");
-
-            let existing_tags = self.repo.tags().await.map_err(BumpError::VCS)?;
-
-            self.logger
-                .log(Verbosity::Low, &format!("{}", "[tag]".magenta()));
-
-            if existing_tags.contains(&tag_name) {
-                self.logger.log(
-                    Verbosity::Low,
-                    &format!(
-                        "\t{}",
-                        format!("tag {tag_name} already exists and will not be created").dimmed()
-                    ),
-                );
-                tracing::warn!("tag {tag_name:?} already exists and will not be created");
-            } else {
-                self.logger.log(
-                    Verbosity::Low,
-                    &format!("\t{}{}", "tag = ".dimmed(), tag_name.yellow()),
-                );
-                self.logger.log(
-                    Verbosity::Low,
-                    &format!("\t{}{}", "message = ".dimmed(), tag_message.yellow()),
-                );
-                self.logger.log(
-                    Verbosity::Low,
-                    &format!("\t{}{}", "sign = ".dimmed(), sign_tag.to_string().yellow()),
-                );
-                if !self.config.global.dry_run {
-                    self.repo
-                        .tag(tag_name.as_str(), Some(&tag_message), sign_tag)
-                        .await
-                        .map_err(BumpError::VCS)?;
-                }
-            }
-        }
-        Ok(())
-    }
 }
 
 #[cfg(test)]
@@ -772,7 +603,7 @@ pub mod tests {
 
     #[test]
     fn test_verbosity_ord() {
-        use super::Verbosity;
+        use crate::logging::Verbosity;
 
         let mut verbosities = [Verbosity::Medium, Verbosity::Low, Verbosity::High];
         verbosities.sort();
@@ -781,4 +612,122 @@ pub mod tests {
             [Verbosity::Low, Verbosity::Medium, Verbosity::High]
         );
     }
+
+    #[tokio::test]
+    async fn test_load_config() -> color_eyre::eyre::Result<()> {
+        init();
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("versioning.toml");
+        tokio::fs::write(
+            &path,
+            indoc::indoc! {r#"
+                [tool.bumpversion]
+                current_version = "1.2.3"
+            "#},
+        )
+        .await?;
+
+        let printer = crate::diagnostics::BufferedPrinter::default();
+        let overrides = crate::config::GlobalConfig::empty();
+        let (config_file, config) =
+            super::load_config(&path, None, &overrides, false, &printer).await?;
+        sim_assert_eq!(
+            config_file,
+            crate::config::ConfigFile::BumpversionToml(path.canonicalize()?)
+        );
+        sim_assert_eq!(config.global.current_version.as_deref(), Some("1.2.3"));
+
+        // an explicit format takes precedence over the file extension
+        let err = super::load_config(
+            &path,
+            Some(crate::config::ConfigFormat::CargoToml),
+            &overrides,
+            false,
+            &printer,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(err, crate::config::Error::MissingConfig { .. }),
+            "unexpected error: {err:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_config_without_bumpversion_section() -> color_eyre::eyre::Result<()> {
+        init();
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("setup.cfg");
+        tokio::fs::write(
+            &path,
+            indoc::indoc! {r"
+                [metadata]
+                name = test
+            "},
+        )
+        .await?;
+
+        let printer = crate::diagnostics::BufferedPrinter::default();
+        let overrides = crate::config::GlobalConfig::empty();
+        let err = super::load_config(&path, None, &overrides, false, &printer)
+            .await
+            .unwrap_err();
+        sim_assert_eq!(
+            err.to_string(),
+            format!(
+                "{:?} does not contain a bumpversion configuration",
+                path.canonicalize()?
+            )
+        );
+
+        let path = dir.path().join("versioning.yaml");
+        tokio::fs::write(&path, "").await?;
+        let err = super::load_config(&path, None, &overrides, false, &printer)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::config::Error::UnknownFormat { .. }),
+            "unexpected error: {err:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_config_with_unknown_keys() -> color_eyre::eyre::Result<()> {
+        init();
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(".bumpversion.toml");
+        tokio::fs::write(
+            &path,
+            indoc::indoc! {r#"
+                [tool.bumpversion]
+                current_version = "1.2.3"
+                comit = true
+            "#},
+        )
+        .await?;
+
+        // unknown keys are ignored with a warning
+        let printer = crate::diagnostics::BufferedPrinter::default();
+        let overrides = crate::config::GlobalConfig::empty();
+        let (_, config) = super::find_config(dir.path(), &overrides, false, &printer)
+            .await?
+            .expect("config file");
+        sim_assert_eq!(config.global.current_version.as_deref(), Some("1.2.3"));
+        assert!(!config.global.commit);
+
+        // unknown keys are errors when strict
+        let err = super::find_config(dir.path(), &overrides, true, &printer)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::config::Error::InvalidConfig { count: 1, .. }),
+            "unexpected error: {err:?}"
+        );
+        Ok(())
+    }
 }
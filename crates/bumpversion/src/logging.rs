@@ -76,7 +76,7 @@ pub trait LogExt {
     fn log_modification(
         &self,
         path: &std::path::Path,
-        modification: Option<crate::files::Modification>,
+        modification: Option<&crate::files::Modification>,
     );
 
     /// Log configured hooks with their names (e.g., 'setup', 'pre-commit').
@@ -90,7 +90,7 @@ where
     fn log_modification(
         &self,
         path: &std::path::Path,
-        modification: Option<crate::files::Modification>,
+        modification: Option<&crate::files::Modification>,
     ) {
         self.log(
             Verbosity::Low,
@@ -104,7 +104,7 @@ where
         let (search_color, replace_color) = (Color::Red, Color::Green);
 
         let diff = modification.diff(None);
-        for replacement in modification.replacements {
+        for replacement in &modification.replacements {
             let search = unescape(&replacement.search);
             let replace = unescape(&replacement.replace);
             self.log(
@@ -0,0 +1,1509 @@
+//! Planning and applying version bumps.
+//!
+//! A [`BumpPlan`] describes everything a bump will do: the new version, the
+//! modified files, the hooks to run, and the commit and tag to create.
+//! Plans are computed without side effects, so they can be inspected or approved
+//! before they are applied.
+use crate::{
+    BumpError, BumpVersion, config, context, files,
+    fs::FileSystem,
+    vcs::{TagAndRevision, VersionControlSystem},
+    version::{self, Version},
+};
+use colored::Colorize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Modification of a single file.
+#[derive(Debug, serde::Serialize)]
+pub struct FileModification {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// The modification, or `None` if the file is not modified (e.g. because it is missing).
+    pub modification: Option<files::Modification>,
+    /// The file does not exist yet and is created.
+    pub created: bool,
+}
+
+/// Hooks to run when applying a plan.
+///
+/// Setup hooks are not part of a plan, as they run before planning,
+/// see [`BumpVersion::setup`].
+#[derive(Debug, Default, serde::Serialize)]
+pub struct Hooks {
+    /// Hooks to run after the files are modified, before committing.
+    pub pre_commit: Vec<String>,
+    /// Hooks to run after committing and tagging.
+    pub post_commit: Vec<String>,
+}
+
+/// Commit to create when applying a plan.
+#[derive(Debug, serde::Serialize)]
+pub struct Commit {
+    /// The commit message.
+    pub message: String,
+    /// Files to add to the commit.
+    pub files: Vec<PathBuf>,
+    /// Extra arguments passed to the commit command.
+    pub args: Vec<String>,
+}
+
+/// Tag to create when applying a plan.
+#[derive(Debug, serde::Serialize)]
+pub struct Tag {
+    /// The tag name.
+    pub name: String,
+    /// The tag message.
+    pub message: String,
+    /// Sign the tag.
+    pub sign: bool,
+    /// The tag already exists and will not be created.
+    pub exists: bool,
+}
+
+/// Push to perform when applying a plan.
+#[derive(Debug, serde::Serialize)]
+pub struct Push {
+    /// The remote to push to, or the default remote of the VCS.
+    pub remote: Option<String>,
+    /// The tag to push together with the current branch, if any.
+    pub tag: Option<String>,
+    /// Extra arguments passed to the push command.
+    pub args: Vec<String>,
+}
+
+/// Everything a version bump will do.
+///
+/// Returned by [`BumpVersion::plan`] and executed by [`BumpVersion::apply`].
+#[derive(Debug, serde::Serialize)]
+pub struct BumpPlan {
+    /// The current version.
+    pub current_version: Version,
+    /// The serialized current version.
+    pub current_version_serialized: String,
+    /// The new version.
+    pub new_version: Version,
+    /// The serialized new version.
+    pub new_version_serialized: String,
+    /// Modifications of the configured files, sorted by path.
+    pub files: Vec<FileModification>,
+    /// Modification of the config file, if any.
+    ///
+    /// If the config file is also a configured file, this includes the modification
+    /// of the configured file, which is then not part of `files`.
+    pub config_file: Option<FileModification>,
+    /// Modification of the changelog, if enabled.
+    pub changelog: Option<FileModification>,
+    /// Hooks to run.
+    pub hooks: Hooks,
+    /// Commit to create, if committing is enabled.
+    pub commit: Option<Commit>,
+    /// Tag to create, if tagging is enabled.
+    pub tag: Option<Tag>,
+    /// Push to perform, if pushing is enabled.
+    pub push: Option<Push>,
+}
+
+impl BumpPlan {
+    /// Returns `true` if the new version matches the current version.
+    ///
+    /// Applying an unchanged plan does nothing.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.current_version_serialized == self.new_version_serialized
+    }
+
+    /// All modified files, including the config file and the changelog.
+    pub fn modifications(&self) -> impl Iterator<Item = (&Path, &files::Modification)> {
+        self.files
+            .iter()
+            .chain(self.config_file.as_ref())
+            .chain(self.changelog.as_ref())
+            .filter_map(|file| Some((file.path.as_path(), file.modification.as_ref()?)))
+    }
+}
+
+/// Errors that can occur when computing the new version.
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum NewVersionError {
+    #[error("failed to parse version {0:?}")]
+    InvalidVersion(String),
+    #[error(transparent)]
+    Bump(#[from] version::BumpError),
+    #[error(transparent)]
+    Serialize(#[from] version::SerializeError),
+    #[error(transparent)]
+    Context(#[from] context::Error),
+}
+
+impl<VCS> From<NewVersionError> for BumpError<VCS>
+where
+    VCS: VersionControlSystem,
+{
+    fn from(err: NewVersionError) -> Self {
+        match err {
+            NewVersionError::InvalidVersion(_) => Self::EmptyVersion,
+            NewVersionError::Bump(err) => Self::Bump(err),
+            NewVersionError::Serialize(err) => Self::Serialize(err),
+            NewVersionError::Context(err) => Self::Context(err),
+        }
+    }
+}
+
+/// Compute and serialize the new version for a `bump` of the current version.
+///
+/// Shared by [`BumpVersion::plan`] and the `show` commands, so that both compute the same version.
+pub(crate) fn new_version(
+    config: &config::FinalizedConfig,
+    version_spec: &version::VersionSpec,
+    current_version: &Version,
+    current_version_serialized: &str,
+    tag_and_revision: &TagAndRevision,
+    bump: crate::Bump<'_>,
+) -> Result<(Version, String), NewVersionError> {
+    let global = &config.global;
+    let new_version = match bump {
+        crate::Bump::Component(comp_name) => {
+            tracing::info!(
+                component = comp_name.to_string(),
+                "attempting to increment version component"
+            );
+            current_version.bump(comp_name)?
+        }
+        crate::Bump::NewVersion(new_version) => {
+            tracing::info!(new_version, "parse new version");
+            Version::parse(new_version, &global.parse_version_pattern, version_spec)
+                .ok_or_else(|| NewVersionError::InvalidVersion(new_version.to_string()))?
+        }
+    };
+
+    tracing::info!(new_version = new_version.to_string(), "next version");
+
+    let mut ctx_without_new_version: HashMap<String, String> = context::get_context(
+        Some(tag_and_revision),
+        Some(current_version),
+        None,
+        Some(current_version_serialized),
+        None,
+    )
+    .collect();
+    context::render_variables(&config.context, &mut ctx_without_new_version)?;
+
+    let new_version_serialized =
+        new_version.serialize(&global.serialize_version_patterns, &ctx_without_new_version)?;
+    tracing::info!(version = new_version_serialized, "next version");
+    Ok((new_version, new_version_serialized))
+}
+
+impl<VCS, L, FS> BumpVersion<VCS, L, FS>
+where
+    VCS: VersionControlSystem,
+    L: crate::logging::Log,
+    FS: FileSystem,
+{
+    /// Parse the current version.
+    fn current_version(&self) -> Result<(Version, &String), BumpError<VCS>> {
+        let current_version_serialized = self
+            .config
+            .global
+            .current_version
+            .as_ref()
+            .ok_or_else(|| BumpError::MissingCurrentVersion)?;
+
+        tracing::debug!(
+            version = current_version_serialized,
+            "parsing current version"
+        );
+
+        let version_spec = version::VersionSpec::from_components(self.components.clone());
+        let current_version = Version::parse(
+            current_version_serialized,
+            &self.config.global.parse_version_pattern,
+            &version_spec,
+        )
+        .ok_or_else(|| BumpError::EmptyVersion)?;
+        Ok((current_version, current_version_serialized))
+    }
+
+    /// Run the setup hooks.
+    ///
+    /// Setup hooks may modify the configured files, so they must run before
+    /// the bump is planned using [`BumpVersion::plan`].
+    ///
+    /// # Errors
+    /// - When the no current version is present or it is empty.
+    /// - When one of the user-provided setup hooks fails.
+    pub async fn setup(&self) -> Result<(), BumpError<VCS>> {
+        let (current_version, _) = self.current_version()?;
+        self.run_setup_hooks(&self.config.global.setup_hooks, Some(&current_version))
+            .await?;
+        Ok(())
+    }
+
+    /// Plan to bump the desired version component to the next value or set the version to `new_version`.
+    ///
+    /// No files are written and no hooks are run.
+    /// Run the setup hooks using [`BumpVersion::setup`] before planning.
+    ///
+    /// # Errors
+    /// - When the no current version is present.
+    /// - When the current or next version are empty.
+    /// - When the current version component cannot be bumped.
+    /// - When the next version cannot be serialized.
+    /// - When a version in a file cannot be replaced.
+    /// - When the VCS does not support the configured commit arguments, signed tags, or pushing.
+    pub async fn plan(&self, bump: crate::Bump<'_>) -> Result<BumpPlan, BumpError<VCS>> {
+        let (current_version, current_version_serialized) = self.current_version()?;
+        let version_spec = version::VersionSpec::from_components(self.components.clone());
+
+        let (new_version, new_version_serialized) = new_version(
+            &self.config,
+            &version_spec,
+            &current_version,
+            current_version_serialized,
+            &self.tag_and_revision,
+            bump,
+        )?;
+
+        let mut plan = BumpPlan {
+            current_version,
+            current_version_serialized: current_version_serialized.clone(),
+            new_version,
+            new_version_serialized,
+            files: vec![],
+            config_file: None,
+            changelog: None,
+            hooks: Hooks::default(),
+            commit: None,
+            tag: None,
+            push: None,
+        };
+
+        if plan.is_unchanged() {
+            tracing::info!(
+                version = plan.new_version_serialized,
+                "next version matches current version"
+            );
+            return Ok(plan);
+        }
+
+        let configured_files = self.configured_files(bump);
+
+        let mut ctx_with_new_version: HashMap<String, String> = context::get_context(
+            Some(&self.tag_and_revision),
+            Some(&plan.current_version),
+            Some(&plan.new_version),
+            Some(&plan.current_version_serialized),
+            Some(&plan.new_version_serialized),
+        )
+        .collect();
+        context::render_variables(&self.config.context, &mut ctx_with_new_version)?;
+
+        plan.files = crate::compute_modifications(
+            &self.fs,
+            &configured_files,
+            &plan.current_version,
+            &plan.new_version,
+            &ctx_with_new_version,
+        )
+        .await?
+        .into_iter()
+        .map(|(path, modification)| FileModification {
+            path: path.clone(),
+            modification,
+            created: false,
+        })
+        .collect();
+
+        plan.config_file = self
+            .plan_config_file(&mut plan.files, &ctx_with_new_version)
+            .await?;
+        if let Some(ref changelog) = self.config.changelog {
+            plan.changelog = Some(
+                self.plan_changelog(changelog, &ctx_with_new_version)
+                    .await?,
+            );
+        }
+
+        plan.hooks.pre_commit = self.config.global.pre_commit_hooks.clone();
+        plan.hooks.post_commit = self.config.global.post_commit_hooks.clone();
+
+        if self.config.global.commit {
+            let changelog = plan
+                .changelog
+                .as_ref()
+                .map(|changelog| changelog.path.clone());
+            plan.commit =
+                Some(self.plan_commit(&configured_files, changelog, &ctx_with_new_version)?);
+        }
+        if self.config.global.tag {
+            plan.tag = Some(self.plan_tag(&ctx_with_new_version).await?);
+        }
+        if self.config.global.push {
+            plan.push = Some(self.plan_push(plan.tag.as_ref()));
+        }
+
+        self.repo
+            .check_supported(
+                plan.commit
+                    .as_ref()
+                    .map_or(&[], |commit| commit.args.as_slice()),
+                plan.tag.as_ref().is_some_and(|tag| tag.sign && !tag.exists),
+                plan.push.is_some(),
+            )
+            .map_err(BumpError::VCS)?;
+
+        Ok(plan)
+    }
+
+    /// The configured files and their changes that are valid for the `bump`.
+    fn configured_files(
+        &self,
+        bump: crate::Bump<'_>,
+    ) -> indexmap::IndexMap<PathBuf, Vec<config::change::FileChange>> {
+        let mut configured_files: indexmap::IndexMap<PathBuf, Vec<config::change::FileChange>> =
+            files::files_to_modify(&self.config, self.file_map.clone()).collect();
+
+        // filter the files that are not valid for this bump
+        if let crate::Bump::Component(version_component_to_bump) = bump {
+            for changes in configured_files.values_mut() {
+                changes.retain(|change| change.will_bump_component(version_component_to_bump));
+                changes.retain(|change| !change.will_not_bump_component(version_component_to_bump));
+            }
+        }
+        configured_files
+    }
+
+    /// Replace the current version in the config file, if any.
+    ///
+    /// If the config file is also one of the configured `files`, the version is replaced
+    /// in its modified contents and its modification is removed from `files`,
+    /// so that both modifications are written.
+    async fn plan_config_file(
+        &self,
+        files: &mut Vec<FileModification>,
+        ctx: &HashMap<String, String>,
+    ) -> Result<Option<FileModification>, BumpError<VCS>> {
+        let Some(ref config_file) = self.config_file else {
+            return Ok(None);
+        };
+        let path = config_file.path();
+        let configured = files
+            .iter()
+            .position(|file| file.path == path)
+            .map(|idx| files.remove(idx))
+            .and_then(|file| file.modification);
+
+        let Some(configured) = configured else {
+            let modification = self.update_config_file(config_file, ctx).await?;
+            return Ok(Some(FileModification {
+                path: path.to_path_buf(),
+                modification,
+                created: false,
+            }));
+        };
+
+        let fs = Modified {
+            fs: &self.fs,
+            path,
+            contents: &configured.after,
+        };
+        let modification = match self.update_config_file_in(&fs, config_file, ctx).await? {
+            Some(modification) => files::Modification {
+                before: configured.before,
+                after: modification.after,
+                replacements: configured
+                    .replacements
+                    .into_iter()
+                    .chain(modification.replacements)
+                    .collect(),
+            },
+            None => configured,
+        };
+        Ok(Some(FileModification {
+            path: path.to_path_buf(),
+            modification: Some(modification),
+            created: false,
+        }))
+    }
+
+    /// Prepend the release notes of the commits since the latest tag to the changelog.
+    async fn plan_changelog(
+        &self,
+        changelog: &config::FinalizedChangelogConfig,
+        ctx: &HashMap<String, String>,
+    ) -> Result<FileModification, BumpError<VCS>> {
+        let path = if changelog.path.is_absolute() {
+            changelog.path.clone()
+        } else {
+            self.repo.path().join(&changelog.path)
+        };
+        // the changelog is created for the first release
+        let created = !self.fs.is_file(&path).await;
+        let before = if created {
+            String::new()
+        } else {
+            self.fs.read_to_string(&path).await?
+        };
+
+        let latest_tag = self
+            .tag_and_revision
+            .tag
+            .as_ref()
+            .map(|tag| tag.current_tag.as_str());
+        let commits = self
+            .repo
+            .commits_since(latest_tag)
+            .await
+            .map_err(BumpError::VCS)?;
+        let release = crate::changelog::render(&commits, changelog, ctx)?;
+        let after = crate::changelog::prepend(&before, &release);
+
+        Ok(FileModification {
+            path,
+            modification: Some(files::Modification {
+                before,
+                after,
+                replacements: vec![],
+            }),
+            created,
+        })
+    }
+
+    /// Plan the commit of the `configured_files`, the `changelog`, the additional files,
+    /// and the config file.
+    fn plan_commit(
+        &self,
+        configured_files: &indexmap::IndexMap<PathBuf, Vec<config::change::FileChange>>,
+        changelog: Option<PathBuf>,
+        ctx: &HashMap<String, String>,
+    ) -> Result<Commit, BumpError<VCS>> {
+        // TODO: warn for files that dirty but not in either configured or additional files
+        let additional_files = self
+            .config
+            .global
+            .additional_files
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|path| {
+                if path.is_absolute() {
+                    path.clone()
+                } else {
+                    self.repo.path().join(path)
+                }
+            });
+
+        let files: BTreeSet<PathBuf> = configured_files
+            .keys()
+            .cloned()
+            .chain(changelog)
+            .chain(additional_files)
+            .chain(
+                self.config_file
+                    .as_ref()
+                    .map(|file| file.path().to_path_buf()),
+            )
+            .collect();
+
+        let message = self.config.global.commit_message.format(ctx, true)?;
+        tracing::info!(msg = message, "commit");
+
+        Ok(Commit {
+            message,
+            files: files.into_iter().collect(),
+            args: self
+                .config
+                .global
+                .commit_args
+                .as_deref()
+                .and_then(shlex::split)
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Plan the tag, checking whether it already exists.
+    async fn plan_tag(&self, ctx: &HashMap<String, String>) -> Result<Tag, BumpError<VCS>> {
+        let name = self.config.global.tag_name.format(ctx, true)?;
+        let message = self.config.global.tag_message.format(ctx, true)?;
+        tracing::info!(msg = message, name, "tag");
+
+        let existing_tags = self.repo.tags().await.map_err(BumpError::VCS)?;
+        let exists = existing_tags.contains(&name);
+        if exists {
+            tracing::warn!("tag {name:?} already exists and will not be created");
+        }
+        Ok(Tag {
+            name,
+            message,
+            sign: self.config.global.sign_tags,
+            exists,
+        })
+    }
+
+    /// Plan pushing the current branch and the new `tag`.
+    fn plan_push(&self, tag: Option<&Tag>) -> Push {
+        let global = &self.config.global;
+        if !global.commit && !global.tag {
+            tracing::warn!("push is enabled, but neither commit nor tag are");
+        }
+        Push {
+            remote: global.push_remote.clone(),
+            tag: tag.filter(|tag| !tag.exists).map(|tag| tag.name.clone()),
+            args: global
+                .push_args
+                .as_deref()
+                .and_then(shlex::split)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Run the pre-commit hooks and commit the changes of a `plan`.
+    async fn pre_commit_and_commit(&self, plan: &BumpPlan) -> Result<(), BumpError<VCS>> {
+        self.run_pre_commit_hooks(
+            &plan.hooks.pre_commit,
+            Some(&plan.current_version),
+            Some(&plan.new_version),
+            &plan.new_version_serialized,
+        )
+        .await?;
+
+        if let Some(ref commit) = plan.commit {
+            self.repo.add(&commit.files).await.map_err(BumpError::VCS)?;
+
+            let env = std::env::vars().chain([
+                ("HGENCODING".to_string(), "utf-8".to_string()),
+                (
+                    "BUMPVERSION_CURRENT_VERSION".to_string(),
+                    plan.current_version_serialized.clone(),
+                ),
+                (
+                    "BUMPVERSION_NEW_VERSION".to_string(),
+                    plan.new_version_serialized.clone(),
+                ),
+            ]);
+            self.repo
+                .commit(commit.message.as_str(), commit.args.as_slice(), env)
+                .await
+                .map_err(BumpError::VCS)?;
+        }
+        Ok(())
+    }
+
+    /// Unstage and restore the files modified by a `plan` and remove the files it created.
+    ///
+    /// Files that cannot be restored are reported and skipped.
+    async fn restore(&self, plan: &BumpPlan) {
+        if let Some(ref commit) = plan.commit
+            && let Err(err) = self.repo.unstage(&commit.files).await
+        {
+            tracing::error!("failed to unstage files: {err}");
+        }
+
+        let (created, modified): (Vec<_>, Vec<_>) = plan
+            .files
+            .iter()
+            .chain(plan.config_file.as_ref())
+            .chain(plan.changelog.as_ref())
+            .filter_map(|file| Some((file, file.modification.as_ref()?)))
+            .partition(|(file, _)| file.created);
+
+        let originals: Vec<_> = modified
+            .iter()
+            .map(|(file, modification)| (file.path.as_path(), modification.before.as_str()))
+            .collect();
+        if let Err(err) = self.fs.write_files(&originals).await {
+            tracing::error!("failed to restore modified files: {err}");
+        }
+        let created: Vec<_> = created
+            .iter()
+            .map(|(file, _)| file.path.as_path())
+            .collect();
+        if let Err(err) = self.fs.remove_files(&created).await {
+            tracing::error!("failed to remove created files: {err}");
+        }
+    }
+
+    /// Check that the files of a `plan` did not change since it was planned.
+    async fn check_unchanged(&self, plan: &BumpPlan) -> Result<(), BumpError<VCS>> {
+        for file in plan
+            .files
+            .iter()
+            .chain(plan.config_file.as_ref())
+            .chain(plan.changelog.as_ref())
+        {
+            let Some(ref modification) = file.modification else {
+                continue;
+            };
+            let unchanged = if file.created {
+                !self.fs.is_file(&file.path).await
+            } else {
+                self.fs
+                    .read_to_string(&file.path)
+                    .await
+                    .is_ok_and(|contents| contents == modification.before)
+            };
+            if !unchanged {
+                return Err(BumpError::ChangedSincePlanned {
+                    path: file.path.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a `plan`.
+    ///
+    /// Writes the modified files, runs the pre-commit hooks, commits, tags, and pushes,
+    /// and finally runs the post-commit hooks.
+    ///
+    /// The modified files are written all at once using the [`FileSystem`] of the manager.
+    /// Nothing is written if any of the files changed since the plan was computed.
+    /// If the pre-commit hooks or the commit fail, the files added to the commit are unstaged,
+    /// the original files are restored, and created files, such as a new changelog, are removed.
+    ///
+    /// # Errors
+    /// - When a file changed since the plan was computed.
+    /// - When one of the user-provided pre or post-commit hooks fails.
+    /// - When a modified file cannot be written.
+    /// - When committing, tagging, or pushing fails.
+    pub async fn apply(&self, plan: &BumpPlan) -> Result<(), BumpError<VCS>> {
+        if plan.is_unchanged() {
+            return Ok(());
+        }
+        self.check_unchanged(plan).await?;
+
+        let modified: Vec<_> = plan
+            .modifications()
+            .map(|(path, modification)| (path, modification.after.as_str()))
+            .collect();
+        self.fs.write_files(&modified).await?;
+
+        if let Err(err) = self.pre_commit_and_commit(plan).await {
+            self.restore(plan).await;
+            return Err(err);
+        }
+
+        if let Some(ref tag) = plan.tag
+            && !tag.exists
+        {
+            self.repo
+                .tag(tag.name.as_str(), Some(&tag.message), tag.sign)
+                .await
+                .map_err(BumpError::VCS)?;
+        }
+
+        if let Some(ref push) = plan.push {
+            self.repo
+                .push(
+                    push.remote.as_deref(),
+                    push.tag.as_deref(),
+                    push.args.as_slice(),
+                )
+                .await
+                .map_err(BumpError::VCS)?;
+        }
+
+        self.run_post_commit_hooks(
+            &plan.hooks.post_commit,
+            Some(&plan.current_version),
+            Some(&plan.new_version),
+            &plan.new_version_serialized,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// A [`FileSystem`] reading the modified `contents` of the file at `path`.
+struct Modified<'a, FS> {
+    fs: &'a FS,
+    path: &'a Path,
+    contents: &'a str,
+}
+
+impl<FS> FileSystem for Modified<'_, FS>
+where
+    FS: FileSystem,
+{
+    async fn is_file(&self, path: &Path) -> bool {
+        path == self.path || self.fs.is_file(path).await
+    }
+
+    async fn read_to_string(&self, path: &Path) -> Result<String, files::IoError> {
+        if path == self.path {
+            Ok(self.contents.to_string())
+        } else {
+            self.fs.read_to_string(path).await
+        }
+    }
+
+    async fn write_files(&self, files: &[(&Path, &str)]) -> Result<(), files::IoError> {
+        self.fs.write_files(files).await
+    }
+
+    async fn remove_files(&self, files: &[&Path]) -> Result<(), files::IoError> {
+        self.fs.remove_files(files).await
+    }
+}
+
+/// Log a `plan` using the `logger`.
+pub(crate) fn log_plan(logger: &impl crate::logging::Log, plan: &BumpPlan) {
+    use crate::logging::{LogExt, Verbosity, format_version};
+    use colored::Color;
+
+    for (label, serialized, version) in [
+        (
+            "[current version]",
+            &plan.current_version_serialized,
+            &plan.current_version,
+        ),
+        (
+            "[new version]",
+            &plan.new_version_serialized,
+            &plan.new_version,
+        ),
+    ] {
+        logger.log(Verbosity::Low, &format!("{}", label.blue()));
+        logger.log(Verbosity::Low, &format!("\t{}", serialized.yellow().bold()));
+        logger.log(
+            Verbosity::Medium,
+            &format!("\t{}", format_version(version, Color::Cyan)),
+        );
+    }
+
+    if plan.is_unchanged() {
+        return;
+    }
+
+    for file in plan
+        .files
+        .iter()
+        .chain(plan.config_file.as_ref())
+        .chain(plan.changelog.as_ref())
+    {
+        logger.log(Verbosity::Low, "");
+        logger.log_modification(&file.path, file.modification.as_ref());
+    }
+
+    logger.log_hooks("pre-commit", &plan.hooks.pre_commit);
+
+    if let Some(ref commit) = plan.commit {
+        logger.log(Verbosity::Low, &format!("{}", "[commit]".magenta()));
+        for path in &commit.files {
+            logger.log(
+                Verbosity::Low,
+                &format!("\t{} {}", "   add".dimmed(), path.to_string_lossy().cyan()),
+            );
+        }
+        logger.log(
+            Verbosity::Low,
+            &format!("\t{} {}", "commit".dimmed(), commit.message.cyan()),
+        );
+    }
+
+    if let Some(ref tag) = plan.tag {
+        logger.log(Verbosity::Low, &format!("{}", "[tag]".magenta()));
+        if tag.exists {
+            logger.log(
+                Verbosity::Low,
+                &format!(
+                    "\t{}",
+                    format!("tag {} already exists and will not be created", tag.name).dimmed()
+                ),
+            );
+        } else {
+            logger.log(
+                Verbosity::Low,
+                &format!("\t{}{}", "tag = ".dimmed(), tag.name.yellow()),
+            );
+            logger.log(
+                Verbosity::Low,
+                &format!("\t{}{}", "message = ".dimmed(), tag.message.yellow()),
+            );
+            logger.log(
+                Verbosity::Low,
+                &format!("\t{}{}", "sign = ".dimmed(), tag.sign.to_string().yellow()),
+            );
+        }
+    }
+
+    if let Some(ref push) = plan.push {
+        logger.log(Verbosity::Low, &format!("{}", "[push]".magenta()));
+        let refs = std::iter::once("HEAD")
+            .chain(push.tag.as_deref())
+            .collect::<Vec<_>>()
+            .join(" ");
+        logger.log(
+            Verbosity::Low,
+            &format!(
+                "\t{} {} {}",
+                "push".dimmed(),
+                push.remote.as_deref().unwrap_or("<default>").cyan(),
+                refs.cyan()
+            ),
+        );
+    }
+
+    logger.log_hooks("post-commit", &plan.hooks.post_commit);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Bump, BumpVersion,
+        command::run_command,
+        config::{self, ChangelogConfig, Config, GlobalConfig, InputFile, file::FileConfig},
+        f_string::PythonFormatString,
+        fs::{LocalFileSystem, MemoryFileSystem},
+        logging::NoOpLogger,
+        vcs::{
+            VersionControlSystem,
+            git::GitRepository,
+            gitoxide::{self, GitoxideRepository},
+            memory::{self, MemoryRepository},
+            temp::EphemeralRepository,
+        },
+    };
+    use async_process::Command;
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    fn no_env() -> [(&'static str, &'static str); 0] {
+        []
+    }
+
+    /// Build a manager for the `config`, resolving its files relative to the `repo`.
+    async fn manager_with<VCS, FS>(
+        repo: VCS,
+        fs: FS,
+        mut config: Config,
+    ) -> eyre::Result<BumpVersion<VCS, NoOpLogger, FS>>
+    where
+        VCS: VersionControlSystem,
+    {
+        config.merge_file_configs_with_global_config();
+        let mut config = config.finalize();
+        let components = config::version::version_component_configs(&config);
+        let file_map =
+            crate::files::resolve_files_from_config(&mut config, &components, Some(repo.path()))?;
+        let tag_name = crate::context::expand_variables(&config.global.tag_name, &config.context);
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&tag_name, &config.global.parse_version_pattern)
+            .await?;
+        Ok(BumpVersion {
+            repo,
+            fs,
+            config,
+            logger: NoOpLogger {},
+            tag_and_revision,
+            file_map,
+            components,
+            config_file: None,
+        })
+    }
+
+    async fn manager(
+        repo: &EphemeralRepository<GitRepository>,
+        global: GlobalConfig,
+    ) -> eyre::Result<BumpVersion<GitRepository, NoOpLogger>> {
+        let config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                ..global
+            },
+            files: vec![(InputFile::Path("VERSION".into()), FileConfig::empty())],
+            ..Config::default()
+        };
+        manager_with(GitRepository::open(repo.path())?, LocalFileSystem, config).await
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_apply() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let version_file = repo.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3\n").await?;
+
+        let plan = manager(
+            &repo,
+            GlobalConfig {
+                tag: Some(true),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?
+        .plan(Bump::Component("minor"))
+        .await?;
+        sim_assert_eq!(plan.new_version_serialized, "1.3.0");
+        sim_assert_eq!(plan.files.len(), 1);
+        let modification = plan.files[0].modification.as_ref().unwrap();
+        sim_assert_eq!(modification.after, "1.3.0\n");
+        let tag = plan.tag.as_ref().unwrap();
+        sim_assert_eq!((tag.name.as_str(), tag.exists), ("v1.3.0", false));
+
+        // planning does not write any files
+        sim_assert_eq!(tokio::fs::read_to_string(&version_file).await?, "1.2.3\n");
+
+        let serialized = serde_json::to_value(&plan)?;
+        sim_assert_eq!(
+            serialized["new_version"],
+            serde_json::json!({ "major": "1", "minor": "3", "patch": "0" })
+        );
+        sim_assert_eq!(serialized["commit"], serde_json::Value::Null);
+
+        let manager = manager(&repo, GlobalConfig::empty()).await?;
+        let plan = manager.plan(Bump::Component("minor")).await?;
+        manager.apply(&plan).await?;
+        sim_assert_eq!(tokio::fs::read_to_string(&version_file).await?, "1.3.0\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_restores_files_when_pre_commit_hook_fails() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let version_file = repo.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3\n").await?;
+
+        let manager = manager(
+            &repo,
+            GlobalConfig {
+                pre_commit_hooks: Some(vec!["false".to_string()]),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?;
+        let plan = manager.plan(Bump::Component("patch")).await?;
+        let err = manager.apply(&plan).await.unwrap_err();
+        assert!(matches!(err, crate::BumpError::Hook(_)), "{err:?}");
+        sim_assert_eq!(tokio::fs::read_to_string(&version_file).await?, "1.2.3\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bump_runs_setup_hooks_before_planning() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let version_file = repo.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3\n").await?;
+
+        let manager = manager(
+            &repo,
+            GlobalConfig {
+                setup_hooks: Some(vec![r#""echo generated >> VERSION""#.to_string()]),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?;
+        manager.bump(Bump::Component("patch")).await?;
+        sim_assert_eq!(
+            tokio::fs::read_to_string(&version_file).await?,
+            "1.2.4\ngenerated\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_fails_when_files_changed_since_planned() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let version_file = repo.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3\n").await?;
+
+        let manager = manager(&repo, GlobalConfig::empty()).await?;
+        let plan = manager.plan(Bump::Component("patch")).await?;
+        tokio::fs::write(&version_file, "1.2.3\nchanged\n").await?;
+
+        let err = manager.apply(&plan).await.unwrap_err();
+        assert!(
+            matches!(err, crate::BumpError::ChangedSincePlanned { ref path } if *path == version_file),
+            "{err:?}"
+        );
+        sim_assert_eq!(
+            tokio::fs::read_to_string(&version_file).await?,
+            "1.2.3\nchanged\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_unstages_files_when_commit_fails() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let version_file = repo.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3\n").await?;
+
+        let git = |args: &[&str]| {
+            let mut cmd = Command::new("git");
+            cmd.args(args).current_dir(repo.path());
+            async move { run_command(&mut cmd).await.map(|output| output.stdout) }
+        };
+        for args in [
+            &["config", "user.name", "bumpversion"][..],
+            &["config", "user.email", "bumpversion@example.com"],
+            &["add", "VERSION"],
+            &["commit", "-m", "initial commit"],
+        ] {
+            git(args).await?;
+        }
+
+        let manager = manager(
+            &repo,
+            GlobalConfig {
+                commit: Some(true),
+                commit_args: Some("--invalid-option".to_string()),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?;
+        let plan = manager.plan(Bump::Component("patch")).await?;
+        let err = manager.apply(&plan).await.unwrap_err();
+        assert!(matches!(err, crate::BumpError::VCS(_)), "{err:?}");
+        sim_assert_eq!(tokio::fs::read_to_string(&version_file).await?, "1.2.3\n");
+        sim_assert_eq!(git(&["diff", "--cached", "--name-only"]).await?, "");
+        sim_assert_eq!(git(&["status", "--porcelain"]).await?, "");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_apply_changelog() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let changelog_file = repo.path().join("CHANGELOG.md");
+        tokio::fs::write(repo.path().join("VERSION"), "1.2.3\n").await?;
+        tokio::fs::write(&changelog_file, "# Changelog\n").await?;
+
+        for args in [
+            &["config", "user.name", "bumpversion"][..],
+            &["config", "user.email", "bumpversion@example.com"],
+            &["add", "VERSION", "CHANGELOG.md"],
+            &["commit", "-m", "feat(cli): add changelog"],
+            &["commit", "--allow-empty", "-m", "docs: document changelog"],
+        ] {
+            run_command(Command::new("git").args(args).current_dir(repo.path())).await?;
+        }
+
+        let mut manager = manager(
+            &repo,
+            GlobalConfig {
+                commit: Some(true),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?;
+        manager.config.changelog = Some(ChangelogConfig::default().finalize());
+
+        let plan = manager.plan(Bump::Component("minor")).await?;
+        let changelog = plan.changelog.as_ref().unwrap();
+        let after = &changelog.modification.as_ref().unwrap().after;
+        let release = after.strip_prefix("# Changelog\n\n## 1.3.0 (").unwrap();
+        let (_, entries) = release.split_once(")\n").unwrap();
+        assert!(
+            entries.starts_with("\n### Features\n\n- add changelog ("),
+            "{after}"
+        );
+        sim_assert_eq!(entries.lines().count(), 4);
+        assert!(
+            plan.commit
+                .as_ref()
+                .unwrap()
+                .files
+                .contains(&changelog.path),
+            "{plan:?}"
+        );
+
+        manager.apply(&plan).await?;
+        sim_assert_eq!(&tokio::fs::read_to_string(&changelog_file).await?, after);
+        sim_assert_eq!(repo.dirty_files().await?, Vec::<std::path::PathBuf>::new());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_commits_created_changelog() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let changelog_file = repo.path().join("CHANGELOG.md");
+        tokio::fs::write(repo.path().join("VERSION"), "1.2.3\n").await?;
+
+        let git = |args: &[&str]| {
+            let mut cmd = Command::new("git");
+            cmd.args(args).current_dir(repo.path());
+            async move { run_command(&mut cmd).await.map(|output| output.stdout) }
+        };
+        for args in [
+            &["config", "user.name", "bumpversion"][..],
+            &["config", "user.email", "bumpversion@example.com"],
+            &["add", "VERSION"],
+            &["commit", "-m", "feat: initial release"],
+        ] {
+            git(args).await?;
+        }
+
+        let mut manager = manager(
+            &repo,
+            GlobalConfig {
+                commit: Some(true),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?;
+        manager.config.changelog = Some(ChangelogConfig::default().finalize());
+
+        let plan = manager.plan(Bump::Component("minor")).await?;
+        assert!(
+            plan.changelog
+                .as_ref()
+                .is_some_and(|changelog| changelog.created),
+            "{plan:?}"
+        );
+        manager.apply(&plan).await?;
+
+        // the created changelog is part of the bump commit
+        assert!(tokio::fs::try_exists(&changelog_file).await?);
+        sim_assert_eq!(
+            git(&["show", "--name-only", "--format=", "HEAD"]).await?,
+            "CHANGELOG.md\nVERSION\n"
+        );
+        sim_assert_eq!(git(&["status", "--porcelain"]).await?, "");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_pushes_commit_and_tag() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let remote = tempfile::TempDir::with_prefix("remote")?;
+        tokio::fs::write(repo.path().join("VERSION"), "1.2.3\n").await?;
+
+        let git = |args: &[&str], dir: &std::path::Path| {
+            let mut cmd = Command::new("git");
+            cmd.args(args).current_dir(dir);
+            async move { run_command(&mut cmd).await.map(|output| output.stdout) }
+        };
+        git(&["init", "--bare"], remote.path()).await?;
+        for args in [
+            &["config", "user.name", "bumpversion"][..],
+            &["config", "user.email", "bumpversion@example.com"],
+            &[
+                "remote",
+                "add",
+                "upstream",
+                &remote.path().to_string_lossy(),
+            ],
+            &["add", "VERSION"],
+            &["commit", "-m", "initial commit"],
+        ] {
+            git(args, repo.path()).await?;
+        }
+
+        let manager = manager(
+            &repo,
+            GlobalConfig {
+                commit: Some(true),
+                tag: Some(true),
+                push: Some(true),
+                push_remote: Some("upstream".to_string()),
+                ..GlobalConfig::empty()
+            },
+        )
+        .await?;
+        let plan = manager.plan(Bump::Component("patch")).await?;
+        let push = plan.push.as_ref().unwrap();
+        sim_assert_eq!(
+            (push.remote.as_deref(), push.tag.as_deref()),
+            (Some("upstream"), Some("v1.2.4"))
+        );
+        manager.apply(&plan).await?;
+
+        let head = git(&["rev-parse", "HEAD"], repo.path()).await?;
+        let branch = git(&["symbolic-ref", "--short", "HEAD"], repo.path()).await?;
+        sim_assert_eq!(
+            git(&["rev-parse", branch.trim()], remote.path()).await?,
+            head
+        );
+        sim_assert_eq!(
+            git(&["rev-parse", "v1.2.4^{commit}"], remote.path()).await?,
+            head
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plan_rejects_features_unsupported_by_gitoxide() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let version_file = repo.path().join("VERSION");
+        tokio::fs::write(&version_file, "1.2.3\n").await?;
+
+        let bump_err = async |global: GlobalConfig| -> eyre::Result<gitoxide::Error> {
+            let config = Config {
+                global: GlobalConfig {
+                    current_version: Some("1.2.3".to_string()),
+                    ..global
+                },
+                files: vec![(InputFile::Path("VERSION".into()), FileConfig::empty())],
+                ..Config::default()
+            };
+            let repo = GitoxideRepository::open(repo.path())?;
+            let manager = manager_with(repo, LocalFileSystem, config).await?;
+            let err = manager.bump(Bump::Component("patch")).await.unwrap_err();
+
+            // nothing is written
+            sim_assert_eq!(tokio::fs::read_to_string(&version_file).await?, "1.2.3\n");
+            match err {
+                crate::BumpError::VCS(err) => Ok(err),
+                err => Err(eyre::eyre!("unexpected error: {err:?}")),
+            }
+        };
+
+        let err = bump_err(GlobalConfig {
+            push: Some(true),
+            ..GlobalConfig::empty()
+        })
+        .await?;
+        assert!(matches!(err, gitoxide::Error::PushUnsupported), "{err:?}");
+
+        let err = bump_err(GlobalConfig {
+            tag: Some(true),
+            sign_tags: Some(true),
+            ..GlobalConfig::empty()
+        })
+        .await?;
+        assert!(
+            matches!(err, gitoxide::Error::SignedTagsUnsupported),
+            "{err:?}"
+        );
+
+        let err = bump_err(GlobalConfig {
+            commit: Some(true),
+            commit_args: Some("--no-verify --signoff".to_string()),
+            ..GlobalConfig::empty()
+        })
+        .await?;
+        assert!(
+            matches!(&err, gitoxide::Error::UnsupportedCommitArgs(args) if args == &["--signoff"]),
+            "{err:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_in_memory() -> eyre::Result<()> {
+        crate::tests::init();
+        let version_file = PathBuf::from("/repo/VERSION");
+        let changelog_file = PathBuf::from("/repo/CHANGELOG.md");
+        let fs: MemoryFileSystem = [
+            (version_file.clone(), "1.2.3\n".to_string()),
+            (changelog_file.clone(), "# Changelog\n".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let repo = MemoryRepository::open("/repo")?;
+        repo.commit("feat: initial release", ["--allow-empty"], no_env())
+            .await?;
+        repo.tag("v1.2.3", None, false).await?;
+        repo.commit("fix: handle empty repos", ["--allow-empty"], no_env())
+            .await?;
+
+        let config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                commit: Some(true),
+                tag: Some(true),
+                push: Some(true),
+                ..GlobalConfig::empty()
+            },
+            files: vec![(InputFile::Path(version_file.clone()), FileConfig::empty())],
+            changelog: Some(ChangelogConfig {
+                path: Some(changelog_file.clone()),
+                ..ChangelogConfig::default()
+            }),
+            ..Config::default()
+        };
+        let manager = manager_with(repo, fs, config).await?;
+        manager.bump(Bump::Component("patch")).await?;
+
+        let written: Vec<_> = manager
+            .fs
+            .writes()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        sim_assert_eq!(written, vec![version_file.clone(), changelog_file.clone()]);
+        sim_assert_eq!(manager.fs.get(&version_file).as_deref(), Some("1.2.4\n"));
+        assert!(
+            manager
+                .fs
+                .get(&changelog_file)
+                .is_some_and(|changelog| changelog.contains("- handle empty repos")),
+        );
+
+        let state = manager.repo.state();
+        let commit = state.commits.last().unwrap();
+        sim_assert_eq!(commit.message, "Bump version: 1.2.3 → 1.2.4");
+        sim_assert_eq!(commit.files, vec![changelog_file, version_file]);
+        sim_assert_eq!(
+            state
+                .tags
+                .iter()
+                .map(|tag| (tag.name.as_str(), tag.target.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("v1.2.3", state.commits[0].sha.as_str()),
+                ("v1.2.4", commit.sha.as_str()),
+            ]
+        );
+        sim_assert_eq!(
+            state.pushes,
+            vec![memory::Push {
+                remote: None,
+                tag: Some("v1.2.4".to_string()),
+                args: vec![],
+            }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_file_that_is_a_configured_file() -> eyre::Result<()> {
+        crate::tests::init();
+        let pyproject_file = PathBuf::from("/repo/pyproject.toml");
+        let pyproject_toml = indoc::indoc! {r#"
+            [project]
+            name = "example"
+            version = "1.2.3"
+
+            [tool.bumpversion]
+            current_version = "1.2.3"
+        "#};
+        let fs: MemoryFileSystem = [(pyproject_file.clone(), pyproject_toml.to_string())]
+            .into_iter()
+            .collect();
+        let repo = MemoryRepository::open("/repo")?;
+
+        let config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                ..GlobalConfig::empty()
+            },
+            files: vec![(
+                InputFile::Path(pyproject_file.clone()),
+                FileConfig {
+                    key_path: Some("project.version".to_string()),
+                    ..FileConfig::empty()
+                },
+            )],
+            ..Config::default()
+        };
+        let mut manager = manager_with(repo, fs, config).await?;
+        manager.config_file = Some(config::ConfigFile::PyProject(pyproject_file.clone()));
+        let plan = manager.plan(Bump::Component("minor")).await?;
+        sim_assert_eq!(plan.modifications().count(), 1);
+        manager.apply(&plan).await?;
+
+        // both the configured key and the current version of the config are bumped
+        sim_assert_eq!(
+            manager.fs.get(&pyproject_file).as_deref(),
+            Some(pyproject_toml.replace("1.2.3", "1.3.0").as_str())
+        );
+        sim_assert_eq!(manager.fs.writes().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_creates_missing_changelog() -> eyre::Result<()> {
+        crate::tests::init();
+        let version_file = PathBuf::from("/repo/VERSION");
+        let changelog_file = PathBuf::from("/repo/CHANGELOG.md");
+        let fs: MemoryFileSystem = [(version_file.clone(), "1.2.3\n".to_string())]
+            .into_iter()
+            .collect();
+        let repo = MemoryRepository::open("/repo")?;
+        repo.commit("feat: initial release", ["--allow-empty"], no_env())
+            .await?;
+
+        let config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                commit: Some(true),
+                pre_commit_hooks: Some(vec!["false".to_string()]),
+                ..GlobalConfig::empty()
+            },
+            files: vec![(InputFile::Path(version_file.clone()), FileConfig::empty())],
+            changelog: Some(ChangelogConfig {
+                path: Some(changelog_file.clone()),
+                ..ChangelogConfig::default()
+            }),
+            ..Config::default()
+        };
+        let mut manager = manager_with(repo, fs, config).await?;
+
+        // the created changelog is removed if committing fails
+        let plan = manager.plan(Bump::Component("minor")).await?;
+        assert!(
+            plan.changelog
+                .as_ref()
+                .is_some_and(|changelog| changelog.created)
+        );
+        let err = manager.apply(&plan).await.unwrap_err();
+        assert!(matches!(err, crate::BumpError::Hook(_)), "{err:?}");
+        sim_assert_eq!(manager.fs.get(&changelog_file), None);
+        sim_assert_eq!(manager.fs.get(&version_file).as_deref(), Some("1.2.3\n"));
+
+        manager.config.global.pre_commit_hooks = vec![];
+        manager.bump(Bump::Component("minor")).await?;
+        let changelog = manager.fs.get(&changelog_file).unwrap_or_default();
+        assert!(
+            changelog.starts_with("## 1.3.0 (") && changelog.contains("- initial release ("),
+            "{changelog}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plan_with_context_variables() -> eyre::Result<()> {
+        crate::tests::init();
+        let version_file = PathBuf::from("/repo/VERSION");
+        let fs: MemoryFileSystem = [(version_file.clone(), "1.2.3\n".to_string())]
+            .into_iter()
+            .collect();
+        let repo = MemoryRepository::open("/repo")?;
+        repo.commit("initial commit", ["--allow-empty"], no_env())
+            .await?;
+
+        let variables = [
+            ("docker_tag", "{release}-{short_branch_name}"),
+            ("release", "v{new_version}"),
+        ]
+        .into_iter()
+        .map(|(name, value)| Ok((name.to_string(), PythonFormatString::parse(value)?)))
+        .collect::<eyre::Result<_>>()?;
+        let config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2.3".to_string()),
+                commit: Some(true),
+                tag: Some(true),
+                tag_name: Some(PythonFormatString::parse("{release}")?),
+                commit_message: Some(PythonFormatString::parse("Release {docker_tag}")?),
+                ..GlobalConfig::empty()
+            },
+            files: vec![(InputFile::Path(version_file.clone()), FileConfig::empty())],
+            context: variables,
+            ..Config::default()
+        };
+        let manager = manager_with(repo, fs, config).await?;
+        let plan = manager.plan(Bump::Component("minor")).await?;
+        sim_assert_eq!(
+            plan.commit.as_ref().map(|commit| commit.message.as_str()),
+            Some("Release v1.3.0-main")
+        );
+        sim_assert_eq!(
+            plan.tag.as_ref().map(|tag| tag.name.as_str()),
+            Some("v1.3.0")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,801 @@
+//! Inspection of the resolved configuration, versions, and template context.
+//!
+//! Collects the state as a JSON [`Value`] that can be queried by dotted field names
+//! and rendered in a human-readable, YAML, or JSON format.
+use crate::{
+    Bump,
+    config::{self, InputFile},
+    context,
+    data_file::json_string,
+    plan::{NewVersionError, new_version},
+    vcs::TagAndRevision,
+    version::{self, Version},
+};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Errors that can occur when showing the current state.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("missing current version")]
+    MissingCurrentVersion,
+    #[error("failed to parse version {0:?}")]
+    InvalidVersion(String),
+    #[error("unknown field {0:?}")]
+    UnknownField(String),
+    #[error("failed to bump version")]
+    Bump(#[from] version::BumpError),
+    #[error("failed to serialize version")]
+    Serialize(#[from] version::SerializeError),
+    #[error("failed to serialize state")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Context(#[from] context::Error),
+}
+
+impl From<NewVersionError> for Error {
+    fn from(err: NewVersionError) -> Self {
+        match err {
+            NewVersionError::InvalidVersion(version) => Self::InvalidVersion(version),
+            NewVersionError::Bump(err) => Self::Bump(err),
+            NewVersionError::Serialize(err) => Self::Serialize(err),
+            NewVersionError::Context(err) => Self::Context(err),
+        }
+    }
+}
+
+/// Get the value of a dotted `field`, e.g. `scm_info.branch_name` or `files.0.filename`.
+#[must_use]
+pub fn get<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+    field.split('.').try_fold(value, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(list) => key.parse::<usize>().ok().and_then(|idx| list.get(idx)),
+        _ => None,
+    })
+}
+
+/// Select the given `fields` of a `value`.
+///
+/// - No fields select the complete value.
+/// - A single field selects only its value.
+/// - Multiple fields select a map of the fields and their values.
+///
+/// # Errors
+/// Returns `Error::UnknownField` if a field does not exist.
+pub fn select(value: &Value, fields: &[impl AsRef<str>]) -> Result<Value, Error> {
+    let get = |field: &str| {
+        get(value, field)
+            .cloned()
+            .ok_or_else(|| Error::UnknownField(field.to_string()))
+    };
+    match fields {
+        [] => Ok(value.clone()),
+        [field] => get(field.as_ref()),
+        fields => fields
+            .iter()
+            .map(|field| Ok((field.as_ref().to_string(), get(field.as_ref())?)))
+            .collect::<Result<serde_json::Map<_, _>, _>>()
+            .map(Value::Object),
+    }
+}
+
+/// Render a `value` in the given `format`.
+#[must_use]
+pub fn render(value: &Value, format: OutputFormat) -> String {
+    match (format, value) {
+        (OutputFormat::Default, Value::String(value)) => value.clone(),
+        (OutputFormat::Default, value) if is_scalar(value) => value.to_string(),
+        (OutputFormat::Default | OutputFormat::Yaml, value) => {
+            let mut out = String::new();
+            write_yaml(value, 0, &mut out);
+            out.trim_end_matches('\n').to_string()
+        }
+        (OutputFormat::Json, value) => {
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+        }
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    match value {
+        Value::Array(list) => list.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => true,
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::String(value) => yaml_string(value),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn write_yaml(value: &Value, indent: usize, out: &mut String) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let _ = write!(out, "{pad}{}:", yaml_string(key));
+                if is_scalar(value) {
+                    let _ = writeln!(out, " {}", yaml_scalar(value));
+                } else {
+                    out.push('\n');
+                    write_yaml(value, indent + 2, out);
+                }
+            }
+        }
+        Value::Array(list) if !list.is_empty() => {
+            for value in list {
+                if is_scalar(value) {
+                    let _ = writeln!(out, "{pad}- {}", yaml_scalar(value));
+                } else {
+                    // render the nested block and start its first line with the item marker
+                    let mut item = String::new();
+                    write_yaml(value, indent + 2, &mut item);
+                    out.push_str(&pad);
+                    out.push_str("- ");
+                    out.push_str(&item[indent + 2..]);
+                }
+            }
+        }
+        scalar => {
+            out.push_str(&pad);
+            out.push_str(&yaml_scalar(scalar));
+            out.push('\n');
+        }
+    }
+}
+
+/// Plain scalars that YAML 1.1 or 1.2 read as numbers or timestamps instead of strings.
+static YAML_NON_STRING_REGEX: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+    regex::Regex::new(
+        r"(?x)
+        ^[-+]?(
+            [0-9][0-9_]*(\.[0-9_]*)?([eE][-+]?[0-9]+)?
+            | \.[0-9][0-9_]*([eE][-+]?[0-9]+)?
+            | 0x[0-9a-fA-F_]+ | 0o?[0-7_]+ | 0b[01_]+
+            | [0-9][0-9_]*(:[0-5]?[0-9])+(\.[0-9_]*)?
+            | \.(inf|Inf|INF|nan|NaN|NAN)
+        )$
+        | ^[0-9]{4}-[0-9]{1,2}-[0-9]{1,2}([Tt\ ]|$)",
+    )
+    .unwrap()
+});
+
+/// Quote a string for YAML if it would otherwise not be read back as the same string.
+fn yaml_string(value: &str) -> String {
+    let is_reserved = matches!(
+        value.to_ascii_lowercase().as_str(),
+        "" | "~" | "null" | "true" | "false" | "y" | "n" | "yes" | "no" | "on" | "off" | "<<" | "="
+    );
+    let has_indicator = value.starts_with([
+        '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@',
+        '`', ' ',
+    ]) || value.ends_with([' ', ':']);
+    let has_special =
+        value.contains(": ") || value.contains(" #") || value.chars().any(char::is_control);
+    if is_reserved || has_indicator || has_special || YAML_NON_STRING_REGEX.is_match(value) {
+        json_string(value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Output format of the `show` command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// Plain values and YAML for structured values.
+    #[default]
+    Default,
+    Yaml,
+    Json,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("invalid output format {0:?} (expected one of default, yaml, json)")]
+pub struct InvalidOutputFormatError(String);
+
+impl std::str::FromStr for OutputFormat {
+    type Err = InvalidOutputFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            _ => Err(InvalidOutputFormatError(s.to_string())),
+        }
+    }
+}
+
+fn format_strings(patterns: &[crate::f_string::PythonFormatString]) -> Vec<String> {
+    patterns.iter().map(ToString::to_string).collect()
+}
+
+/// The input of a file configuration, keyed as in the configuration file.
+#[derive(Debug, serde::Serialize)]
+#[serde(untagged)]
+enum FileInput<'a> {
+    Path {
+        filename: &'a std::path::Path,
+    },
+    GlobPattern {
+        glob: &'a str,
+        glob_exclude: &'a Option<Vec<String>>,
+    },
+}
+
+/// A file configuration, keyed as in the configuration file.
+#[derive(Debug, serde::Serialize)]
+struct FileState<'a> {
+    #[serde(flatten)]
+    input: FileInput<'a>,
+    parse: String,
+    serialize: Vec<String>,
+    search: String,
+    regex: bool,
+    replace: &'a str,
+    ignore_missing_file: bool,
+    ignore_missing_version: bool,
+    key_path: &'a Option<String>,
+}
+
+impl<'a> FileState<'a> {
+    fn new(file: &'a InputFile, config: &'a config::FinalizedFileConfig) -> Self {
+        let input = match file {
+            InputFile::Path(path) => FileInput::Path { filename: path },
+            InputFile::GlobPattern {
+                pattern,
+                exclude_patterns,
+            } => FileInput::GlobPattern {
+                glob: pattern,
+                glob_exclude: exclude_patterns,
+            },
+        };
+        Self {
+            input,
+            parse: config.parse_version_pattern.to_string(),
+            serialize: format_strings(&config.serialize_version_patterns),
+            search: config.search.to_string(),
+            regex: config.search.is_regex(),
+            replace: &config.replace,
+            ignore_missing_file: config.ignore_missing_file,
+            ignore_missing_version: config.ignore_missing_version,
+            key_path: &config.key_path,
+        }
+    }
+}
+
+/// Information about the repository, keyed as in the template context.
+#[derive(Debug, serde::Serialize)]
+struct ScmInfo<'a> {
+    tool: Option<&'a str>,
+    commit_sha: Option<&'a str>,
+    distance_to_latest_tag: Option<usize>,
+    current_tag: Option<&'a str>,
+    current_version: Option<&'a str>,
+    dirty: Option<bool>,
+    branch_name: Option<&'a str>,
+    short_branch_name: Option<&'a str>,
+    repository_root: Option<&'a std::path::Path>,
+}
+
+impl<'a> ScmInfo<'a> {
+    fn new(tag_and_revision: &'a TagAndRevision) -> Self {
+        let TagAndRevision {
+            tool,
+            tag,
+            revision,
+        } = tag_and_revision;
+        Self {
+            tool: tool.as_deref(),
+            commit_sha: tag.as_ref().map(|tag| tag.commit_sha.as_str()),
+            distance_to_latest_tag: tag.as_ref().map(|tag| tag.distance_to_latest_tag),
+            current_tag: tag.as_ref().map(|tag| tag.current_tag.as_str()),
+            current_version: tag.as_ref().map(|tag| tag.current_version.as_str()),
+            dirty: tag.as_ref().map(|tag| tag.dirty),
+            branch_name: revision
+                .as_ref()
+                .map(|revision| revision.branch_name.as_str()),
+            short_branch_name: revision
+                .as_ref()
+                .map(|revision| revision.short_branch_name.as_str()),
+            repository_root: revision
+                .as_ref()
+                .map(|revision| revision.repository_root.as_path()),
+        }
+    }
+}
+
+/// The global configuration, keyed as in the configuration file.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, serde::Serialize)]
+struct GlobalState<'a> {
+    allow_dirty: bool,
+    current_version: &'a Option<String>,
+    parse: String,
+    serialize: Vec<String>,
+    search: String,
+    regex: bool,
+    replace: &'a str,
+    no_configured_files: bool,
+    ignore_missing_files: bool,
+    ignore_missing_version: bool,
+    dry_run: bool,
+    commit: bool,
+    tag: bool,
+    sign_tags: bool,
+    tag_name: String,
+    tag_message: String,
+    message: String,
+    commit_args: &'a Option<String>,
+    push: bool,
+    push_remote: &'a Option<String>,
+    push_args: &'a Option<String>,
+    auto_bump_breaking: &'a str,
+    auto_bump_feat: &'a str,
+    auto_bump_fix: &'a str,
+    setup_hooks: &'a [String],
+    pre_commit_hooks: &'a [String],
+    post_commit_hooks: &'a [String],
+    additional_files: &'a Option<Vec<PathBuf>>,
+}
+
+impl<'a> GlobalState<'a> {
+    fn new(global: &'a config::GlobalConfigFinalized) -> Self {
+        Self {
+            allow_dirty: global.allow_dirty,
+            current_version: &global.current_version,
+            parse: global.parse_version_pattern.to_string(),
+            serialize: format_strings(&global.serialize_version_patterns),
+            search: global.search.to_string(),
+            regex: global.search.is_regex(),
+            replace: &global.replace,
+            no_configured_files: global.no_configured_files,
+            ignore_missing_files: global.ignore_missing_files,
+            ignore_missing_version: global.ignore_missing_version,
+            dry_run: global.dry_run,
+            commit: global.commit,
+            tag: global.tag,
+            sign_tags: global.sign_tags,
+            tag_name: global.tag_name.to_string(),
+            tag_message: global.tag_message.to_string(),
+            message: global.commit_message.to_string(),
+            commit_args: &global.commit_args,
+            push: global.push,
+            push_remote: &global.push_remote,
+            push_args: &global.push_args,
+            auto_bump_breaking: &global.auto_bump_breaking,
+            auto_bump_feat: &global.auto_bump_feat,
+            auto_bump_fix: &global.auto_bump_fix,
+            setup_hooks: &global.setup_hooks,
+            pre_commit_hooks: &global.pre_commit_hooks,
+            post_commit_hooks: &global.post_commit_hooks,
+            additional_files: &global.additional_files,
+        }
+    }
+}
+
+/// The complete state shown by the `show` command.
+#[derive(Debug, serde::Serialize)]
+struct State<'a> {
+    #[serde(flatten)]
+    global: GlobalState<'a>,
+    files: Vec<FileState<'a>>,
+    parts: &'a config::VersionComponentConfigs,
+    current_version_components: Option<&'a Version>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_version: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_version_components: Option<&'a Version>,
+    scm_info: ScmInfo<'a>,
+    context: BTreeMap<String, String>,
+}
+
+fn parse_version(
+    version: &str,
+    parse_version_pattern: &regex::Regex,
+    version_spec: &version::VersionSpec,
+) -> Result<Version, Error> {
+    Version::parse(version, parse_version_pattern, version_spec)
+        .ok_or_else(|| Error::InvalidVersion(version.to_string()))
+}
+
+/// Collect the resolved configuration, versions, and template context.
+///
+/// If `bump` is given, the new version and its components are included as well.
+///
+/// # Errors
+/// - When the current version cannot be parsed.
+/// - When a `bump` is given but there is no current version.
+/// - When the new version cannot be computed or serialized.
+pub fn state(
+    config: &config::FinalizedConfig,
+    components: &config::VersionComponentConfigs,
+    tag_and_revision: &TagAndRevision,
+    bump: Option<Bump<'_>>,
+) -> Result<Value, Error> {
+    let global = &config.global;
+    let parse_version_pattern = &global.parse_version_pattern;
+    let version_spec = version::VersionSpec::from_components(components.clone());
+    let current_version_serialized = global.current_version.as_deref();
+    let current_version = current_version_serialized
+        .map(|version| parse_version(version, parse_version_pattern, &version_spec))
+        .transpose()?;
+
+    let new_version = match bump {
+        None => None,
+        Some(bump) => {
+            let (current_version, current_version_serialized) = current_version
+                .as_ref()
+                .zip(current_version_serialized)
+                .ok_or(Error::MissingCurrentVersion)?;
+            Some(new_version(
+                config,
+                &version_spec,
+                current_version,
+                current_version_serialized,
+                tag_and_revision,
+                bump,
+            )?)
+        }
+    };
+
+    let mut ctx: HashMap<String, String> = context::get_context(
+        Some(tag_and_revision),
+        current_version.as_ref(),
+        new_version.as_ref().map(|(version, _)| version),
+        current_version_serialized,
+        new_version
+            .as_ref()
+            .map(|(_, serialized)| serialized.as_str()),
+    )
+    .collect();
+    context::render_variables(&config.context, &mut ctx)?;
+    let ctx: BTreeMap<String, String> = ctx
+        .into_iter()
+        // do not leak environment variables
+        .filter(|(key, _)| !key.starts_with('$'))
+        .collect();
+
+    let state = State {
+        global: GlobalState::new(global),
+        files: config
+            .files
+            .iter()
+            .map(|(file, file_config)| FileState::new(file, file_config))
+            .collect(),
+        parts: components,
+        current_version_components: current_version.as_ref(),
+        new_version: new_version
+            .as_ref()
+            .map(|(_, serialized)| serialized.as_str()),
+        new_version_components: new_version.as_ref().map(|(version, _)| version),
+        scm_info: ScmInfo::new(tag_and_revision),
+        context: ctx,
+    };
+    Ok(serde_json::to_value(state)?)
+}
+
+/// The possible next versions when bumping each of the version components.
+#[derive(Debug)]
+pub struct BumpPreview {
+    /// The current version.
+    pub current_version: String,
+    /// The new version for each version component, or the reason it cannot be bumped.
+    pub bumps: Vec<(String, Result<String, Error>)>,
+}
+
+/// Bump each of the version `components` of the current version.
+///
+/// Failing bumps are reported per component instead of aborting the preview.
+///
+/// # Errors
+/// Returns an error if there is no current version or it cannot be parsed.
+pub fn preview_bumps(
+    config: &config::FinalizedConfig,
+    components: &config::VersionComponentConfigs,
+    tag_and_revision: &TagAndRevision,
+) -> Result<BumpPreview, Error> {
+    let current_version_serialized = config
+        .global
+        .current_version
+        .as_deref()
+        .ok_or(Error::MissingCurrentVersion)?;
+    let version_spec = version::VersionSpec::from_components(components.clone());
+    let current_version = parse_version(
+        current_version_serialized,
+        &config.global.parse_version_pattern,
+        &version_spec,
+    )?;
+    let bumps = components
+        .keys()
+        .map(|component| {
+            let new_version = new_version(
+                config,
+                &version_spec,
+                &current_version,
+                current_version_serialized,
+                tag_and_revision,
+                Bump::Component(component),
+            )
+            .map(|(_, serialized)| serialized)
+            .map_err(Error::from);
+            (component.clone(), new_version)
+        })
+        .collect();
+    Ok(BumpPreview {
+        current_version: current_version_serialized.to_string(),
+        bumps,
+    })
+}
+
+/// Join an error and all of its sources.
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        let _ = write!(message, ": {err}");
+        source = err.source();
+    }
+    message
+}
+
+impl std::fmt::Display for BumpPreview {
+    /// Render the bumps as a tree, e.g.
+    ///
+    /// ```text
+    /// 1.2.3 ─┬─ major ─ 2.0.0
+    ///        ├─ minor ─ 1.3.0
+    ///        └─ patch ─ 1.2.4
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.current_version)?;
+        let pad = " ".repeat(self.current_version.chars().count());
+        for (idx, (component, new_version)) in self.bumps.iter().enumerate() {
+            let branch = match (idx, self.bumps.len()) {
+                (0, 1) => " ───",
+                (0, _) => " ─┬─",
+                (idx, len) if idx + 1 == len => " └─",
+                _ => " ├─",
+            };
+            if idx > 0 {
+                write!(f, "\n{pad} ")?;
+            }
+            write!(f, "{branch} {component} ─ ")?;
+            match new_version {
+                Ok(new_version) => write!(f, "{new_version}")?,
+                Err(err) => write!(f, "error: {}", error_chain(err))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OutputFormat, get, render, select};
+    use crate::{
+        Bump,
+        config::{self, Config, GlobalConfig},
+        f_string::PythonFormatString,
+        vcs::{RevisionInfo, TagAndRevision},
+    };
+    use color_eyre::eyre;
+    use serde_json::{Value, json};
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    fn finalized_config(current_version: &str) -> config::FinalizedConfig {
+        Config {
+            global: GlobalConfig {
+                current_version: Some(current_version.to_string()),
+                ..GlobalConfig::empty()
+            },
+            ..Config::default()
+        }
+        .finalize()
+    }
+
+    #[test]
+    fn test_show_versions() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let config = finalized_config("1.2.3");
+        let components = config::version::version_component_configs(&config);
+        let tag_and_revision = TagAndRevision {
+            tool: Some("git".to_string()),
+            tag: None,
+            revision: Some(RevisionInfo {
+                branch_name: "main".to_string(),
+                ..RevisionInfo::default()
+            }),
+        };
+        let state = super::state(
+            &config,
+            &components,
+            &tag_and_revision,
+            Some(Bump::Component("minor")),
+        )?;
+
+        let selected = select(&state, &["current_version", "new_version"])?;
+        sim_assert_eq!(
+            render(&selected, OutputFormat::Json),
+            indoc::indoc! {r#"
+                {
+                  "current_version": "1.2.3",
+                  "new_version": "1.3.0"
+                }"#}
+        );
+        sim_assert_eq!(
+            render(&select(&state, &["new_version"])?, OutputFormat::Default),
+            "1.3.0"
+        );
+        sim_assert_eq!(
+            render(
+                &select(&state, &["new_version_components"])?,
+                OutputFormat::Default
+            ),
+            indoc::indoc! {r#"
+                major: "1"
+                minor: "3"
+                patch: "0""#}
+        );
+        sim_assert_eq!(
+            select(&state, &["scm_info.branch_name"])?,
+            Value::String("main".to_string())
+        );
+        sim_assert_eq!(
+            select(&state, &["scm_info.tool", "context.tool"])?,
+            json!({"scm_info.tool": "git", "context.tool": "git"})
+        );
+        sim_assert_eq!(
+            select(&state, &["context.current_minor", "context.new_minor"])?,
+            json!({"context.current_minor": "2", "context.new_minor": "3"})
+        );
+        assert!(get(&state, "context.$HOME").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_show_unknown_field() {
+        crate::tests::init();
+
+        let config = finalized_config("1.2.3");
+        let components = config::version::version_component_configs(&config);
+        let state =
+            super::state(&config, &components, &TagAndRevision::default(), None).expect("state");
+        assert!(get(&state, "new_version").is_none());
+        assert!(matches!(
+            select(&state, &["missing"]),
+            Err(super::Error::UnknownField(field)) if field == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_render_yaml() {
+        crate::tests::init();
+
+        let value = json!({
+            "version": "1.2.3",
+            "empty": "",
+            "number": "1.2",
+            "flag": true,
+            "missing": null,
+            "list": ["a", "b: c"],
+            "none": [],
+            "nested": [{"name": "major", "value": "1"}, ["x"]],
+        });
+        sim_assert_eq!(
+            render(&value, OutputFormat::Yaml),
+            indoc::indoc! {r#"
+                version: 1.2.3
+                empty: ""
+                number: "1.2"
+                flag: true
+                missing: null
+                list:
+                  - a
+                  - "b: c"
+                none: []
+                nested:
+                  - name: major
+                    value: "1"
+                  - - x"#}
+        );
+    }
+
+    #[test]
+    fn test_render_yaml_round_trips_strings() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let strings = [
+            "0123",
+            "1e3",
+            "+1",
+            ".5",
+            "1_000",
+            "0x1F",
+            "0o17",
+            "0b101",
+            "1:20",
+            "1.2",
+            ".inf",
+            "-.INF",
+            ".NaN",
+            "yes",
+            "Off",
+            "y",
+            "~",
+            "null",
+            "2024-01-01",
+            "- item",
+            "key: value",
+            "# comment",
+            "'quoted'",
+            "trailing ",
+            "multi\nline",
+            "tab\tseparated",
+            "1.2.3",
+            "v1.0",
+            "main",
+        ];
+        let value = Value::Array(strings.iter().map(|s| json!(s)).collect());
+        let rendered = render(&value, OutputFormat::Yaml);
+        let docs = yaml_rust2::YamlLoader::load_from_str(&rendered)?;
+        let parsed: Vec<_> = docs[0]
+            .as_vec()
+            .ok_or_else(|| eyre::eyre!("expected a list, got {rendered}"))?
+            .iter()
+            .map(|value| value.as_str())
+            .collect();
+        sim_assert_eq!(
+            parsed,
+            strings.iter().copied().map(Some).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_preview_bumps() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let config = Config {
+            global: GlobalConfig {
+                current_version: Some("1.2-final".to_string()),
+                parse_version_pattern: Some(
+                    r"(?P<major>\d+)\.(?P<minor>\d+)-(?P<release>\w+)".try_into()?,
+                ),
+                serialize_version_patterns: Some(vec![PythonFormatString::parse(
+                    "{major}.{minor}-{release}",
+                )?]),
+                ..GlobalConfig::empty()
+            },
+            components: [(
+                "release".to_string(),
+                config::VersionComponentSpec {
+                    values: vec!["dev".to_string(), "final".to_string()],
+                    ..config::VersionComponentSpec::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Config::default()
+        }
+        .finalize();
+        let components = config::version::version_component_configs(&config);
+        let preview = super::preview_bumps(&config, &components, &TagAndRevision::default())?;
+        sim_assert_eq!(
+            preview.to_string(),
+            indoc::indoc! {r#"
+                1.2-final ─┬─ major ─ 2.0-dev
+                           ├─ minor ─ 1.3-dev
+                           └─ release ─ error: failed to bump version: "final" is already the last value among ["dev", "final"] and cannot be bumped"#}
+        );
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@
 use crate::{
     command::run_command,
     f_string::{PythonFormatString, Value},
-    vcs::{RevisionInfo, TagAndRevision, TagInfo, VersionControlSystem},
+    vcs::{CommitInfo, RevisionInfo, TagAndRevision, TagInfo, VersionControlSystem},
 };
 use async_process::Command;
 use std::path::{Path, PathBuf};
@@ -29,9 +29,9 @@ pub enum Error {
     InvalidTag(#[from] InvalidTagError),
 
     #[error("failed to template {format_string}")]
-    MissingArgument {
+    Format {
         #[source]
-        source: crate::f_string::MissingArgumentError,
+        source: crate::f_string::FormatError,
         format_string: PythonFormatString,
     },
 }
@@ -86,7 +86,7 @@ fn extract_regex_flags(pattern: &str) -> (&str, &str) {
 /// # Errors
 /// - When the given `parse_version_regex` cannot be transformed to extract the
 ///   current version from the git tag
-fn get_version_from_tag<'a>(
+pub(crate) fn get_version_from_tag<'a>(
     tag: &'a str,
     tag_name: &PythonFormatString,
     parse_version_regex: &regex::Regex,
@@ -174,7 +174,7 @@ impl GitRepository {
     ) -> Result<Option<TagInfo>, Error> {
         let tag_pattern = tag_name
             .format(&[("new_version", "*")].into_iter().collect(), true)
-            .map_err(|source| Error::MissingArgument {
+            .map_err(|source| Error::Format {
                 source,
                 format_string: tag_name.clone(),
             })?;
@@ -195,7 +195,7 @@ impl GitRepository {
 
         match run_command(&mut cmd).await {
             Ok(tag_info) => {
-                let raw_tag = tag_info.stdout;
+                let raw_tag = tag_info.stdout.trim().to_string();
                 let mut tag_parts: Vec<&str> = raw_tag.split('-').collect();
 
                 let dirty = tag_parts
@@ -245,13 +245,12 @@ impl GitRepository {
                 }))
             }
             Err(err) => {
-                if let crate::command::Error::Failed { ref output, .. } = err {
-                    if output
+                if let crate::command::Error::Failed { ref output, .. } = err
+                    && output
                         .stderr
                         .contains("No names found, cannot describe anything")
-                    {
-                        return Ok(None);
-                    }
+                {
+                    return Ok(None);
                 }
                 Err(err.into())
             }
@@ -263,6 +262,8 @@ impl GitRepository {
 impl VersionControlSystem for GitRepository {
     type Error = Error;
 
+    const TOOL: &'static str = "git";
+
     fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
         Ok(Self { path: path.into() })
     }
@@ -313,6 +314,27 @@ impl VersionControlSystem for GitRepository {
     where
         P: AsRef<std::ffi::OsStr>,
     {
+        let files: Vec<_> = files.into_iter().collect();
+
+        // `--update` skips untracked files, so new files (such as a changelog) are added first
+        let mut cmd = Command::new("git");
+        cmd.args(["ls-files", "--others", "--exclude-standard", "-z", "--"])
+            .args(&files)
+            .current_dir(&self.path);
+        let untracked_output = run_command(&mut cmd).await?;
+        let untracked: Vec<&str> = untracked_output
+            .stdout
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .collect();
+        if !untracked.is_empty() {
+            let mut cmd = Command::new("git");
+            cmd.args(["add", "--"])
+                .args(untracked)
+                .current_dir(&self.path);
+            let _add_output = run_command(&mut cmd).await?;
+        }
+
         let mut cmd = Command::new("git");
         cmd.arg("add")
             .arg("--update")
@@ -322,6 +344,18 @@ impl VersionControlSystem for GitRepository {
         Ok(())
     }
 
+    async fn unstage<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        let mut cmd = Command::new("git");
+        cmd.args(["reset", "--quiet", "--"])
+            .args(files)
+            .current_dir(&self.path);
+        let _reset_output = run_command(&mut cmd).await?;
+        Ok(())
+    }
+
     async fn dirty_files(&self) -> Result<Vec<PathBuf>, Error> {
         let mut cmd = Command::new("git");
         cmd.args(["status", "-u", "--porcelain"])
@@ -354,6 +388,28 @@ impl VersionControlSystem for GitRepository {
         Ok(())
     }
 
+    async fn push<A, AS>(
+        &self,
+        remote: Option<&str>,
+        tag: Option<&str>,
+        extra_args: A,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        AS: AsRef<std::ffi::OsStr>,
+    {
+        let mut cmd = Command::new("git");
+        cmd.current_dir(&self.path);
+        cmd.args(["push", "--atomic"]);
+        cmd.args(extra_args);
+        cmd.args([remote.unwrap_or("origin"), "HEAD"]);
+        if let Some(tag) = tag {
+            cmd.arg(format!("refs/tags/{tag}"));
+        }
+        let _push_output = run_command(&mut cmd).await?;
+        Ok(())
+    }
+
     async fn tags(&self) -> Result<Vec<String>, Error> {
         let mut cmd = Command::new("git");
         cmd.current_dir(&self.path);
@@ -366,6 +422,39 @@ impl VersionControlSystem for GitRepository {
             .collect())
     }
 
+    async fn commits_since(&self, revision: Option<&str>) -> Result<Vec<CommitInfo>, Error> {
+        // separate fields by unit separators and commits by record separators
+        let mut cmd = Command::new("git");
+        cmd.current_dir(&self.path);
+        cmd.args(["log", "--format=%H%x1f%s%x1f%b%x1e"]);
+        if let Some(revision) = revision {
+            cmd.arg(format!("{revision}..HEAD"));
+        }
+        let output = match run_command(&mut cmd).await {
+            Ok(output) => output,
+            Err(crate::command::Error::Failed { ref output, .. })
+                if output.stderr.contains("does not have any commits yet") =>
+            {
+                return Ok(vec![]);
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let commits = output
+            .stdout
+            .split('\x1e')
+            .map(str::trim)
+            .filter(|commit| !commit.is_empty())
+            .filter_map(|commit| {
+                let mut fields = commit.splitn(3, '\x1f');
+                let sha = fields.next()?.to_string();
+                let subject = fields.next().unwrap_or_default().to_string();
+                let body = fields.next().unwrap_or_default().trim().to_string();
+                Some(CommitInfo { sha, subject, body })
+            })
+            .collect();
+        Ok(commits)
+    }
+
     async fn latest_tag_and_revision(
         &self,
         tag_name: &PythonFormatString,
@@ -381,7 +470,11 @@ impl VersionControlSystem for GitRepository {
         let tag = self.latest_tag_info(tag_name, parse_version_regex).await?;
         let revision = self.revision_info().await.ok().flatten();
 
-        Ok(TagAndRevision { tag, revision })
+        Ok(TagAndRevision {
+            tool: Some(Self::TOOL.to_string()),
+            tag,
+            revision,
+        })
     }
 }
 
@@ -498,4 +591,43 @@ mod tests {
         sim_assert_eq_sorted!(repo.dirty_files().await?, dirty_files);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_push_to_bare_remote() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<git::GitRepository> = EphemeralRepository::new().await?;
+        let remote = tempfile::TempDir::with_prefix("remote")?;
+        let git = |args: &[&str], dir: &std::path::Path| {
+            let mut cmd = Command::new("git");
+            cmd.args(args).current_dir(dir);
+            async move { run_command(&mut cmd).await }
+        };
+        git(&["init", "--bare"], remote.path()).await?;
+        for args in [
+            &["config", "user.name", "bumpversion"][..],
+            &["config", "user.email", "bumpversion@example.com"],
+            &["remote", "add", "origin", &remote.path().to_string_lossy()],
+            &["commit", "--allow-empty", "-m", "initial commit"],
+        ] {
+            git(args, repo.path()).await?;
+        }
+        repo.tag("v1.0.0", Some("release"), false).await?;
+
+        repo.push::<_, &str>(None, Some("v1.0.0"), []).await?;
+
+        let head = git(&["rev-parse", "HEAD"], repo.path()).await?;
+        let branch = git(&["symbolic-ref", "--short", "HEAD"], repo.path()).await?;
+        let remote_head = git(&["rev-parse", branch.stdout.trim()], remote.path()).await?;
+        let remote_tag = git(&["rev-parse", "v1.0.0^{commit}"], remote.path()).await?;
+        sim_assert_eq!(remote_head.stdout, head.stdout);
+        sim_assert_eq!(remote_tag.stdout, head.stdout);
+
+        // pushing to a missing remote fails
+        assert!(
+            repo.push::<_, &str>(Some("missing"), None, [])
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,789 @@
+//! In-process git backend for version control operations.
+//!
+//! Implements the `VersionControlSystem` trait using [gitoxide](https://github.com/GitoxideLabs/gitoxide),
+//! so that no `git` binary is required.
+use crate::{
+    f_string::PythonFormatString,
+    vcs::{
+        CommitInfo, RevisionInfo, TagAndRevision, TagInfo, VersionControlSystem,
+        git::{BRANCH_NAME_REGEX, get_version_from_tag},
+    },
+};
+use gix::bstr::{BString, ByteSlice};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Gitoxide VCS error type.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("git error: {0}")]
+    Gix(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+
+    #[error("regex error: {0}")]
+    Regex(#[from] regex::Error),
+
+    #[error("repository at {0:?} has no working directory")]
+    MissingWorkdir(PathBuf),
+
+    #[error("path {path:?} is outside of the repository at {workdir:?}")]
+    PathOutsideRepository { path: PathBuf, workdir: PathBuf },
+
+    #[error("missing git identity: set `user.name` and `user.email` in the git config")]
+    MissingIdentity,
+
+    #[error("nothing to commit")]
+    NothingToCommit,
+
+    #[error("signing tags is not supported by the gitoxide backend")]
+    SignedTagsUnsupported,
+
+    #[error("pushing is not supported by the gitoxide backend")]
+    PushUnsupported,
+
+    #[error("commit arguments {0:?} are not supported by the gitoxide backend")]
+    UnsupportedCommitArgs(Vec<String>),
+
+    #[error("failed to template {format_string}")]
+    Format {
+        #[source]
+        source: crate::f_string::FormatError,
+        format_string: PythonFormatString,
+    },
+}
+
+impl Error {
+    fn gix(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Gix(Box::new(err))
+    }
+}
+
+/// Represents a git repository at a given filesystem path, accessed in-process.
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GitoxideRepository {
+    path: PathBuf,
+    repo: gix::ThreadSafeRepository,
+}
+
+/// Commit arguments that are implied by the gitoxide backend.
+///
+/// Commits never run hooks, and empty commits are only created if allowed.
+const SUPPORTED_COMMIT_ARGS: [&str; 2] = ["--allow-empty", "--no-verify"];
+
+/// Reject commit arguments that are not in [`SUPPORTED_COMMIT_ARGS`].
+fn check_commit_args(args: &[String]) -> Result<(), Error> {
+    let unsupported: Vec<String> = args
+        .iter()
+        .filter(|arg| !SUPPORTED_COMMIT_ARGS.contains(&arg.as_str()))
+        .cloned()
+        .collect();
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedCommitArgs(unsupported))
+    }
+}
+
+/// Clean up a commit or tag `message` like `git` does by default.
+fn cleanup_message(message: &str) -> String {
+    format!("{}\n", message.trim_end())
+}
+
+/// Resolve the signature of the `role` (`AUTHOR` or `COMMITTER`) from the `env` or the git config.
+fn signature(
+    repo: &gix::Repository,
+    role: &str,
+    env: &HashMap<String, String>,
+) -> Result<gix::actor::Signature, Error> {
+    let config = repo.config_snapshot();
+    let resolve = |env_key: String, config_key: &str| -> Option<BString> {
+        env.get(&env_key)
+            .map(|value| value.as_str().into())
+            .or_else(|| config.string(config_key).map(Cow::into_owned))
+    };
+    let name = resolve(format!("GIT_{role}_NAME"), "user.name");
+    let email = resolve(format!("GIT_{role}_EMAIL"), "user.email");
+    let (Some(name), Some(email)) = (name, email) else {
+        return Err(Error::MissingIdentity);
+    };
+    Ok(gix::actor::Signature {
+        name,
+        email,
+        time: gix::date::Time::now_local_or_utc(),
+    })
+}
+
+impl GitoxideRepository {
+    /// Returns the working directory of the repository.
+    fn workdir(repo: &gix::Repository) -> Result<PathBuf, Error> {
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| Error::MissingWorkdir(repo.path().to_path_buf()))?;
+        Ok(workdir.canonicalize()?)
+    }
+
+    /// Read the index from disk, or return an empty index if there is none yet.
+    ///
+    /// The index snapshot shared by the repository is not used, as it is not reloaded
+    /// if the index changed within the timestamp granularity of the filesystem.
+    fn open_index(repo: &gix::Repository) -> Result<gix::index::File, Error> {
+        if !repo.index_path().is_file() {
+            let state = gix::index::State::new(repo.object_hash());
+            return Ok(gix::index::File::from_state(state, repo.index_path()));
+        }
+        repo.open_index().map_err(Error::gix)
+    }
+
+    /// Returns the path of `file` relative to the `workdir`, as used in the index.
+    fn relative_path(&self, workdir: &Path, file: &Path) -> Result<BString, Error> {
+        let path = self.path.join(file);
+        let path = path.canonicalize().unwrap_or(path);
+        let relative = path
+            .strip_prefix(workdir)
+            .map_err(|_| Error::PathOutsideRepository {
+                path: path.clone(),
+                workdir: workdir.to_path_buf(),
+            })?;
+        Ok(gix::path::to_unix_separators_on_windows(gix::path::into_bstr(relative)).into_owned())
+    }
+
+    /// Returns a dictionary containing revision information.
+    fn revision_info(&self) -> Result<Option<RevisionInfo>, Error> {
+        let repo = self.repo.to_thread_local();
+        let head = repo.head().map_err(Error::gix)?;
+        if head.is_unborn() {
+            return Ok(None);
+        }
+        let repository_root = Self::workdir(&repo)?;
+        let branch_name = head
+            .referent_name()
+            .map_or_else(|| "HEAD".to_string(), |name| name.shorten().to_string());
+        let short_branch_name: String = BRANCH_NAME_REGEX
+            .replace_all(&branch_name, "")
+            .to_lowercase()
+            .chars()
+            .take(20)
+            .collect();
+
+        Ok(Some(RevisionInfo {
+            branch_name,
+            short_branch_name,
+            repository_root,
+        }))
+    }
+
+    /// Get the commit info for the repo, like `git describe --tags --long --dirty`.
+    ///
+    /// The `tag_name` is the tag name format used to locate the latest tag.
+    /// The `parse_pattern` is a regular expression pattern used to parse the version from the tag.
+    async fn latest_tag_info(
+        &self,
+        tag_name: &PythonFormatString,
+        parse_version_regex: &regex::Regex,
+    ) -> Result<Option<TagInfo>, Error> {
+        let tag_pattern = tag_name
+            .format(&[("new_version", "*")].into_iter().collect(), true)
+            .map_err(|source| Error::Format {
+                source,
+                format_string: tag_name.clone(),
+            })?;
+        let tag_regex = regex::Regex::new(&format!(
+            "^{}$",
+            regex::escape(&tag_pattern).replace(r"\*", ".*")
+        ))?;
+
+        let outcome = {
+            let repo = self.repo.to_thread_local();
+            let head = repo.head().map_err(Error::gix)?;
+            let Some(head_id) = head.id() else {
+                return Ok(None);
+            };
+
+            // prefer annotated tags, then newer tags, like `git describe`
+            let mut candidates: Vec<_> = repo
+                .references()
+                .map_err(Error::gix)?
+                .tags()
+                .map_err(Error::gix)?
+                .filter_map(Result::ok)
+                .filter_map(|mut reference| {
+                    let name = reference.name().shorten().to_string();
+                    if !tag_regex.is_match(&name) {
+                        return None;
+                    }
+                    let target_id = reference.target().try_id().map(ToOwned::to_owned);
+                    let peeled_id = reference.peel_to_id().ok()?.detach();
+                    let tag_time = match target_id {
+                        Some(target_id) if target_id != peeled_id => repo
+                            .find_tag(target_id)
+                            .ok()
+                            .and_then(|tag| tag.tagger().ok().flatten().map(|t| t.seconds()))
+                            .map(|seconds| (1, seconds)),
+                        _ => None,
+                    };
+                    Some((peeled_id, tag_time.unwrap_or((0, 0)), name))
+                })
+                .collect();
+            candidates.sort_by(|(_, a_time, a_name), (_, b_time, b_name)| {
+                a_time.cmp(b_time).then_with(|| b_name.cmp(a_name))
+            });
+            let name_by_oid = candidates
+                .into_iter()
+                .map(|(id, _, name)| (id, Cow::Owned(BString::from(name))))
+                .collect();
+
+            let cache = repo.commit_graph_if_enabled().map_err(Error::gix)?;
+            let mut graph = repo.revision_graph(cache.as_ref());
+            gix::revision::plumbing::describe(
+                &head_id,
+                &mut graph,
+                gix::revision::plumbing::describe::Options {
+                    name_by_oid,
+                    max_candidates: 10,
+                    fallback_to_oid: false,
+                    first_parent: false,
+                },
+            )
+            .map_err(Error::gix)?
+        };
+        let Some(outcome) = outcome else {
+            return Ok(None);
+        };
+        let Some(current_tag) = outcome.name.map(|name| name.to_string()) else {
+            return Ok(None);
+        };
+
+        let dirty = !self.dirty_files().await?.is_empty();
+        let commit_sha = outcome.id.to_string();
+        let distance_to_latest_tag = outcome.depth as usize;
+        let version = get_version_from_tag(&current_tag, tag_name, parse_version_regex)?;
+        let current_numeric_version = current_tag.trim_start_matches('v');
+        let current_version = version.unwrap_or(current_numeric_version).to_string();
+
+        tracing::debug!(
+            dirty,
+            commit_sha,
+            distance_to_latest_tag,
+            current_tag,
+            version,
+            current_numeric_version,
+            current_version
+        );
+
+        Ok(Some(TagInfo {
+            dirty,
+            commit_sha,
+            distance_to_latest_tag,
+            current_tag,
+            current_version,
+        }))
+    }
+}
+
+impl VersionControlSystem for GitoxideRepository {
+    type Error = Error;
+
+    const TOOL: &'static str = "git";
+
+    fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let repo =
+            gix::ThreadSafeRepository::discover(&path).map_err(|err| Error::gix(Box::new(err)))?;
+        Ok(Self { path, repo })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn check_supported(
+        &self,
+        commit_args: &[String],
+        sign_tags: bool,
+        push: bool,
+    ) -> Result<(), Error> {
+        check_commit_args(commit_args)?;
+        if sign_tags {
+            return Err(Error::SignedTagsUnsupported);
+        }
+        if push {
+            return Err(Error::PushUnsupported);
+        }
+        Ok(())
+    }
+
+    async fn commit<A, E, AS, EK, EV>(
+        &self,
+        message: &str,
+        extra_args: A,
+        env: E,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        E: IntoIterator<Item = (EK, EV)>,
+        AS: AsRef<std::ffi::OsStr>,
+        EK: AsRef<std::ffi::OsStr>,
+        EV: AsRef<std::ffi::OsStr>,
+    {
+        let extra_args: Vec<String> = extra_args
+            .into_iter()
+            .map(|arg| arg.as_ref().to_string_lossy().to_string())
+            .collect();
+        check_commit_args(&extra_args)?;
+        let allow_empty = extra_args.iter().any(|arg| arg == "--allow-empty");
+        let env: HashMap<String, String> = std::env::vars()
+            .chain(env.into_iter().map(|(key, value)| {
+                (
+                    key.as_ref().to_string_lossy().to_string(),
+                    value.as_ref().to_string_lossy().to_string(),
+                )
+            }))
+            .collect();
+
+        let repo = self.repo.to_thread_local();
+        let index = Self::open_index(&repo)?;
+        let mut editor = repo
+            .edit_tree(gix::ObjectId::empty_tree(repo.object_hash()))
+            .map_err(Error::gix)?;
+        for entry in index.entries() {
+            if entry.stage() != gix::index::entry::Stage::Unconflicted {
+                continue;
+            }
+            let Some(mode) = entry.mode.to_tree_entry_mode() else {
+                continue;
+            };
+            editor
+                .upsert(entry.path(&index), mode.kind(), entry.id)
+                .map_err(Error::gix)?;
+        }
+        let tree = editor.write().map_err(Error::gix)?.detach();
+
+        let parent = repo.head().map_err(Error::gix)?.id().map(gix::Id::detach);
+        let parent_tree = match parent {
+            Some(parent) => Some(
+                repo.find_commit(parent)
+                    .map_err(Error::gix)?
+                    .tree_id()
+                    .map_err(Error::gix)?
+                    .detach(),
+            ),
+            None => None,
+        };
+        if !allow_empty && parent_tree.is_some_and(|parent_tree| parent_tree == tree) {
+            return Err(Error::NothingToCommit);
+        }
+
+        let author = signature(&repo, "AUTHOR", &env)?;
+        let committer = signature(&repo, "COMMITTER", &env)?;
+        let mut author_time = gix::date::parse::TimeBuf::default();
+        let mut committer_time = gix::date::parse::TimeBuf::default();
+        repo.commit_as(
+            committer.to_ref(&mut committer_time),
+            author.to_ref(&mut author_time),
+            "HEAD",
+            cleanup_message(message),
+            tree,
+            parent,
+        )
+        .map_err(Error::gix)?;
+        Ok(())
+    }
+
+    async fn add<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        let repo = self.repo.to_thread_local();
+        let workdir = Self::workdir(&repo)?;
+        let mut index = Self::open_index(&repo)?;
+        let files = files
+            .into_iter()
+            .map(|file| self.relative_path(&workdir, Path::new(file.as_ref())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // like `git add`, untracked files are added unless they are ignored
+        let ignored: Vec<bool> = {
+            let mut excludes = repo
+                .excludes(
+                    &index,
+                    None,
+                    gix::worktree::stack::state::ignore::Source::default(),
+                )
+                .map_err(Error::gix)?;
+            files
+                .iter()
+                .map(|rela_path| {
+                    if index.entry_index_by_path(rela_path.as_bstr()).is_ok() {
+                        return Ok(false);
+                    }
+                    let platform =
+                        excludes.at_path(gix::path::from_bstr(rela_path.as_bstr()), None)?;
+                    Ok(platform.is_excluded())
+                })
+                .collect::<Result<_, std::io::Error>>()?
+        };
+
+        let mut removed = Vec::new();
+        let mut added = false;
+        for (rela_path, ignored) in files.into_iter().zip(ignored) {
+            if ignored {
+                continue;
+            }
+            let idx = index.entry_index_by_path(rela_path.as_bstr()).ok();
+            let path = workdir.join(gix::path::from_bstr(rela_path.as_bstr()));
+            let metadata = match gix::index::fs::Metadata::from_path_no_follow(&path) {
+                Ok(metadata) if metadata.is_file() => metadata,
+                Ok(_) => continue,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    if idx.is_some() {
+                        removed.push(rela_path);
+                    }
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let data = std::fs::read(&path)?;
+            let id = repo.write_blob(data).map_err(Error::gix)?.detach();
+            let stat = gix::index::entry::Stat::from_fs(&metadata).map_err(Error::gix)?;
+            if let Some(idx) = idx {
+                let entry = &mut index.entries_mut()[idx];
+                entry.id = id;
+                entry.stat = stat;
+            } else {
+                let mode = if metadata.is_executable() {
+                    gix::index::entry::Mode::FILE_EXECUTABLE
+                } else {
+                    gix::index::entry::Mode::FILE
+                };
+                index.dangerously_push_entry(
+                    stat,
+                    id,
+                    gix::index::entry::Flags::empty(),
+                    mode,
+                    rela_path.as_bstr(),
+                );
+                added = true;
+            }
+        }
+
+        if added {
+            index.sort_entries();
+        }
+        if !removed.is_empty() {
+            index.remove_entries(|_, path, _| removed.iter().any(|removed| removed == path));
+        }
+        index
+            .write(gix::index::write::Options::default())
+            .map_err(Error::gix)?;
+        Ok(())
+    }
+
+    async fn unstage<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        let repo = self.repo.to_thread_local();
+        let workdir = Self::workdir(&repo)?;
+        let head_tree_id = repo.head_tree_id_or_empty().map_err(Error::gix)?;
+        let head_tree = repo.find_tree(head_tree_id).map_err(Error::gix)?;
+        let mut index = Self::open_index(&repo)?;
+        let mut removed = Vec::new();
+
+        for file in files {
+            let rela_path = self.relative_path(&workdir, Path::new(file.as_ref()))?;
+            let Ok(idx) = index.entry_index_by_path(rela_path.as_bstr()) else {
+                continue;
+            };
+            // like `git reset`, the index entry is reset to the entry of `HEAD`
+            let head_entry = head_tree
+                .lookup_entry_by_path(gix::path::from_bstr(rela_path.as_bstr()))
+                .map_err(Error::gix)?;
+            let Some(head_entry) = head_entry else {
+                removed.push(rela_path);
+                continue;
+            };
+            let entry = &mut index.entries_mut()[idx];
+            entry.id = head_entry.object_id();
+            // the working tree file no longer matches the entry
+            entry.stat = gix::index::entry::Stat::default();
+        }
+
+        if !removed.is_empty() {
+            index.remove_entries(|_, path, _| removed.iter().any(|removed| removed == path));
+        }
+        index
+            .write(gix::index::write::Options::default())
+            .map_err(Error::gix)?;
+        Ok(())
+    }
+
+    async fn dirty_files(&self) -> Result<Vec<PathBuf>, Error> {
+        let repo = self.repo.to_thread_local();
+        let status = repo
+            .status(gix::progress::Discard)
+            .map_err(Error::gix)?
+            .index(gix::worktree::IndexPersistedOrInMemory::InMemory(
+                Self::open_index(&repo)?,
+            ))
+            .untracked_files(gix::status::UntrackedFiles::None)
+            .into_iter(Vec::<BString>::new())
+            .map_err(Error::gix)?;
+
+        let mut dirty = Vec::new();
+        for item in status {
+            let item = item.map_err(Error::gix)?;
+            dirty.push(self.path().join(gix::path::from_bstr(item.location())));
+        }
+        dirty.sort();
+        dirty.dedup();
+        Ok(dirty)
+    }
+
+    async fn tag(&self, name: &str, message: Option<&str>, sign: bool) -> Result<(), Error> {
+        if sign {
+            return Err(Error::SignedTagsUnsupported);
+        }
+        let repo = self.repo.to_thread_local();
+        let head = repo.head_id().map_err(Error::gix)?.detach();
+        let constraint = gix::refs::transaction::PreviousValue::MustNotExist;
+        if let Some(message) = message {
+            let env = std::env::vars().collect();
+            let tagger = signature(&repo, "COMMITTER", &env)?;
+            let mut tagger_time = gix::date::parse::TimeBuf::default();
+            repo.tag(
+                name,
+                head,
+                gix::objs::Kind::Commit,
+                Some(tagger.to_ref(&mut tagger_time)),
+                cleanup_message(message),
+                constraint,
+            )
+            .map_err(Error::gix)?;
+        } else {
+            repo.tag_reference(name, head, constraint)
+                .map_err(Error::gix)?;
+        }
+        Ok(())
+    }
+
+    async fn push<A, AS>(
+        &self,
+        _remote: Option<&str>,
+        _tag: Option<&str>,
+        _extra_args: A,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        AS: AsRef<std::ffi::OsStr>,
+    {
+        Err(Error::PushUnsupported)
+    }
+
+    async fn tags(&self) -> Result<Vec<String>, Error> {
+        let repo = self.repo.to_thread_local();
+        let references = repo.references().map_err(Error::gix)?;
+        let mut tags = Vec::new();
+        for reference in references.tags().map_err(Error::gix)? {
+            let reference = reference.map_err(Error::Gix)?;
+            tags.push(reference.name().shorten().to_string());
+        }
+        Ok(tags)
+    }
+
+    async fn commits_since(&self, revision: Option<&str>) -> Result<Vec<CommitInfo>, Error> {
+        let repo = self.repo.to_thread_local();
+        let Some(head) = repo.head().map_err(Error::gix)?.id() else {
+            return Ok(vec![]);
+        };
+        let mut walk = repo.rev_walk([head]);
+        if let Some(revision) = revision {
+            let hidden = repo
+                .rev_parse_single(format!("{revision}^{{commit}}").as_str())
+                .map_err(Error::gix)?;
+            walk = walk.with_hidden([hidden]);
+        }
+
+        let mut commits = Vec::new();
+        for info in walk.all().map_err(Error::gix)? {
+            let commit = info.map_err(Error::gix)?.object().map_err(Error::gix)?;
+            let message = commit.message().map_err(Error::gix)?;
+            commits.push(CommitInfo {
+                sha: commit.id.to_string(),
+                subject: message.summary().to_string(),
+                body: message
+                    .body
+                    .map(|body| body.to_str_lossy().trim().to_string())
+                    .unwrap_or_default(),
+            });
+        }
+        Ok(commits)
+    }
+
+    async fn latest_tag_and_revision(
+        &self,
+        tag_name: &PythonFormatString,
+        parse_version_regex: &regex::Regex,
+    ) -> Result<TagAndRevision, Error> {
+        let tag = self.latest_tag_info(tag_name, parse_version_regex).await?;
+        let revision = self.revision_info().ok().flatten();
+
+        Ok(TagAndRevision {
+            tool: Some(Self::TOOL.to_string()),
+            tag,
+            revision,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        command::run_command,
+        f_string::PythonFormatString,
+        vcs::{
+            VersionControlSystem,
+            git::GitRepository,
+            gitoxide::{Error, GitoxideRepository},
+            temp::EphemeralRepository,
+        },
+    };
+    use async_process::Command;
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[tokio::test]
+    async fn test_check_supported() -> eyre::Result<()> {
+        crate::tests::init();
+        let git_repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let repo = GitoxideRepository::open(git_repo.path())?;
+
+        assert!(
+            repo.check_supported(&["--no-verify".to_string()], false, false)
+                .is_ok()
+        );
+        assert!(matches!(
+            repo.check_supported(&["--signoff".to_string()], false, false),
+            Err(Error::UnsupportedCommitArgs(args)) if args == ["--signoff"]
+        ));
+        assert!(matches!(
+            repo.check_supported(&[], true, false),
+            Err(Error::SignedTagsUnsupported)
+        ));
+        assert!(matches!(
+            repo.check_supported(&[], false, true),
+            Err(Error::PushUnsupported)
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_same_results_as_git_commands() -> eyre::Result<()> {
+        crate::tests::init();
+        let git_repo: EphemeralRepository<GitRepository> = EphemeralRepository::new().await?;
+        let git = |args: &[&str]| {
+            let mut cmd = Command::new("git");
+            cmd.args(args).current_dir(git_repo.path());
+            async move { run_command(&mut cmd).await }
+        };
+        git(&["config", "user.name", "bumpversion"]).await?;
+        git(&["config", "user.email", "bumpversion@example.com"]).await?;
+        let repo = GitoxideRepository::open(git_repo.path())?;
+
+        let tag_name = PythonFormatString::parse("v{new_version}")?;
+        let parse_version_regex =
+            regex::Regex::new(r"(?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+)(-(?P<pre>.*))?")?;
+        let latest = async || -> eyre::Result<_> {
+            let expected = git_repo
+                .latest_tag_and_revision(&tag_name, &parse_version_regex)
+                .await?;
+            let actual = repo
+                .latest_tag_and_revision(&tag_name, &parse_version_regex)
+                .await?;
+            sim_assert_eq!(actual, expected);
+            Ok(actual)
+        };
+
+        // no commits yet
+        sim_assert_eq!(latest().await?.tag, None);
+        assert!(repo.commits_since(None).await?.is_empty());
+
+        let readme = repo.path().join("README.md");
+        tokio::fs::write(&readme, "version 1.0.0-rc-1").await?;
+        git(&["add", "README.md"]).await?;
+        repo.commit::<_, _, &str, &str, &str>("feat: initial release", [], [])
+            .await?;
+        repo.tag("v1.0.0-rc-1", Some("release candidate"), false)
+            .await?;
+        repo.tag("unrelated", None, false).await?;
+        assert!(repo.tag("v1.0.0-rc-1", None, false).await.is_err());
+        assert!(repo.tag("signed", None, true).await.is_err());
+        sim_assert_eq!(repo.tags().await?, git_repo.tags().await?);
+
+        let tag = latest().await?.tag.unwrap();
+        sim_assert_eq!(tag.current_tag, "v1.0.0-rc-1");
+        sim_assert_eq!(tag.current_version, "1.0.0-rc-1");
+        sim_assert_eq!(tag.distance_to_latest_tag, 0);
+
+        // untracked files are ignored
+        tokio::fs::write(repo.path().join("untracked.txt"), "untracked").await?;
+        tokio::fs::write(&readme, "version 1.0.0").await?;
+        sim_assert_eq!(repo.dirty_files().await?, vec![readme.clone()]);
+        sim_assert_eq!(repo.dirty_files().await?, git_repo.dirty_files().await?);
+        assert!(latest().await?.tag.unwrap().dirty);
+
+        // nothing is staged yet
+        assert!(
+            repo.commit::<_, _, &str, &str, &str>("fix: nothing", [], [])
+                .await
+                .is_err()
+        );
+
+        // staged changes and newly added files are unstaged
+        let added = repo.path().join("added.txt");
+        tokio::fs::write(&added, "added").await?;
+        git(&["add", "added.txt"]).await?;
+        repo.add([&readme]).await?;
+        repo.unstage([&readme, &added]).await?;
+        sim_assert_eq!(git(&["diff", "--cached", "--name-only"]).await?.stdout, "");
+        sim_assert_eq!(repo.dirty_files().await?, git_repo.dirty_files().await?);
+        tokio::fs::remove_file(&added).await?;
+
+        // untracked files are added unless they are ignored
+        tokio::fs::write(repo.path().join(".git/info/exclude"), "ignored.txt\n").await?;
+        tokio::fs::write(repo.path().join("ignored.txt"), "ignored").await?;
+        repo.add([
+            &readme,
+            &repo.path().join("untracked.txt"),
+            &repo.path().join("ignored.txt"),
+        ])
+        .await?;
+        repo.commit(
+            "fix: second\n\nwith a body",
+            ["--no-verify"],
+            [("GIT_AUTHOR_NAME", "someone else")],
+        )
+        .await?;
+        assert!(git_repo.dirty_files().await?.is_empty());
+        sim_assert_eq!(
+            git(&["log", "-1", "--format=%an|%cn|%B"]).await?.stdout,
+            "someone else|bumpversion|fix: second\n\nwith a body\n\n"
+        );
+        sim_assert_eq!(
+            git(&["ls-files"]).await?.stdout,
+            "README.md\nuntracked.txt\n"
+        );
+
+        let tag = latest().await?.tag.unwrap();
+        sim_assert_eq!(tag.distance_to_latest_tag, 1);
+        assert!(!tag.dirty);
+
+        let commits = repo.commits_since(Some("v1.0.0-rc-1")).await?;
+        sim_assert_eq!(commits, git_repo.commits_since(Some("v1.0.0-rc-1")).await?);
+        sim_assert_eq!(repo.commits_since(None).await?.len(), 2);
+        Ok(())
+    }
+}
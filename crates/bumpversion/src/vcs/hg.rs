@@ -0,0 +1,526 @@
+//! Mercurial backend for version control operations.
+//!
+//! Implements the `VersionControlSystem` trait using hg commands.
+use crate::{
+    command::run_command,
+    f_string::PythonFormatString,
+    vcs::{
+        CommitInfo, RevisionInfo, TagAndRevision, TagInfo, VersionControlSystem,
+        git::{BRANCH_NAME_REGEX, get_version_from_tag},
+    },
+};
+use async_process::Command;
+use std::path::{Path, PathBuf};
+
+/// Mercurial VCS error type.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("command failed: {0}")]
+    CommandFailed(#[from] crate::command::Error),
+
+    #[error("regex error: {0}")]
+    Regex(#[from] regex::Error),
+
+    #[error("mercurial does not support signed tags")]
+    SignedTagsUnsupported,
+
+    #[error("failed to template {format_string}")]
+    Format {
+        #[source]
+        source: crate::f_string::FormatError,
+        format_string: PythonFormatString,
+    },
+}
+
+/// Represents a mercurial repository at a given filesystem path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MercurialRepository {
+    path: PathBuf,
+}
+
+/// The tag `hg` reports when a changeset has no tagged ancestor.
+const NULL_TAG: &str = "null";
+
+/// Quote `value` as a string literal for use in a revset or template.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// The template printing the node, followed by the latest tags matching `tag_regex`
+/// and their distance, one per line.
+fn latest_tag_template(tag_regex: &str) -> String {
+    format!(
+        r"{{node}}\n{{latesttag({}) % '{{tag}}\x1f{{distance}}\n'}}",
+        quote(&format!("re:{tag_regex}"))
+    )
+}
+
+/// Parse the output of the [`latest_tag_template`].
+///
+/// # Returns
+/// The tuple `(commit_sha, Option<(tag, distance)>)`.
+fn parse_latest_tag(output: &str) -> (&str, Option<(&str, usize)>) {
+    let mut lines = output.lines().map(str::trim);
+    let commit_sha = lines.next().unwrap_or_default();
+    let latest_tag = lines
+        .filter_map(|line| line.split_once('\x1f'))
+        .filter(|(tag, _)| *tag != NULL_TAG)
+        .find_map(|(tag, distance)| Some((tag, distance.parse().ok()?)));
+    (commit_sha, latest_tag)
+}
+
+/// Parse commits separated by record separators, with the node and description
+/// separated by a unit separator.
+fn parse_commits(output: &str) -> Vec<CommitInfo> {
+    output
+        .split('\x1e')
+        .map(str::trim)
+        .filter(|commit| !commit.is_empty())
+        .filter_map(|commit| {
+            let (sha, description) = commit.split_once('\x1f')?;
+            let (subject, body) = description.split_once('\n').unwrap_or((description, ""));
+            Some(CommitInfo {
+                sha: sha.to_string(),
+                subject: subject.trim().to_string(),
+                body: body.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+impl MercurialRepository {
+    /// Returns a dictionary containing revision information.
+    async fn revision_info(&self) -> Result<Option<RevisionInfo>, Error> {
+        let mut cmd = Command::new("hg");
+        cmd.args(["log", "-r", ".", "--template", r"{reporoot}\n{branch}\n"])
+            .current_dir(&self.path);
+
+        let res = run_command(&mut cmd).await?;
+        let mut lines = res.stdout.lines().map(str::trim);
+        let Some(repository_root) = lines.next().map(PathBuf::from) else {
+            return Ok(None);
+        };
+        let Some(branch_name) = lines.next() else {
+            return Ok(None);
+        };
+        let short_branch_name: String = BRANCH_NAME_REGEX
+            .replace_all(branch_name, "")
+            .to_lowercase()
+            .chars()
+            .take(20)
+            .collect();
+
+        Ok(Some(RevisionInfo {
+            branch_name: branch_name.to_string(),
+            short_branch_name,
+            repository_root,
+        }))
+    }
+
+    /// Get the commit info for the repo.
+    ///
+    /// The `tag_name` is the tag name format used to locate the latest tag.
+    /// The `parse_pattern` is a regular expression pattern used to parse the version from the tag.
+    async fn latest_tag_info(
+        &self,
+        tag_name: &PythonFormatString,
+        parse_version_regex: &regex::Regex,
+    ) -> Result<Option<TagInfo>, Error> {
+        let tag_pattern = tag_name
+            .format(&[("new_version", "*")].into_iter().collect(), true)
+            .map_err(|source| Error::Format {
+                source,
+                format_string: tag_name.clone(),
+            })?;
+        let tag_regex = format!("^{}$", regex::escape(&tag_pattern).replace(r"\*", ".*"));
+
+        let mut cmd = Command::new("hg");
+        cmd.args(["log", "-r", ".", "--template"])
+            .arg(latest_tag_template(&tag_regex))
+            .current_dir(&self.path);
+        let output = run_command(&mut cmd).await?;
+
+        let (commit_sha, latest_tag) = parse_latest_tag(&output.stdout);
+        let Some((current_tag, distance_to_latest_tag)) = latest_tag else {
+            return Ok(None);
+        };
+        let dirty = !self.dirty_files().await?.is_empty();
+        let version = get_version_from_tag(current_tag, tag_name, parse_version_regex)?;
+        let current_numeric_version = current_tag.trim_start_matches('v');
+        let current_version = version.unwrap_or(current_numeric_version).to_string();
+
+        tracing::debug!(
+            dirty,
+            commit_sha,
+            distance_to_latest_tag,
+            current_tag,
+            version,
+            current_numeric_version,
+            current_version
+        );
+
+        Ok(Some(TagInfo {
+            dirty,
+            commit_sha: commit_sha.to_string(),
+            distance_to_latest_tag,
+            current_tag: current_tag.to_string(),
+            current_version,
+        }))
+    }
+}
+
+impl VersionControlSystem for MercurialRepository {
+    type Error = Error;
+
+    const TOOL: &'static str = "hg";
+
+    fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        Ok(Self { path: path.into() })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn commit<A, E, AS, EK, EV>(
+        &self,
+        message: &str,
+        extra_args: A,
+        env: E,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        E: IntoIterator<Item = (EK, EV)>,
+        AS: AsRef<std::ffi::OsStr>,
+        EK: AsRef<std::ffi::OsStr>,
+        EV: AsRef<std::ffi::OsStr>,
+    {
+        let tmp = tempfile::TempDir::new()?;
+        let tmp_file_path = tmp.path().join("commit-message.txt");
+        tokio::fs::write(&tmp_file_path, message).await?;
+
+        let mut cmd = Command::new("hg");
+        cmd.arg("commit");
+        cmd.arg("--logfile");
+        cmd.arg(&tmp_file_path);
+        cmd.args(extra_args);
+        cmd.envs(env);
+        cmd.current_dir(&self.path);
+        let _commit_output = run_command(&mut cmd).await?;
+        Ok(())
+    }
+
+    async fn add<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        let mut cmd = Command::new("hg");
+        cmd.arg("add").args(files).current_dir(&self.path);
+        let _add_output = run_command(&mut cmd).await?;
+        Ok(())
+    }
+
+    async fn unstage<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        // changes to tracked files are not staged, only newly added files are
+        let mut cmd = Command::new("hg");
+        cmd.args(["status", "--added", "--no-status", "--print0"])
+            .args(files)
+            .current_dir(&self.path);
+        let status_output = run_command(&mut cmd).await?;
+        let added: Vec<&str> = status_output
+            .stdout
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .collect();
+        if added.is_empty() {
+            return Ok(());
+        }
+        let mut cmd = Command::new("hg");
+        cmd.arg("forget").args(added).current_dir(&self.path);
+        let _forget_output = run_command(&mut cmd).await?;
+        Ok(())
+    }
+
+    async fn dirty_files(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut cmd = Command::new("hg");
+        cmd.args(["status", "--modified", "--added", "--removed", "--deleted"])
+            .args(["--no-status", "--print0"])
+            .current_dir(&self.path);
+
+        let status_output = run_command(&mut cmd).await?;
+        let dirty = status_output
+            .stdout
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .map(|file| self.path().join(file))
+            .collect();
+        Ok(dirty)
+    }
+
+    fn check_supported(
+        &self,
+        _commit_args: &[String],
+        sign_tags: bool,
+        _push: bool,
+    ) -> Result<(), Error> {
+        if sign_tags {
+            return Err(Error::SignedTagsUnsupported);
+        }
+        Ok(())
+    }
+
+    async fn tag(&self, name: &str, message: Option<&str>, sign: bool) -> Result<(), Error> {
+        // mercurial tags are committed to `.hgtags` and cannot be signed
+        if sign {
+            return Err(Error::SignedTagsUnsupported);
+        }
+        let mut cmd = Command::new("hg");
+        cmd.current_dir(&self.path);
+        cmd.arg("tag");
+        if let Some(message) = message {
+            cmd.args(["--message", message]);
+        }
+        cmd.arg(name);
+        let _tag_output = run_command(&mut cmd).await?;
+        Ok(())
+    }
+
+    async fn push<A, AS>(
+        &self,
+        remote: Option<&str>,
+        _tag: Option<&str>,
+        extra_args: A,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        AS: AsRef<std::ffi::OsStr>,
+    {
+        // tags are part of the history, so pushing the working directory parent includes them.
+        // The remote applies all changesets in a single transaction.
+        let mut cmd = Command::new("hg");
+        cmd.current_dir(&self.path);
+        cmd.args(["push", "--rev", "."]);
+        cmd.args(extra_args);
+        cmd.arg(remote.unwrap_or("default"));
+        match run_command(&mut cmd).await {
+            Ok(_) => Ok(()),
+            Err(crate::command::Error::Failed { ref output, .. })
+                if output.stdout.contains("no changes found") =>
+            {
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn tags(&self) -> Result<Vec<String>, Error> {
+        let mut cmd = Command::new("hg");
+        cmd.current_dir(&self.path);
+        cmd.args(["tags", "--quiet"]);
+        let output = run_command(&mut cmd).await?;
+        Ok(output
+            .stdout
+            .lines()
+            .map(str::trim)
+            .filter(|tag| *tag != "tip")
+            .map(ToString::to_string)
+            .collect())
+    }
+
+    async fn commits_since(&self, revision: Option<&str>) -> Result<Vec<CommitInfo>, Error> {
+        let revset = match revision {
+            Some(revision) => format!("reverse(only(., {}))", quote(revision)),
+            None => "reverse(::. - null)".to_string(),
+        };
+        // separate fields by unit separators and commits by record separators
+        let mut cmd = Command::new("hg");
+        cmd.current_dir(&self.path);
+        cmd.args(["log", "-r", &revset, "--template", r"{node}\x1f{desc}\x1e"]);
+        let output = run_command(&mut cmd).await?;
+        Ok(parse_commits(&output.stdout))
+    }
+
+    async fn latest_tag_and_revision(
+        &self,
+        tag_name: &PythonFormatString,
+        parse_version_regex: &regex::Regex,
+    ) -> Result<TagAndRevision, Error> {
+        let tag = self.latest_tag_info(tag_name, parse_version_regex).await?;
+        let revision = self.revision_info().await.ok().flatten();
+
+        Ok(TagAndRevision {
+            tool: Some(Self::TOOL.to_string()),
+            tag,
+            revision,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        f_string::PythonFormatString,
+        vcs::{CommitInfo, VersionControlSystem, hg, temp::EphemeralRepository},
+    };
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+
+    #[test]
+    fn test_latest_tag_template() {
+        crate::tests::init();
+        sim_assert_eq!(
+            super::latest_tag_template(r"^v.*$"),
+            r"{node}\n{latesttag('re:^v.*$') % '{tag}\x1f{distance}\n'}"
+        );
+        sim_assert_eq!(super::quote(r"it's \d"), r"'it\'s \\d'");
+    }
+
+    #[test]
+    fn test_parse_latest_tag() {
+        crate::tests::init();
+        sim_assert_eq!(
+            super::parse_latest_tag("abc123\nv1.2.3\x1f2\nv1.2.3-rc1\x1f2\n"),
+            ("abc123", Some(("v1.2.3", 2)))
+        );
+        sim_assert_eq!(
+            super::parse_latest_tag("abc123\nnull\x1f4\n"),
+            ("abc123", None)
+        );
+        sim_assert_eq!(
+            super::parse_latest_tag("abc123\r\nnull\x1f4\r\nv1.2.3\x1f1\r\n"),
+            ("abc123", Some(("v1.2.3", 1)))
+        );
+        sim_assert_eq!(
+            super::parse_latest_tag("abc123\nv1.2.3\x1finvalid\nv1.2.2\n"),
+            ("abc123", None)
+        );
+        sim_assert_eq!(super::parse_latest_tag("abc123\n"), ("abc123", None));
+        sim_assert_eq!(super::parse_latest_tag(""), ("", None));
+    }
+
+    #[test]
+    fn test_parse_commits() {
+        crate::tests::init();
+        sim_assert_eq!(
+            super::parse_commits(
+                "bbb\x1ffix(cli): second fix\n\nwith a body\x1e\naaa\x1ffeat: first\x1e"
+            ),
+            vec![
+                CommitInfo {
+                    sha: "bbb".to_string(),
+                    subject: "fix(cli): second fix".to_string(),
+                    body: "with a body".to_string(),
+                },
+                CommitInfo {
+                    sha: "aaa".to_string(),
+                    subject: "feat: first".to_string(),
+                    body: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_commits_edge_cases() {
+        crate::tests::init();
+        sim_assert_eq!(super::parse_commits(""), vec![]);
+        sim_assert_eq!(
+            super::parse_commits(concat!(
+                "ccc\x1ffeat!: windows\r\n\r\nBREAKING CHANGE: crlf\r\n\x1e\n",
+                "bbb\x1f\x1e\n",
+                "missing separator\x1e\n",
+                "aaa\x1ffix: keeps \x1f in the description\x1e",
+            )),
+            vec![
+                CommitInfo {
+                    sha: "ccc".to_string(),
+                    subject: "feat!: windows".to_string(),
+                    body: "BREAKING CHANGE: crlf".to_string(),
+                },
+                CommitInfo {
+                    sha: "bbb".to_string(),
+                    subject: String::new(),
+                    body: String::new(),
+                },
+                CommitInfo {
+                    sha: "aaa".to_string(),
+                    subject: "fix: keeps \x1f in the description".to_string(),
+                    body: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[ignore = "requires mercurial"]
+    #[tokio::test]
+    async fn test_commit_and_tag() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo: EphemeralRepository<hg::MercurialRepository> = EphemeralRepository::new().await?;
+        tokio::fs::write(
+            repo.path().join(".hg/hgrc"),
+            "[ui]\nusername = bumpversion <bumpversion@example.com>\n",
+        )
+        .await?;
+        let tag_name = PythonFormatString::parse("v{new_version}")?;
+        let parse_version_regex = regex::Regex::new(r"(?P<major>\d+)\.(?P<minor>\d+)")?;
+
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&tag_name, &parse_version_regex)
+            .await?;
+        sim_assert_eq!(tag_and_revision.tool.as_deref(), Some("hg"));
+        sim_assert_eq!(tag_and_revision.tag, None);
+
+        let readme = repo.path().join("README.md");
+        tokio::fs::write(&readme, "version 1.2").await?;
+        repo.add([&readme]).await?;
+        repo.unstage([&readme]).await?;
+        assert!(
+            repo.commit::<_, _, &str, &str, &str>("nothing added", [], [])
+                .await
+                .is_err()
+        );
+        repo.add([&readme]).await?;
+        repo.commit::<_, _, &str, &str, &str>("feat: initial release", [], [])
+            .await?;
+        repo.tag("v1.2", Some("release v1.2"), false).await?;
+        assert!(repo.tag("v1.3", None, true).await.is_err());
+        sim_assert_eq!(repo.tags().await?, vec!["v1.2".to_string()]);
+
+        tokio::fs::write(&readme, "version 1.3").await?;
+        sim_assert_eq!(repo.dirty_files().await?, vec![readme.clone()]);
+        repo.commit::<_, _, &str, &str, &str>("fix: second\n\nwith a body", [], [])
+            .await?;
+        sim_assert_eq!(repo.dirty_files().await?, Vec::<std::path::PathBuf>::new());
+
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&tag_name, &parse_version_regex)
+            .await?;
+        let tag = tag_and_revision.tag.unwrap();
+        sim_assert_eq!(tag.current_tag, "v1.2");
+        sim_assert_eq!(tag.current_version, "1.2");
+        sim_assert_eq!(tag.distance_to_latest_tag, 2);
+        sim_assert_eq!(
+            tag_and_revision
+                .revision
+                .map(|revision| revision.branch_name),
+            Some("default".to_string())
+        );
+
+        let commits = repo.commits_since(Some("v1.2")).await?;
+        sim_assert_eq!(
+            commits
+                .iter()
+                .map(|commit| (commit.subject.as_str(), commit.body.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("fix: second", "with a body"), ("release v1.2", "")]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,480 @@
+//! In-memory version control system.
+//!
+//! Implements the `VersionControlSystem` trait without touching the filesystem,
+//! recording all staged files, commits, tags, and pushes so they can be inspected in tests.
+use crate::{
+    f_string::PythonFormatString,
+    vcs::{
+        CommitInfo, RevisionInfo, TagAndRevision, TagInfo, VersionControlSystem,
+        git::{BRANCH_NAME_REGEX, get_version_from_tag},
+    },
+};
+use parking_lot::Mutex;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// In-memory VCS error type.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("regex error: {0}")]
+    Regex(#[from] regex::Error),
+
+    #[error("nothing to commit")]
+    NothingToCommit,
+
+    #[error("cannot tag without any commits")]
+    MissingHead,
+
+    #[error("tag {0:?} already exists")]
+    TagExists(String),
+
+    #[error("unknown revision {0:?}")]
+    UnknownRevision(String),
+
+    #[error("failed to template {format_string}")]
+    Format {
+        #[source]
+        source: crate::f_string::FormatError,
+        format_string: PythonFormatString,
+    },
+}
+
+/// A recorded commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The commit SHA hash.
+    pub sha: String,
+    /// The commit message.
+    pub message: String,
+    /// The files staged for the commit, sorted by path.
+    pub files: Vec<PathBuf>,
+    /// Extra arguments passed to the commit.
+    pub args: Vec<String>,
+}
+
+/// A recorded tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// The tag name.
+    pub name: String,
+    /// The tag message, if the tag is annotated.
+    pub message: Option<String>,
+    /// Whether the tag is signed.
+    pub sign: bool,
+    /// The SHA hash of the tagged commit.
+    pub target: String,
+}
+
+/// A recorded push.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Push {
+    /// The remote, or `None` for the default remote.
+    pub remote: Option<String>,
+    /// The tag pushed together with the current branch, if any.
+    pub tag: Option<String>,
+    /// Extra arguments passed to the push.
+    pub args: Vec<String>,
+}
+
+/// Everything recorded by a [`MemoryRepository`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    /// The name of the current branch.
+    pub branch_name: String,
+    /// Files staged for the next commit.
+    pub staged: BTreeSet<PathBuf>,
+    /// Files with uncommitted changes.
+    pub dirty: BTreeSet<PathBuf>,
+    /// Commits, oldest first.
+    pub commits: Vec<Commit>,
+    /// Tags, in the order they were created.
+    pub tags: Vec<Tag>,
+    /// Pushes, in the order they were performed.
+    pub pushes: Vec<Push>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            branch_name: "main".to_string(),
+            staged: BTreeSet::default(),
+            dirty: BTreeSet::default(),
+            commits: Vec::default(),
+            tags: Vec::default(),
+            pushes: Vec::default(),
+        }
+    }
+}
+
+/// An in-memory repository that records all changes.
+///
+/// Commits receive deterministic SHA hashes based on their position in the history.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MemoryRepository {
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+impl MemoryRepository {
+    /// Returns a snapshot of the recorded state.
+    #[must_use]
+    pub fn state(&self) -> State {
+        self.state.lock().clone()
+    }
+
+    /// Set the name of the current branch.
+    pub fn set_branch_name(&self, branch_name: impl Into<String>) {
+        self.state.lock().branch_name = branch_name.into();
+    }
+
+    /// Mark `files` as having uncommitted changes.
+    pub fn set_dirty_files<P>(&self, files: impl IntoIterator<Item = P>)
+    where
+        P: Into<PathBuf>,
+    {
+        self.state.lock().dirty = files.into_iter().map(Into::into).collect();
+    }
+
+    fn commit_info(commit: &Commit) -> CommitInfo {
+        let (subject, body) = commit
+            .message
+            .split_once('\n')
+            .unwrap_or((&commit.message, ""));
+        CommitInfo {
+            sha: commit.sha.clone(),
+            subject: subject.trim().to_string(),
+            body: body.trim().to_string(),
+        }
+    }
+
+    /// Get the latest tag reachable from the current commit, like `git describe --tags --long`.
+    fn latest_tag_info(
+        &self,
+        tag_name: &PythonFormatString,
+        parse_version_regex: &regex::Regex,
+    ) -> Result<Option<TagInfo>, Error> {
+        let tag_pattern = tag_name
+            .format(&[("new_version", "*")].into_iter().collect(), true)
+            .map_err(|source| Error::Format {
+                source,
+                format_string: tag_name.clone(),
+            })?;
+        let tag_regex = regex::Regex::new(&format!(
+            "^{}$",
+            regex::escape(&tag_pattern).replace(r"\*", ".*")
+        ))?;
+
+        let state = self.state.lock();
+        let Some(head) = state.commits.last() else {
+            return Ok(None);
+        };
+        // prefer annotated tags, then newer tags, like `git describe`
+        let latest_tag = state
+            .commits
+            .iter()
+            .rev()
+            .enumerate()
+            .find_map(|(distance, commit)| {
+                let tag = state
+                    .tags
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, tag)| tag.target == commit.sha && tag_regex.is_match(&tag.name))
+                    .max_by_key(|(idx, tag)| (tag.message.is_some(), *idx))?
+                    .1;
+                Some((distance, tag))
+            });
+        let Some((distance_to_latest_tag, tag)) = latest_tag else {
+            return Ok(None);
+        };
+
+        let current_tag = tag.name.clone();
+        let version = get_version_from_tag(&current_tag, tag_name, parse_version_regex)?;
+        let current_version = version
+            .unwrap_or(current_tag.trim_start_matches('v'))
+            .to_string();
+        Ok(Some(TagInfo {
+            dirty: !state.dirty.is_empty(),
+            commit_sha: head.sha.clone(),
+            distance_to_latest_tag,
+            current_tag,
+            current_version,
+        }))
+    }
+
+    /// Returns the revision information, or `None` if there are no commits.
+    fn revision_info(&self) -> Option<RevisionInfo> {
+        let state = self.state.lock();
+        if state.commits.is_empty() {
+            return None;
+        }
+        let short_branch_name: String = BRANCH_NAME_REGEX
+            .replace_all(&state.branch_name, "")
+            .to_lowercase()
+            .chars()
+            .take(20)
+            .collect();
+        Some(RevisionInfo {
+            branch_name: state.branch_name.clone(),
+            short_branch_name,
+            repository_root: self.path.clone(),
+        })
+    }
+}
+
+impl VersionControlSystem for MemoryRepository {
+    type Error = Error;
+
+    const TOOL: &'static str = "memory";
+
+    fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        Ok(Self {
+            path: path.into(),
+            state: Mutex::default(),
+        })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn add<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        let files = files.into_iter().map(|file| PathBuf::from(file.as_ref()));
+        self.state.lock().staged.extend(files);
+        Ok(())
+    }
+
+    async fn unstage<P>(&self, files: impl IntoIterator<Item = P>) -> Result<(), Error>
+    where
+        P: AsRef<std::ffi::OsStr>,
+    {
+        let mut state = self.state.lock();
+        for file in files {
+            state.staged.remove(Path::new(file.as_ref()));
+        }
+        Ok(())
+    }
+
+    async fn commit<A, E, AS, EK, EV>(
+        &self,
+        message: &str,
+        extra_args: A,
+        _env: E,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        E: IntoIterator<Item = (EK, EV)>,
+        AS: AsRef<std::ffi::OsStr>,
+        EK: AsRef<std::ffi::OsStr>,
+        EV: AsRef<std::ffi::OsStr>,
+    {
+        let args: Vec<String> = extra_args
+            .into_iter()
+            .map(|arg| arg.as_ref().to_string_lossy().to_string())
+            .collect();
+        let mut state = self.state.lock();
+        if state.staged.is_empty() && !args.iter().any(|arg| arg == "--allow-empty") {
+            return Err(Error::NothingToCommit);
+        }
+        let files = std::mem::take(&mut state.staged);
+        state.dirty.retain(|file| !files.contains(file));
+        let sha = format!("{:040x}", state.commits.len() + 1);
+        state.commits.push(Commit {
+            sha,
+            message: message.to_string(),
+            files: files.into_iter().collect(),
+            args,
+        });
+        Ok(())
+    }
+
+    async fn tag(&self, name: &str, message: Option<&str>, sign: bool) -> Result<(), Error> {
+        let mut state = self.state.lock();
+        let target = state
+            .commits
+            .last()
+            .map(|commit| commit.sha.clone())
+            .ok_or(Error::MissingHead)?;
+        if state.tags.iter().any(|tag| tag.name == name) {
+            return Err(Error::TagExists(name.to_string()));
+        }
+        state.tags.push(Tag {
+            name: name.to_string(),
+            message: message.map(ToString::to_string),
+            sign,
+            target,
+        });
+        Ok(())
+    }
+
+    async fn push<A, AS>(
+        &self,
+        remote: Option<&str>,
+        tag: Option<&str>,
+        extra_args: A,
+    ) -> Result<(), Error>
+    where
+        A: IntoIterator<Item = AS>,
+        AS: AsRef<std::ffi::OsStr>,
+    {
+        let args = extra_args
+            .into_iter()
+            .map(|arg| arg.as_ref().to_string_lossy().to_string())
+            .collect();
+        self.state.lock().pushes.push(Push {
+            remote: remote.map(ToString::to_string),
+            tag: tag.map(ToString::to_string),
+            args,
+        });
+        Ok(())
+    }
+
+    async fn tags(&self) -> Result<Vec<String>, Error> {
+        let state = self.state.lock();
+        Ok(state.tags.iter().map(|tag| tag.name.clone()).collect())
+    }
+
+    async fn commits_since(&self, revision: Option<&str>) -> Result<Vec<CommitInfo>, Error> {
+        let state = self.state.lock();
+        let start = match revision {
+            None => 0,
+            Some(revision) => {
+                let sha = state
+                    .tags
+                    .iter()
+                    .find(|tag| tag.name == revision)
+                    .map_or(revision, |tag| tag.target.as_str());
+                state
+                    .commits
+                    .iter()
+                    .position(|commit| commit.sha == sha)
+                    .ok_or_else(|| Error::UnknownRevision(revision.to_string()))?
+                    + 1
+            }
+        };
+        Ok(state.commits[start..]
+            .iter()
+            .rev()
+            .map(Self::commit_info)
+            .collect())
+    }
+
+    async fn dirty_files(&self) -> Result<Vec<PathBuf>, Error> {
+        Ok(self.state.lock().dirty.iter().cloned().collect())
+    }
+
+    async fn latest_tag_and_revision(
+        &self,
+        tag_name: &PythonFormatString,
+        parse_version_regex: &regex::Regex,
+    ) -> Result<TagAndRevision, Error> {
+        let tag = self.latest_tag_info(tag_name, parse_version_regex)?;
+        let revision = self.revision_info();
+
+        Ok(TagAndRevision {
+            tool: Some(Self::TOOL.to_string()),
+            tag,
+            revision,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Commit, MemoryRepository, Tag};
+    use crate::{
+        config::GlobalConfigFinalized,
+        vcs::{TagInfo, VersionControlSystem},
+    };
+    use color_eyre::eyre;
+    use similar_asserts::assert_eq as sim_assert_eq;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_memory_repository() -> eyre::Result<()> {
+        crate::tests::init();
+        let repo = MemoryRepository::open("/repo")?;
+        let config = GlobalConfigFinalized::default();
+        let no_env: [(&str, &str); 0] = [];
+
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&config.tag_name, &config.parse_version_pattern)
+            .await?;
+        sim_assert_eq!(tag_and_revision.tag, None);
+        sim_assert_eq!(tag_and_revision.revision, None);
+        assert!(repo.tag("v1.2.3", None, false).await.is_err());
+        assert!(repo.commit("empty", [""; 0], no_env).await.is_err());
+
+        repo.commit("initial commit", ["--allow-empty"], no_env)
+            .await?;
+        repo.tag("v1.2.3", Some("Release 1.2.3"), false).await?;
+        assert!(repo.tag("v1.2.3", None, false).await.is_err());
+
+        repo.set_dirty_files(["/repo/VERSION", "/repo/README.md"]);
+        repo.add(["/repo/VERSION", "/repo/README.md"]).await?;
+        repo.unstage(["/repo/README.md"]).await?;
+        repo.commit("fix: bump\n\nwith a body", ["--no-verify"], no_env)
+            .await?;
+        sim_assert_eq!(
+            repo.dirty_files().await?,
+            vec![PathBuf::from("/repo/README.md")]
+        );
+
+        let tag_and_revision = repo
+            .latest_tag_and_revision(&config.tag_name, &config.parse_version_pattern)
+            .await?;
+        sim_assert_eq!(tag_and_revision.tool.as_deref(), Some("memory"));
+        sim_assert_eq!(
+            tag_and_revision.tag,
+            Some(TagInfo {
+                dirty: true,
+                commit_sha: format!("{:040x}", 2),
+                distance_to_latest_tag: 1,
+                current_tag: "v1.2.3".to_string(),
+                current_version: "1.2.3".to_string(),
+            })
+        );
+        sim_assert_eq!(
+            tag_and_revision
+                .revision
+                .map(|revision| revision.repository_root),
+            Some(PathBuf::from("/repo"))
+        );
+
+        let commits = repo.commits_since(Some("v1.2.3")).await?;
+        sim_assert_eq!(
+            commits
+                .iter()
+                .map(|commit| (commit.subject.as_str(), commit.body.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("fix: bump", "with a body")]
+        );
+        sim_assert_eq!(repo.commits_since(None).await?.len(), 2);
+        assert!(repo.commits_since(Some("v0.1.0")).await.is_err());
+
+        let state = repo.state();
+        sim_assert_eq!(
+            state.commits.last(),
+            Some(&Commit {
+                sha: format!("{:040x}", 2),
+                message: "fix: bump\n\nwith a body".to_string(),
+                files: vec![PathBuf::from("/repo/VERSION")],
+                args: vec!["--no-verify".to_string()],
+            })
+        );
+        sim_assert_eq!(
+            state.tags,
+            vec![Tag {
+                name: "v1.2.3".to_string(),
+                message: Some("Release 1.2.3".to_string()),
+                sign: false,
+                target: format!("{:040x}", 1),
+            }]
+        );
+        Ok(())
+    }
+}
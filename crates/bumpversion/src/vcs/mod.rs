@@ -3,6 +3,9 @@
 //! Defines the `VersionControlSystem` trait and related data structures
 //! for interacting with git and other VCS backends.
 pub mod git;
+pub mod gitoxide;
+pub mod hg;
+pub mod memory;
 
 #[cfg(test)]
 pub mod temp;
@@ -39,21 +42,63 @@ pub struct RevisionInfo {
     pub repository_root: PathBuf,
 }
 
+/// A commit in the history of the repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitInfo {
+    /// The commit SHA hash.
+    pub sha: String,
+    /// The first line of the commit message.
+    pub subject: String,
+    /// The remainder of the commit message.
+    pub body: String,
+}
+
 /// Combined container for both optional `TagInfo` and `RevisionInfo`.
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TagAndRevision {
+    /// The name of the VCS tool, e.g. `git` or `hg`.
+    pub tool: Option<String>,
     /// The current tag information.
     pub tag: Option<TagInfo>,
     /// The latest revision.
     pub revision: Option<RevisionInfo>,
 }
 
+/// Implementation used to access git repositories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GitBackend {
+    /// Run `git` commands, see [`git::GitRepository`].
+    #[default]
+    Command,
+    /// Access the repository in-process, see [`gitoxide::GitoxideRepository`].
+    Gitoxide,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[error("invalid git backend {0:?} (expected one of `command`, `gitoxide`)")]
+pub struct InvalidGitBackendError(String);
+
+impl std::str::FromStr for GitBackend {
+    type Err = InvalidGitBackendError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "command" | "git" => Ok(Self::Command),
+            "gitoxide" | "gix" => Ok(Self::Gitoxide),
+            _ => Err(InvalidGitBackendError(value.to_string())),
+        }
+    }
+}
+
 /// Abstract interface for version control systems.
 ///
 /// Implementors can open repositories, add/commit files, tag releases, and query history.
 pub trait VersionControlSystem {
     type Error: std::error::Error + Send + Sync + 'static;
 
+    /// The name of the VCS tool, e.g. `git`.
+    const TOOL: &'static str;
+
     /// Open the VCS repository at the given path.
     fn open(path: impl Into<PathBuf>) -> Result<Self, Self::Error>
     where
@@ -62,7 +107,24 @@ pub trait VersionControlSystem {
     /// Return the root path of the repository.
     fn path(&self) -> &Path;
 
+    /// Check that the repository supports committing with `commit_args`,
+    /// signing tags if `sign_tags` is set, and pushing if `push` is set.
+    ///
+    /// Called when planning a bump, so that nothing is written if the bump cannot be applied.
+    /// All features are supported by default.
+    fn check_supported(
+        &self,
+        commit_args: &[String],
+        sign_tags: bool,
+        push: bool,
+    ) -> Result<(), Self::Error> {
+        let _ = (commit_args, sign_tags, push);
+        Ok(())
+    }
+
     /// Stage a set of files for commit.
+    ///
+    /// Untracked files are added as well, unless they are ignored.
     fn add<P>(
         &self,
         files: impl IntoIterator<Item = P>,
@@ -70,6 +132,14 @@ pub trait VersionControlSystem {
     where
         P: AsRef<std::ffi::OsStr>;
 
+    /// Unstage a set of files staged with [`add`](Self::add), keeping their changes.
+    fn unstage<P>(
+        &self,
+        files: impl IntoIterator<Item = P>,
+    ) -> impl Future<Output = Result<(), Self::Error>>
+    where
+        P: AsRef<std::ffi::OsStr>;
+
     /// Create a commit with the given message and environment.
     fn commit<A, E, AS, EK, EV>(
         &self,
@@ -92,9 +162,31 @@ pub trait VersionControlSystem {
         sign: bool,
     ) -> impl Future<Output = Result<(), Self::Error>>;
 
+    /// Push the current branch and the `tag` to the `remote` atomically.
+    ///
+    /// Either all refs are updated on the remote, or none of them.
+    /// If `remote` is `None`, the default remote of the VCS is used.
+    fn push<A, AS>(
+        &self,
+        remote: Option<&str>,
+        tag: Option<&str>,
+        extra_args: A,
+    ) -> impl Future<Output = Result<(), Self::Error>>
+    where
+        A: IntoIterator<Item = AS>,
+        AS: AsRef<std::ffi::OsStr>;
+
     /// List all tags in the repository.
     fn tags(&self) -> impl Future<Output = Result<Vec<String>, Self::Error>>;
 
+    /// List the commits reachable from `HEAD` but not from `revision`, newest first.
+    ///
+    /// If `revision` is `None`, all commits reachable from `HEAD` are listed.
+    fn commits_since(
+        &self,
+        revision: Option<&str>,
+    ) -> impl Future<Output = Result<Vec<CommitInfo>, Self::Error>>;
+
     /// List files with uncommitted changes.
     fn dirty_files(&self) -> impl Future<Output = Result<Vec<PathBuf>, Self::Error>>;
 
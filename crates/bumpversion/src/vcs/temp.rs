@@ -44,7 +44,7 @@ where
 
     async fn init(path: &Path) -> eyre::Result<()> {
         tokio::fs::create_dir_all(path).await?;
-        let mut cmd = Command::new("git");
+        let mut cmd = Command::new(VCS::TOOL);
         cmd.args(["init"]);
         cmd.current_dir(path);
         let _ = run_command(&mut cmd).await?;
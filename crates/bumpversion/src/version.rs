@@ -10,26 +10,61 @@ use std::collections::{HashMap, HashSet};
 /// Maps component names (e.g., "major", "minor") to their string values.
 pub type RawVersion<'a> = HashMap<&'a str, &'a str>;
 
-// #[derive(Debug, Clone, PartialEq, Eq)]
-// pub struct ValuesFunction<'a> {
-//     values: &'a [String],
-// }
-//
-// impl ValuesFunction<'_> {
-//     /// Return the item after ``value`` in the list
-//     fn bump(&self, value: &str) -> Result<&str> {
-//         let current_idx = self.values.iter().position(|v| *v == value);
-//         let current_idx =
-//             current_idx.ok_or_else(|| eyre::eyre!("{value:?} must be one of {:?}", self.values))?;
-//         let bumped_value = self.values.get(current_idx + 1).ok_or_else(|| {
-//             eyre::eyre!(
-//                 "the component has already the maximum value among {:?} and cannot be bumped.",
-//                 self.values
-//             )
-//         })?;
-//         Ok(bumped_value.as_str())
-//     }
-// }
+/// Function for bumping version components that have an explicit list of values.
+///
+/// Bumping moves the component to the value after the current one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValuesFunction<'a> {
+    /// The ordered list of possible values.
+    pub values: &'a [String],
+    /// The value that is optional to include in the version.
+    pub optional_value: Option<&'a str>,
+    /// The value dependent components are reset to.
+    pub first_value: Option<&'a str>,
+}
+
+impl<'a> ValuesFunction<'a> {
+    /// Create a new `ValuesFunction`.
+    ///
+    /// Both `optional_value` and `first_value` default to the first of the `values`.
+    #[must_use]
+    pub fn new(
+        values: &'a [String],
+        optional_value: Option<&'a str>,
+        first_value: Option<&'a str>,
+    ) -> Self {
+        let first = values.first().map(String::as_str);
+        Self {
+            values,
+            optional_value: optional_value.or(first),
+            first_value: first_value.or(first),
+        }
+    }
+
+    /// Return the item after `value` in the list of values.
+    ///
+    /// # Errors
+    /// - When `value` is not one of the values.
+    /// - When `value` is already the last value.
+    pub fn bump(&self, value: &str) -> Result<&'a str, BumpError> {
+        let current_idx =
+            self.values
+                .iter()
+                .position(|v| v == value)
+                .ok_or_else(|| BumpError::InvalidValue {
+                    value: value.to_string(),
+                    values: self.values.to_vec(),
+                })?;
+        let bumped_value = self
+            .values
+            .get(current_idx + 1)
+            .ok_or_else(|| BumpError::MaxValue {
+                value: value.to_string(),
+                values: self.values.to_vec(),
+            })?;
+        Ok(bumped_value.as_str())
+    }
+}
 
 // TODO: refactor this
 /// Numeric parsing and bumping utilities for version components.
@@ -216,6 +251,127 @@ pub mod numeric {
     }
 }
 
+/// Calendar versioning utilities for version components.
+pub mod calver {
+    use chrono::Datelike;
+    use std::collections::HashMap;
+
+    /// Errors that can occur when formatting a `CalVer` component.
+    #[derive(thiserror::Error, Debug)]
+    pub enum Error {
+        /// The `calver_format` is not a valid format string.
+        #[error("invalid calver format {format:?}")]
+        InvalidFormatString {
+            /// Underlying parse error.
+            #[source]
+            source: crate::f_string::ParseError,
+            /// The offending calver format.
+            format: String,
+        },
+        /// The `calver_format` references an unknown calver code.
+        #[error("invalid calver format {format:?}")]
+        Format {
+            /// Underlying format error.
+            #[source]
+            source: crate::f_string::FormatError,
+            /// The offending calver format.
+            format: String,
+        },
+    }
+
+    /// Source of the current date used when bumping `CalVer` components.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub enum Clock {
+        /// Use the local date of the system.
+        #[default]
+        System,
+        /// Use a fixed date.
+        ///
+        /// Useful for deterministic tests.
+        Fixed(chrono::NaiveDate),
+    }
+
+    impl Clock {
+        /// Return the current date according to this clock.
+        #[must_use]
+        pub fn today(&self) -> chrono::NaiveDate {
+            match self {
+                Self::System => chrono::Local::now().date_naive(),
+                Self::Fixed(date) => *date,
+            }
+        }
+    }
+
+    /// Return the `CalVer` codes (e.g. `YYYY`, `0M`) and their values for `date`.
+    ///
+    /// The codes follow the ones supported by `bump-my-version`.
+    #[must_use]
+    pub fn calver_codes(date: chrono::NaiveDate) -> HashMap<&'static str, String> {
+        let quarter = date.month0() / 3 + 1;
+        [
+            ("YYYY", "%Y"),
+            ("YY", "%-y"),
+            ("0Y", "%y"),
+            ("MMM", "%b"),
+            ("MM", "%-m"),
+            ("0M", "%m"),
+            ("DD", "%-d"),
+            ("0D", "%d"),
+            ("JJJ", "%-j"),
+            ("00J", "%j"),
+            ("WW", "%-W"),
+            ("0W", "%W"),
+            ("UU", "%-U"),
+            ("0U", "%U"),
+            ("VV", "%-V"),
+            ("0V", "%V"),
+            ("GGGG", "%G"),
+            ("GG", "%-g"),
+            ("0G", "%g"),
+        ]
+        .into_iter()
+        .map(|(code, format)| (code, date.format(format).to_string()))
+        .chain([("Q", quarter.to_string())])
+        .collect()
+    }
+
+    /// Function for bumping `CalVer` version components.
+    ///
+    /// Bumping always yields the formatted date of today.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct CalVerFunction<'a> {
+        /// The format string of the component, e.g. `{YYYY}.{0M}`.
+        pub calver_format: &'a str,
+    }
+
+    impl<'a> CalVerFunction<'a> {
+        /// Create a new `CalVerFunction` for the given `calver_format`.
+        #[must_use]
+        pub fn new(calver_format: &'a str) -> Self {
+            Self { calver_format }
+        }
+
+        /// Format the `calver_format` for the given date.
+        ///
+        /// # Errors
+        /// - When the `calver_format` is not a valid format string.
+        /// - When the `calver_format` uses an unknown calver code.
+        pub fn bump(&self, today: chrono::NaiveDate) -> Result<String, Error> {
+            let format_string = crate::f_string::PythonFormatString::parse(self.calver_format)
+                .map_err(|source| Error::InvalidFormatString {
+                    source,
+                    format: self.calver_format.to_string(),
+                })?;
+            format_string
+                .format(&calver_codes(today), true)
+                .map_err(|source| Error::Format {
+                    source,
+                    format: self.calver_format.to_string(),
+                })
+        }
+    }
+}
+
 /// A single version component, combining a value and its bump/reset specification.
 ///
 /// Determines how the component is bumped and how dependents are reset.
@@ -224,6 +380,7 @@ pub struct Component {
     // value: String,
     value: Option<String>,
     spec: VersionComponentSpec,
+    clock: calver::Clock,
     // todo: add spec here too?
     // pub func: Box<dyn BumpComponent<Error = >>, // avoid this and just dispatch in `bump()`?
 
@@ -254,9 +411,28 @@ pub enum BumpError {
     /// Underlying numeric bump error (e.g., missing digits, overflow).
     #[error(transparent)]
     Numeric(#[from] numeric::Error),
+    /// Underlying `CalVer` bump error (e.g., invalid calver format).
+    #[error(transparent)]
+    CalVer(#[from] calver::Error),
     /// Specified component name does not exist in the version.
     #[error("invalid version component {0:?}")]
     InvalidComponent(String),
+    /// The current value is not one of the configured values of the component.
+    #[error("{value:?} must be one of {values:?}")]
+    InvalidValue {
+        /// The offending value.
+        value: String,
+        /// The configured values of the component.
+        values: Vec<String>,
+    },
+    /// The component is already at its last value and cannot be bumped.
+    #[error("{value:?} is already the last value among {values:?} and cannot be bumped")]
+    MaxValue {
+        /// The current (last) value.
+        value: String,
+        /// The configured values of the component.
+        values: Vec<String>,
+    },
 }
 
 impl Component {
@@ -283,53 +459,112 @@ impl Component {
         Self {
             value: value.map(std::string::ToString::to_string),
             spec,
+            clock: calver::Clock::default(),
+        }
+    }
+
+    /// Use the given `clock` when bumping `CalVer` components.
+    #[must_use]
+    pub fn with_clock(mut self, clock: calver::Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Return the `CalVer` function if this component has a `calver_format`.
+    fn calver_function(&self) -> Option<calver::CalVerFunction<'_>> {
+        self.spec
+            .calver_format
+            .as_deref()
+            .map(calver::CalVerFunction::new)
+    }
+
+    /// Return the values function if this component has a list of values.
+    fn values_function(&self) -> Option<ValuesFunction<'_>> {
+        if self.spec.values.is_empty() {
+            None
+        } else {
+            Some(ValuesFunction::new(
+                &self.spec.values,
+                self.spec.optional_value.as_deref(),
+                self.spec.first_value.as_deref(),
+            ))
         }
     }
 
     /// Return the effective current value of this component.
     ///
     /// Falls back to the `spec.first_value` if no explicit value is set.
+    /// Components with a list of values fall back to their optional value instead.
     #[must_use]
     pub fn value(&self) -> Option<&str> {
-        self.value.as_deref().or(self.spec.first_value.as_deref())
+        match self.values_function() {
+            Some(func) => self.value.as_deref().or(func.optional_value),
+            None => self.value.as_deref().or(self.spec.first_value.as_deref()),
+        }
+    }
+
+    /// Return the value that is optional to include when serializing this component.
+    ///
+    /// Components with a list of values default to their first value.
+    /// `CalVer` components are never optional.
+    #[must_use]
+    pub fn optional_value(&self) -> Option<&str> {
+        if self.calver_function().is_some() {
+            return None;
+        }
+        match self.values_function() {
+            Some(func) => func.optional_value,
+            None => self.spec.optional_value.as_deref(),
+        }
     }
 
     /// Return a new `Component` initialized with its `spec.first_value`.
     ///
+    /// Components with a list of values default to their first value.
+    /// `CalVer` components are initialized with the date of today.
     /// Useful for resetting dependent components.
-    #[must_use]
-    pub fn first(&self) -> Self {
-        Self {
-            value: self.spec.first_value.clone(),
+    ///
+    /// # Errors
+    /// Returns `BumpError::CalVer` if the `calver_format` is invalid.
+    pub fn first(&self) -> Result<Self, BumpError> {
+        let first_value = if let Some(func) = self.calver_function() {
+            Some(func.bump(self.clock.today())?)
+        } else if let Some(func) = self.values_function() {
+            func.first_value.map(ToString::to_string)
+        } else {
+            let func = numeric::NumericFunction::new(
+                self.spec.first_value.as_deref(),
+                self.spec.optional_value.as_deref(),
+            )?;
+            Some(func.first_value.to_string())
+        };
+        Ok(Self {
+            value: first_value,
             ..self.clone()
-        }
+        })
     }
 
     /// Bump this component according to its specification.
     ///
+    /// `CalVer` components bump to the date of today.
     /// For components with explicit value lists, uses those; otherwise numeric bump.
     ///
     /// # Errors
-    /// Returns `BumpError::Numeric` or `BumpError::InvalidComponent` on failure.
+    /// Returns `BumpError::Numeric` for invalid numeric values, `BumpError::CalVer` for
+    /// invalid calver formats, or `BumpError::InvalidValue` and `BumpError::MaxValue` for
+    /// components with a list of values.
     pub fn bump(&self) -> Result<Self, BumpError> {
-        let value = if self.spec.values.is_empty() {
-            // numeric
+        let value = if let Some(func) = self.calver_function() {
+            func.bump(self.clock.today())?
+        } else if let Some(func) = self.values_function() {
+            func.bump(self.value().unwrap_or_default())?.to_string()
+        } else {
             let func = numeric::NumericFunction::new(
                 self.spec.first_value.as_deref(),
                 self.spec.optional_value.as_deref(),
             )?;
-            func.bump(self.value.as_deref().unwrap_or("0"))
-        } else {
-            todo!("value bump function");
-            // let func = ValuesFunction {
-            //     values: self.spec.values.as_slice(),
-            // };
-            // let value = self
-            //     .value
-            //     .as_deref()
-            //     .unwrap_or(self.spec.values[0].as_str());
-            // func.bump(value).map(ToString::to_string)
-        }?;
+            func.bump(self.value.as_deref().unwrap_or("0"))?
+        };
         Ok(Self {
             value: Some(value),
             ..self.clone()
@@ -361,6 +596,16 @@ impl std::fmt::Display for Version {
     }
 }
 
+/// Serializes the version as a map of its component names to their values.
+impl serde::Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.components.iter().map(|(k, v)| (k, v.value())))
+    }
+}
+
 impl IntoIterator for Version {
     type Item = (String, Component);
     type IntoIter = indexmap::map::IntoIter<String, Component>;
@@ -426,14 +671,14 @@ impl Version {
 
     /// Iterate over the version components in order.
     #[must_use]
-    pub fn iter(&self) -> indexmap::map::Iter<String, Component> {
+    pub fn iter(&self) -> indexmap::map::Iter<'_, String, Component> {
         self.components.iter()
     }
 
     /// Iterate over names of non-optional components (those with explicit values).
     pub fn required_component_names(&self) -> impl Iterator<Item = &str> {
         self.iter()
-            .filter(|(_, v)| v.value() != v.spec.optional_value.as_deref())
+            .filter(|(_, v)| v.value() != v.optional_value())
             .map(|(k, _)| k.as_str())
     }
 
@@ -494,9 +739,13 @@ impl Version {
 
         let should_reset = components_to_reset.contains(component);
         if !should_reset {
-            new_components.insert(component.to_string(), self.components[component].bump()?);
-            let dependants = self.spec.dependents(component);
-            components_to_reset.extend(dependants);
+            let bumped = self.components[component].bump()?;
+            // `CalVer` components do not change when bumped on the same day
+            if bumped != self.components[component] {
+                let dependants = self.spec.dependents(component);
+                components_to_reset.extend(dependants);
+            }
+            new_components.insert(component.to_string(), bumped);
         }
 
         // dbg!(&new_components, &components_to_reset);
@@ -506,7 +755,7 @@ impl Version {
             // dbg!(&self.components);
             let is_independent = self.components[comp_name].spec.independent == Some(true);
             if !is_independent {
-                new_components.insert(comp_name.to_string(), self.components[comp_name].first());
+                new_components.insert(comp_name.to_string(), self.components[comp_name].first()?);
                 // *new_components.entry(comp_name.to_string()).or_default() =
                 //     self.components[comp_name].first();
             }
@@ -528,6 +777,7 @@ pub struct VersionSpec {
     components: VersionComponentConfigs,
     dependency_map: HashMap<String, Vec<String>>,
     components_to_always_increment: Vec<String>,
+    clock: calver::Clock,
 }
 
 impl VersionSpec {
@@ -582,9 +832,17 @@ impl VersionSpec {
             components,
             dependency_map,
             components_to_always_increment,
+            clock: calver::Clock::default(),
         }
     }
 
+    /// Use the given `clock` when bumping `CalVer` components.
+    #[must_use]
+    pub fn with_clock(mut self, clock: calver::Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Return the set of component names that transitively depend on `comp_name`.
     #[must_use]
     pub fn dependents(&self, comp_name: &str) -> HashSet<&str> {
@@ -624,7 +882,8 @@ impl VersionSpec {
             .iter()
             .map(|(comp_name, comp_config)| {
                 let comp_value = raw_components.get(comp_name.as_str()).copied();
-                let component = Component::new(comp_value, comp_config.clone());
+                let component =
+                    Component::new(comp_value, comp_config.clone()).with_clock(self.clock);
                 (comp_name.to_string(), component)
             })
             .collect();
@@ -646,9 +905,9 @@ pub enum SerializeError {
         /// List of attempted (index, format pattern) pairs.
         formats: Vec<(usize, PythonFormatString)>,
     },
-    /// A required argument for formatting was missing.
+    /// A required argument for formatting was missing or could not be formatted.
     #[error(transparent)]
-    MissingArgument(#[from] crate::f_string::MissingArgumentError),
+    Format(#[from] crate::f_string::FormatError),
 }
 
 /// Attempts to serialize a version with the given serialization format.
@@ -753,8 +1012,261 @@ fn parse_raw_version<'a>(version: &'a str, pattern: &'a regex::Regex) -> RawVers
 
 #[cfg(test)]
 mod tests {
+    use crate::config::version::{VersionComponentConfigs, VersionComponentSpec};
+    use crate::f_string::PythonFormatString;
     use color_eyre::eyre;
     use similar_asserts::assert_eq as sim_assert_eq;
+    use std::collections::HashMap;
+
+    fn release_version_spec() -> super::VersionSpec {
+        let components: VersionComponentConfigs = [
+            ("major", VersionComponentSpec::default()),
+            ("minor", VersionComponentSpec::default()),
+            ("patch", VersionComponentSpec::default()),
+            (
+                "release",
+                VersionComponentSpec {
+                    values: vec!["dev".to_string(), "rc".to_string(), "final".to_string()],
+                    optional_value: Some("final".to_string()),
+                    ..VersionComponentSpec::default()
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|(name, spec)| (name.to_string(), spec))
+        .collect();
+        super::VersionSpec::from_components(components)
+    }
+
+    fn serialize_release_version(version: &super::Version) -> eyre::Result<String> {
+        let patterns = [
+            PythonFormatString::parse("{major}.{minor}.{patch}-{release}")?,
+            PythonFormatString::parse("{major}.{minor}.{patch}")?,
+        ];
+        let ctx: HashMap<&str, &str> = HashMap::new();
+        Ok(version.serialize(&patterns, &ctx)?)
+    }
+
+    #[test]
+    fn test_bump_values_component() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let parse_regex = regex::Regex::new(
+            r"(?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+)(-(?P<release>[a-z]+))?",
+        )?;
+        let spec = release_version_spec();
+        let version = super::Version::parse("1.2.3-dev", &parse_regex, &spec)
+            .ok_or_else(|| eyre::eyre!("failed to parse version"))?;
+
+        let version = version.bump("release")?;
+        sim_assert_eq!(version.get("release").and_then(|c| c.value()), Some("rc"));
+        sim_assert_eq!(serialize_release_version(&version)?, "1.2.3-rc");
+
+        // the optional value is omitted when serializing
+        let version = version.bump("release")?;
+        sim_assert_eq!(
+            version.get("release").and_then(|c| c.value()),
+            Some("final")
+        );
+        sim_assert_eq!(serialize_release_version(&version)?, "1.2.3");
+
+        let err = version.bump("release").unwrap_err();
+        assert!(
+            matches!(
+                err,
+                super::BumpError::MaxValue { ref value, .. } if value == "final"
+            ),
+            "unexpected error: {err:?}"
+        );
+
+        // dependent components are reset to their first value
+        let version = version.bump("patch")?;
+        sim_assert_eq!(version.get("release").and_then(|c| c.value()), Some("dev"));
+        sim_assert_eq!(serialize_release_version(&version)?, "1.2.4-dev");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_values_component_missing_optional_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let parse_regex = regex::Regex::new(
+            r"(?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+)(-(?P<release>[a-z]+))?",
+        )?;
+        let spec = release_version_spec();
+        let version = super::Version::parse("1.2.3", &parse_regex, &spec)
+            .ok_or_else(|| eyre::eyre!("failed to parse version"))?;
+
+        // a missing component takes its optional value
+        sim_assert_eq!(
+            version.get("release").and_then(|c| c.value()),
+            Some("final")
+        );
+        sim_assert_eq!(
+            version.required_component_names().collect::<Vec<_>>(),
+            vec!["major", "minor", "patch"]
+        );
+
+        let version = version.bump("patch")?;
+        sim_assert_eq!(serialize_release_version(&version)?, "1.2.4-dev");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_values_component_invalid_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let parse_regex = regex::Regex::new(
+            r"(?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+)(-(?P<release>[a-z]+))?",
+        )?;
+        let spec = release_version_spec();
+        let version = super::Version::parse("1.2.3-beta", &parse_regex, &spec)
+            .ok_or_else(|| eyre::eyre!("failed to parse version"))?;
+
+        let err = version.bump("release").unwrap_err();
+        assert!(
+            matches!(
+                err,
+                super::BumpError::InvalidValue { ref value, .. } if value == "beta"
+            ),
+            "unexpected error: {err:?}"
+        );
+        Ok(())
+    }
+
+    fn calver_version_spec(today: chrono::NaiveDate) -> super::VersionSpec {
+        let components: VersionComponentConfigs = [
+            (
+                "release",
+                VersionComponentSpec {
+                    calver_format: Some("{YYYY}.{0M}.{0D}".to_string()),
+                    always_increment: true,
+                    ..VersionComponentSpec::default()
+                },
+            ),
+            (
+                "patch",
+                VersionComponentSpec {
+                    first_value: Some("0".to_string()),
+                    ..VersionComponentSpec::default()
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|(name, spec)| (name.to_string(), spec))
+        .collect();
+        super::VersionSpec::from_components(components)
+            .with_clock(super::calver::Clock::Fixed(today))
+    }
+
+    fn serialize_calver_version(version: &super::Version) -> eyre::Result<String> {
+        let patterns = [PythonFormatString::parse("{release}.{patch}")?];
+        let ctx: HashMap<&str, &str> = HashMap::new();
+        Ok(version.serialize(&patterns, &ctx)?)
+    }
+
+    #[test]
+    fn test_reset_numeric_component_to_first_value() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let parse_regex =
+            regex::Regex::new(r"(?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+)\.(?P<build>\d+)")?;
+        let components: VersionComponentConfigs = [
+            ("major", VersionComponentSpec::default()),
+            ("minor", VersionComponentSpec::default()),
+            ("patch", VersionComponentSpec::default()),
+            (
+                "build",
+                VersionComponentSpec {
+                    first_value: Some("1".to_string()),
+                    ..VersionComponentSpec::default()
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|(name, spec)| (name.to_string(), spec))
+        .collect();
+        let spec = super::VersionSpec::from_components(components);
+        let version = super::Version::parse("1.2.3.5", &parse_regex, &spec)
+            .ok_or_else(|| eyre::eyre!("failed to parse version"))?;
+
+        // components without a first value are reset to zero
+        let version = version.bump("minor")?;
+        sim_assert_eq!(version.get("patch").and_then(|c| c.value()), Some("0"));
+        sim_assert_eq!(version.get("build").and_then(|c| c.value()), Some("1"));
+
+        let version = version.bump("build")?;
+        sim_assert_eq!(version.get("build").and_then(|c| c.value()), Some("2"));
+        let version = version.bump("patch")?;
+        sim_assert_eq!(version.get("build").and_then(|c| c.value()), Some("1"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_calver_codes() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 5)
+            .ok_or_else(|| eyre::eyre!("invalid date"))?;
+        let codes = super::calver::calver_codes(date);
+        let code = |code: &str| codes.get(code).map(String::as_str);
+        sim_assert_eq!(code("YYYY"), Some("2024"));
+        sim_assert_eq!(code("YY"), Some("24"));
+        sim_assert_eq!(code("0M"), Some("03"));
+        sim_assert_eq!(code("MM"), Some("3"));
+        sim_assert_eq!(code("0D"), Some("05"));
+        sim_assert_eq!(code("DD"), Some("5"));
+        sim_assert_eq!(code("WW"), Some("10"));
+        sim_assert_eq!(code("Q"), Some("1"));
+
+        let func = super::calver::CalVerFunction::new("{YY}.{WW}");
+        sim_assert_eq!(func.bump(date)?, "24.10");
+
+        let func = super::calver::CalVerFunction::new("{YYYY}.{unknown}");
+        assert!(func.bump(date).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_calver_component_same_day() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let parse_regex = regex::Regex::new(r"(?P<release>\d{4}\.\d{2}\.\d{2})\.(?P<patch>\d+)")?;
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 1, 15)
+            .ok_or_else(|| eyre::eyre!("invalid date"))?;
+        let spec = calver_version_spec(today);
+        let version = super::Version::parse("2024.01.15.3", &parse_regex, &spec)
+            .ok_or_else(|| eyre::eyre!("failed to parse version"))?;
+
+        // the date did not change, so the patch is not reset
+        let bumped = version.bump("patch")?;
+        sim_assert_eq!(serialize_calver_version(&bumped)?, "2024.01.15.4");
+
+        // bumping the calver component on the same day does not change anything
+        let bumped = version.bump("release")?;
+        sim_assert_eq!(serialize_calver_version(&bumped)?, "2024.01.15.3");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bump_calver_component_new_day() -> eyre::Result<()> {
+        crate::tests::init();
+
+        let parse_regex = regex::Regex::new(r"(?P<release>\d{4}\.\d{2}\.\d{2})\.(?P<patch>\d+)")?;
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 2, 1)
+            .ok_or_else(|| eyre::eyre!("invalid date"))?;
+        let spec = calver_version_spec(today);
+        let version = super::Version::parse("2024.01.15.3", &parse_regex, &spec)
+            .ok_or_else(|| eyre::eyre!("failed to parse version"))?;
+
+        // the date changed, so the patch is reset instead of bumped
+        let bumped = version.bump("patch")?;
+        sim_assert_eq!(serialize_calver_version(&bumped)?, "2024.02.01.0");
+
+        let bumped = version.bump("release")?;
+        sim_assert_eq!(serialize_calver_version(&bumped)?, "2024.02.01.0");
+        Ok(())
+    }
 
     #[test]
     fn test_parse_raw_version() -> eyre::Result<()> {